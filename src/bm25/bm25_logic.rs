@@ -0,0 +1,132 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub struct Bm25Logic;
+
+impl Bm25Logic {
+    pub fn build_bm25(
+        documents: &[Vec<String>],
+        k1: f32,
+        b: f32,
+        delta: f32,
+    ) -> (Vec<HashMap<String, f32>>, HashMap<String, f32>) {
+        let idf = Self::calculate_idf(documents);
+        let average_length =
+            documents.iter().map(|d| d.len()).sum::<usize>() as f32 / documents.len().max(1) as f32;
+
+        #[cfg(feature = "parallel")]
+        {
+            let document_scores = documents
+                .par_iter()
+                .map(|document| Self::score_document(document, &idf, average_length, k1, b, delta))
+                .collect::<Vec<HashMap<String, f32>>>();
+            (document_scores, idf)
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            let document_scores = documents
+                .iter()
+                .map(|document| Self::score_document(document, &idf, average_length, k1, b, delta))
+                .collect::<Vec<HashMap<String, f32>>>();
+            (document_scores, idf)
+        }
+    }
+
+    /// The non-negative idf of Lucene: `ln(1 + (N - df + 0.5) / (df + 0.5))`.
+    fn calculate_idf(documents: &[Vec<String>]) -> HashMap<String, f32> {
+        let total = documents.len() as f32;
+
+        documents
+            .iter()
+            .flat_map(|document| document.iter().collect::<HashSet<&String>>())
+            .fold(HashMap::<&String, f32>::new(), |mut acc, term| {
+                *acc.entry(term).or_insert(0.0) += 1.0;
+                acc
+            })
+            .into_iter()
+            .map(|(term, df)| {
+                (
+                    term.to_string(),
+                    (1.0 + (total - df + 0.5) / (df + 0.5)).ln(),
+                )
+            })
+            .collect()
+    }
+
+    fn score_document(
+        document: &[String],
+        idf: &HashMap<String, f32>,
+        average_length: f32,
+        k1: f32,
+        b: f32,
+        delta: f32,
+    ) -> HashMap<String, f32> {
+        let length_norm = 1.0 - b + b * document.len() as f32 / average_length.max(f32::EPSILON);
+
+        document
+            .iter()
+            .fold(HashMap::<&String, f32>::new(), |mut acc, term| {
+                *acc.entry(term).or_insert(0.0) += 1.0;
+                acc
+            })
+            .into_iter()
+            .map(|(term, tf)| {
+                let saturation = tf * (k1 + 1.0) / (tf + k1 * length_norm);
+                (
+                    term.to_string(),
+                    idf.get(term).unwrap_or(&0.0) * (saturation + delta),
+                )
+            })
+            .collect()
+    }
+
+    pub fn score_query(
+        query: &[String],
+        document_scores: &[HashMap<String, f32>],
+    ) -> Vec<(usize, f32)> {
+        // BM25+ only lower-bounds terms present in the document, missing terms add nothing
+        let score = |scores: &HashMap<String, f32>| {
+            query
+                .iter()
+                .filter_map(|term| scores.get(term))
+                .sum::<f32>()
+        };
+
+        #[cfg(feature = "parallel")]
+        let mut ranked = document_scores
+            .par_iter()
+            .map(score)
+            .enumerate()
+            .filter(|(_, score)| *score > 0.0)
+            .collect::<Vec<(usize, f32)>>();
+
+        #[cfg(not(feature = "parallel"))]
+        let mut ranked = document_scores
+            .iter()
+            .map(score)
+            .enumerate()
+            .filter(|(_, score)| *score > 0.0)
+            .collect::<Vec<(usize, f32)>>();
+
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::common::{Documents, Punctuation, Stopwords};
+
+type K1 = f32;
+type B = f32;
+type Delta = f32;
+
+/// The parameters to be used in the BM25 algorithm.
+pub enum Bm25Params<'a> {
+    /// ## Arguments
+    /// 1. `documents` - The unprocessed documents to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `k1` - The term frequency saturation, defaults to 1.2.
+    /// * `b` - The document length normalization, defaults to 0.75.
+    /// * `delta` - The BM25+ lower bound of a term score, defaults to 0.0 (plain BM25).
+    WithDefaults(Documents<'a>, Stopwords<'a>),
+
+    /// ## Arguments
+    /// 1. `documents` - The unprocessed documents to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `delta` - The BM25+ lower bound of a term score, usually 1.0.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `k1` - The term frequency saturation, defaults to 1.2.
+    /// * `b` - The document length normalization, defaults to 0.75.
+    WithDefaultsAndDelta(Documents<'a>, Stopwords<'a>, Delta),
+
+    /// ## Arguments
+    /// 1. `documents` - The unprocessed documents to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `punctuation` - Optional list of punctuation symbols.
+    /// 4. `k1` - The term frequency saturation.
+    /// 5. `b` - The document length normalization, from 0.0 to 1.0.
+    /// 6. `delta` - The BM25+ lower bound of a term score, 0.0 for plain BM25.
+    All(Documents<'a>, Stopwords<'a>, Punctuation<'a>, K1, B, Delta),
+}
+
+impl<'a> Bm25Params<'a> {
+    /// Returns the params to be used in the BM25 algorithm.
+    pub fn get_params(&self) -> (Documents<'_>, Stopwords<'_>, Punctuation<'_>, K1, B, Delta) {
+        match self {
+            Bm25Params::WithDefaults(documents, stop_words) => {
+                (documents, stop_words, None, 1.2, 0.75, 0.0)
+            }
+            Bm25Params::WithDefaultsAndDelta(documents, stop_words, delta) => {
+                (documents, stop_words, None, 1.2, 0.75, *delta)
+            }
+            Bm25Params::All(documents, stop_words, punctuation, k1, b, delta) => (
+                documents,
+                stop_words,
+                *punctuation,
+                *k1,
+                (*b).clamp(0.0, 1.0),
+                *delta,
+            ),
+        }
+    }
+}
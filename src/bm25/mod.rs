@@ -0,0 +1,94 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+mod bm25_logic;
+pub mod bm25_params;
+use bm25_logic::Bm25Logic;
+pub use bm25_params::Bm25Params;
+
+use crate::{
+    common::{get_ranked_scores, get_ranked_strings},
+    tf_idf::DocumentProcessor,
+};
+
+pub struct Bm25 {
+    document_scores: Vec<HashMap<String, f32>>,
+    idf: HashMap<String, f32>,
+    document_processor: DocumentProcessor,
+}
+
+impl Bm25 {
+    /// Create a new Bm25 instance.
+    pub fn new(params: Bm25Params) -> Self {
+        let (documents, stop_words, punctuation, k1, b, delta) = params.get_params();
+        let document_processor = DocumentProcessor::new(stop_words, punctuation);
+        let (document_scores, idf) = Bm25Logic::build_bm25(
+            &document_processor.split_documents_into_terms(documents),
+            k1,
+            b,
+            delta,
+        );
+
+        Self {
+            document_scores,
+            idf,
+            document_processor,
+        }
+    }
+
+    /// Gets the score of a term in a document, 0.0 if the term is not in the document.
+    pub fn get_term_score(&self, document: usize, term: &str) -> f32 {
+        self.document_scores
+            .get(document)
+            .and_then(|scores| scores.get(term))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Gets the top n terms of a document with the highest score.
+    pub fn get_ranked_terms(&self, document: usize, n: usize) -> Vec<String> {
+        self.document_scores
+            .get(document)
+            .map_or_else(Vec::new, |scores| get_ranked_strings(scores, n))
+    }
+
+    /// Gets the top n terms of a document with the highest score and their score.
+    pub fn get_ranked_term_scores(&self, document: usize, n: usize) -> Vec<(String, f32)> {
+        self.document_scores
+            .get(document)
+            .map_or_else(Vec::new, |scores| get_ranked_scores(scores, n))
+    }
+
+    /// Scores every document against a query, returning the indexes and scores of the matching
+    /// documents from the most to the least relevant.
+    pub fn score_query(&self, query: &str) -> Vec<(usize, f32)> {
+        Bm25Logic::score_query(
+            &self.document_processor.split_into_terms(query),
+            &self.document_scores,
+        )
+    }
+
+    /// Gets the IDF weight of a term in the vocabulary.
+    pub fn get_idf(&self, term: &str) -> Option<f32> {
+        self.idf.get(term).copied()
+    }
+
+    /// Gets the term scores map of a document.
+    pub fn get_term_scores_map(&self, document: usize) -> Option<&HashMap<String, f32>> {
+        self.document_scores.get(document)
+    }
+}
@@ -0,0 +1,647 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{CoOccurrenceMatrix, CoOccurrenceOptions, ContextScope};
+use crate::tokenizer::{ParagraphSplit, Tokenize};
+
+/// The extra random directions sampled by the randomized SVD, improving its accuracy.
+const SVD_OVERSAMPLING: usize = 10;
+const SVD_POWER_ITERATIONS: usize = 2;
+const SVD_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+const SVD_TOLERANCE: f32 = 1e-10;
+const JACOBI_SWEEPS: usize = 50;
+/// The documents counted in parallel before their partial counts are merged into the matrix,
+/// bounding the memory held by the partial counts.
+#[cfg(feature = "parallel")]
+const DOCUMENTS_CHUNK_SIZE: usize = 1_024;
+
+pub struct CoOccurrenceLogic;
+
+impl CoOccurrenceLogic {
+    pub fn create_words_indexes(words: &[String]) -> HashMap<String, usize> {
+        #[cfg(feature = "parallel")]
+        {
+            words
+                .par_iter()
+                .enumerate()
+                .map(|(i, w)| (w.to_string(), i))
+                .collect::<HashMap<String, usize>>()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| (w.to_string(), i))
+                .collect::<HashMap<String, usize>>()
+        }
+    }
+
+    /// Gives every word of the vocabulary, tokenized like the documents, its label. The first word wins
+    /// when several are tokenized the same.
+    fn create_phrase_indexes<T, F>(
+        words_indexes: &HashMap<String, usize>,
+        tokenize: &F,
+    ) -> HashMap<String, usize>
+    where
+        T: Tokenize,
+        F: Fn(&str) -> T,
+    {
+        let mut words = words_indexes.iter().collect::<Vec<(&String, &usize)>>();
+        words.sort_by_key(|(_, label)| **label);
+
+        words
+            .into_iter()
+            .fold(HashMap::new(), |mut phrase_indexes, (word, label)| {
+                let phrase = tokenize(word).words().join(" ");
+
+                if !phrase.is_empty() {
+                    phrase_indexes.entry(phrase).or_insert(*label);
+                }
+                phrase_indexes
+            })
+    }
+
+    /// Adds the co-occurrences of the words within the window of each other to the matrix, or only of
+    /// the words following each word when directed. The documents are split into phrases by the
+    /// tokenizer, and the words can be multi-word phrases, matched inside them and counted as a single
+    /// position of the window. With the `parallel` feature the documents are counted in parallel by
+    /// chunks, into partial `f64` counts merged into the matrix after every chunk.
+    pub fn count_documents<T, F>(
+        matrix: &mut CoOccurrenceMatrix,
+        documents: &[String],
+        words_indexes: &HashMap<String, usize>,
+        window_size: usize,
+        options: &CoOccurrenceOptions,
+        tokenize: F,
+    ) where
+        T: Tokenize,
+        F: Fn(&str) -> T + Sync,
+    {
+        let phrase_indexes = Self::create_phrase_indexes(words_indexes, &tokenize);
+        let max_phrase_length = phrase_indexes
+            .keys()
+            .map(|phrase| phrase.split_whitespace().count())
+            .max()
+            .unwrap_or(1);
+
+        #[cfg(feature = "parallel")]
+        {
+            documents.chunks(DOCUMENTS_CHUNK_SIZE).for_each(|chunk| {
+                chunk
+                    .par_iter()
+                    .fold(HashMap::<(usize, usize), f64>::new, |mut counts, doc| {
+                        Self::count_document(
+                            doc,
+                            &phrase_indexes,
+                            max_phrase_length,
+                            window_size,
+                            options,
+                            &tokenize,
+                            |row, column, count| {
+                                *counts.entry((row, column)).or_insert(0.0) += count as f64
+                            },
+                        );
+                        counts
+                    })
+                    .reduce(HashMap::new, |mut counts, partial_counts| {
+                        partial_counts
+                            .into_iter()
+                            .for_each(|(cell, count)| *counts.entry(cell).or_insert(0.0) += count);
+                        counts
+                    })
+                    .into_iter()
+                    .for_each(|((row, column), count)| matrix.add(row, column, count as f32))
+            });
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            documents.iter().for_each(|doc| {
+                Self::count_document(
+                    doc,
+                    &phrase_indexes,
+                    max_phrase_length,
+                    window_size,
+                    options,
+                    &tokenize,
+                    |row, column, count| matrix.add(row, column, count),
+                )
+            });
+        }
+    }
+
+    /// Matches the longest phrase of the vocabulary at every word of the tokenizer phrases, never
+    /// across two of them, and returns the label of every match, `None` for the words out of the
+    /// vocabulary.
+    fn get_phrase_labels(
+        phrases: &[String],
+        phrase_indexes: &HashMap<String, usize>,
+        max_phrase_length: usize,
+    ) -> Vec<Option<usize>> {
+        let mut labels = Vec::<Option<usize>>::new();
+
+        phrases.iter().for_each(|phrase| {
+            let phrase_words = phrase.split_whitespace().collect::<Vec<&str>>();
+            let mut i = 0;
+
+            while i < phrase_words.len() {
+                let matched = (2..=max_phrase_length.min(phrase_words.len() - i))
+                    .rev()
+                    .find_map(|length| {
+                        phrase_indexes
+                            .get(&phrase_words[i..i + length].join(" "))
+                            .map(|label| (length, *label))
+                    });
+
+                match matched {
+                    Some((length, label)) => {
+                        labels.push(Some(label));
+                        i += length;
+                    }
+                    None => {
+                        labels.push(phrase_indexes.get(phrase_words[i]).copied());
+                        i += 1;
+                    }
+                }
+            }
+        });
+
+        labels
+    }
+
+    fn count_document<T, F, A>(
+        doc: &str,
+        phrase_indexes: &HashMap<String, usize>,
+        max_phrase_length: usize,
+        window_size: usize,
+        options: &CoOccurrenceOptions,
+        tokenize: &F,
+        mut add: A,
+    ) where
+        T: Tokenize,
+        F: Fn(&str) -> T,
+        A: FnMut(usize, usize, f32),
+    {
+        if options.context_scope != ContextScope::Window {
+            Self::get_contexts(doc, options.context_scope).for_each(|context| {
+                Self::count_context(
+                    &tokenize(context).phrases(None),
+                    phrase_indexes,
+                    max_phrase_length,
+                    options.directed,
+                    &mut add,
+                )
+            });
+            return;
+        }
+
+        let doc_labels = Self::get_phrase_labels(
+            &tokenize(doc).phrases(None),
+            phrase_indexes,
+            max_phrase_length,
+        );
+        doc_labels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, label)| label.map(|first_index| (i, first_index)))
+            .for_each(|(i, first_index)| {
+                Self::get_window_range(window_size, i, doc_labels.len(), options.directed)
+                    .filter_map(|j| {
+                        if i == j {
+                            return None;
+                        }
+
+                        doc_labels
+                            .get(j)
+                            .copied()
+                            .flatten()
+                            .map(|other_index| (j, other_index))
+                    })
+                    .for_each(|(j, other_index)| {
+                        add(
+                            first_index,
+                            other_index,
+                            options.window_weighting.weight(i.abs_diff(j)),
+                        )
+                    });
+            });
+    }
+
+    /// Splits a document into the sentences, paragraphs or the whole document sharing a context.
+    fn get_contexts<'a>(
+        doc: &'a str,
+        scope: ContextScope,
+    ) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        match scope {
+            ContextScope::Sentence => Box::new(doc.unicode_sentences()),
+            ContextScope::Paragraph => Box::new(ParagraphSplit::Lines.split(doc).into_iter()),
+            ContextScope::Window | ContextScope::Document => Box::new(std::iter::once(doc)),
+        }
+    }
+
+    /// Adds a co-occurrence of every pair of distinct words in a context, once however often they
+    /// appear, or only of the words first appearing after each word when directed.
+    fn count_context<A>(
+        phrases: &[String],
+        phrase_indexes: &HashMap<String, usize>,
+        max_phrase_length: usize,
+        directed: bool,
+        add: &mut A,
+    ) where
+        A: FnMut(usize, usize, f32),
+    {
+        let mut seen = HashSet::<usize>::new();
+        let labels = Self::get_phrase_labels(phrases, phrase_indexes, max_phrase_length)
+            .into_iter()
+            .flatten()
+            .filter(|label| seen.insert(*label))
+            .collect::<Vec<usize>>();
+
+        labels.iter().enumerate().for_each(|(i, first_index)| {
+            labels
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| i != *j && (!directed || i < *j))
+                .for_each(|(_, other_index)| add(*first_index, *other_index, 1.0))
+        });
+    }
+
+    /// Multiplies a matrix given as the non-zero entries of its rows by its transpose, every cell
+    /// being the dot product of two rows.
+    pub fn multiply_by_transpose(rows: &[Vec<(usize, f32)>]) -> Vec<Vec<f32>> {
+        let length = rows.len();
+        let mut columns = vec![Vec::<(usize, f32)>::new(); length];
+        rows.iter().enumerate().for_each(|(row, entries)| {
+            entries
+                .iter()
+                .for_each(|(column, value)| columns[*column].push((row, *value)))
+        });
+        let multiply_row = |entries: &Vec<(usize, f32)>| {
+            let mut products = vec![0.0_f32; length];
+            entries.iter().for_each(|(column, value)| {
+                columns[*column]
+                    .iter()
+                    .for_each(|(other, other_value)| products[*other] += value * other_value)
+            });
+            products
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            rows.par_iter().map(multiply_row).collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            rows.iter().map(multiply_row).collect()
+        }
+    }
+
+    /// Groups the nodes of an undirected weighted graph, given as the sorted neighbours of every node,
+    /// into communities with the Louvain method, returning the community of every node. A higher
+    /// resolution gives more and smaller communities.
+    pub fn detect_communities(adjacency: &[Vec<(usize, f32)>], resolution: f32) -> Vec<usize> {
+        let mut communities = (0..adjacency.len()).collect::<Vec<usize>>();
+        let mut graph = adjacency.to_vec();
+
+        loop {
+            let node_communities = Self::move_nodes(&graph, resolution);
+            let community_count = node_communities.iter().max().map_or(0, |max| max + 1);
+
+            if community_count == graph.len() {
+                break;
+            }
+
+            communities
+                .iter_mut()
+                .for_each(|community| *community = node_communities[*community]);
+            graph = Self::aggregate_graph(&graph, &node_communities, community_count);
+        }
+
+        communities
+    }
+
+    /// Moves every node to the neighbouring community with the highest modularity gain until no move
+    /// improves it, returning the renumbered community of every node.
+    fn move_nodes(graph: &[Vec<(usize, f32)>], resolution: f32) -> Vec<usize> {
+        let degrees = graph
+            .iter()
+            .map(|neighbours| neighbours.iter().map(|(_, weight)| weight).sum::<f32>())
+            .collect::<Vec<f32>>();
+        let total_weight = degrees.iter().sum::<f32>();
+        let mut communities = (0..graph.len()).collect::<Vec<usize>>();
+        let mut community_degrees = degrees.clone();
+
+        if total_weight == 0.0 {
+            return communities;
+        }
+
+        loop {
+            let mut improved = false;
+
+            for node in 0..graph.len() {
+                let current = communities[node];
+                community_degrees[current] -= degrees[node];
+                let mut links = HashMap::<usize, f32>::new();
+                graph[node]
+                    .iter()
+                    .filter(|(neighbour, _)| *neighbour != node)
+                    .for_each(|(neighbour, weight)| {
+                        *links.entry(communities[*neighbour]).or_insert(0.0) += weight
+                    });
+                let gain = |community: usize| {
+                    links.get(&community).copied().unwrap_or(0.0)
+                        - resolution * community_degrees[community] * degrees[node] / total_weight
+                };
+                let mut candidates = links.keys().copied().collect::<Vec<usize>>();
+                candidates.sort_unstable();
+                let (best, _) = candidates.into_iter().fold(
+                    (current, gain(current)),
+                    |(best, best_gain), community| {
+                        let community_gain = gain(community);
+                        if community_gain > best_gain + f32::EPSILON {
+                            (community, community_gain)
+                        } else {
+                            (best, best_gain)
+                        }
+                    },
+                );
+                community_degrees[best] += degrees[node];
+
+                if best != current {
+                    communities[node] = best;
+                    improved = true;
+                }
+            }
+
+            if !improved {
+                break;
+            }
+        }
+
+        let mut renumbered = HashMap::<usize, usize>::new();
+        communities
+            .into_iter()
+            .map(|community| {
+                let next = renumbered.len();
+                *renumbered.entry(community).or_insert(next)
+            })
+            .collect()
+    }
+
+    /// Merges the nodes of every community into a single node, the weights between communities being
+    /// the sums of the weights between their nodes.
+    fn aggregate_graph(
+        graph: &[Vec<(usize, f32)>],
+        communities: &[usize],
+        community_count: usize,
+    ) -> Vec<Vec<(usize, f32)>> {
+        let mut weights = vec![HashMap::<usize, f32>::new(); community_count];
+        graph.iter().enumerate().for_each(|(node, neighbours)| {
+            neighbours.iter().for_each(|(neighbour, weight)| {
+                *weights[communities[node]]
+                    .entry(communities[*neighbour])
+                    .or_insert(0.0) += weight
+            })
+        });
+
+        weights
+            .into_iter()
+            .map(|neighbours| {
+                let mut neighbours = neighbours.into_iter().collect::<Vec<(usize, f32)>>();
+                neighbours.sort_by_key(|(neighbour, _)| *neighbour);
+                neighbours
+            })
+            .collect()
+    }
+
+    /// Factorizes a square matrix, given as the non-zero entries of its rows, with a randomized
+    /// truncated SVD, returning the first `dimensions` left singular vectors of every row scaled by the
+    /// square root of their singular values.
+    pub fn factorize(rows: &[Vec<(usize, f32)>], dimensions: usize) -> Vec<Vec<f32>> {
+        let length = rows.len();
+        let dimensions = dimensions.min(length);
+        let sketch_size = (dimensions + SVD_OVERSAMPLING).min(length);
+        let mut seed = SVD_SEED;
+        let mut random = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed >> 40) as f32 / (1_u64 << 23) as f32 - 1.0
+        };
+        let omega = (0..length)
+            .map(|_| (0..sketch_size).map(|_| random()).collect())
+            .collect::<Vec<Vec<f32>>>();
+
+        let mut q = Self::orthonormalize(Self::multiply(rows, &omega));
+        for _ in 0..SVD_POWER_ITERATIONS {
+            let z = Self::orthonormalize(Self::multiply_transposed(rows, &q, length));
+            q = Self::orthonormalize(Self::multiply(rows, &z));
+        }
+
+        // B = Qᵀ·A is small, its singular values come from the eigenvalues of B·Bᵀ = Zᵀ·Z
+        let z = Self::multiply_transposed(rows, &q, length);
+        let gram = (0..sketch_size)
+            .map(|a| {
+                (0..sketch_size)
+                    .map(|b| z.iter().map(|row| row[a] * row[b]).sum::<f32>())
+                    .collect::<Vec<f32>>()
+            })
+            .collect::<Vec<Vec<f32>>>();
+        let (eigenvalues, eigenvectors) = Self::get_eigen_decomposition(gram);
+        let mut order = (0..sketch_size).collect::<Vec<usize>>();
+        order.sort_by(|a, b| eigenvalues[*b].total_cmp(&eigenvalues[*a]));
+        let scales = order
+            .iter()
+            .take(dimensions)
+            .map(|component| eigenvalues[*component].max(0.0).sqrt().sqrt())
+            .collect::<Vec<f32>>();
+
+        q.iter()
+            .map(|row| {
+                order
+                    .iter()
+                    .take(dimensions)
+                    .zip(&scales)
+                    .map(|(component, scale)| {
+                        row.iter()
+                            .enumerate()
+                            .map(|(c, value)| value * eigenvectors[c][*component])
+                            .sum::<f32>()
+                            * scale
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Multiplies a sparse square matrix by a dense one.
+    fn multiply(rows: &[Vec<(usize, f32)>], dense: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let width = dense.first().map_or(0, Vec::len);
+        let multiply_row = |entries: &Vec<(usize, f32)>| {
+            let mut product = vec![0.0_f32; width];
+            entries.iter().for_each(|(column, value)| {
+                product
+                    .iter_mut()
+                    .zip(&dense[*column])
+                    .for_each(|(cell, other)| *cell += value * other)
+            });
+            product
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            rows.par_iter().map(multiply_row).collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            rows.iter().map(multiply_row).collect()
+        }
+    }
+
+    /// Multiplies the transpose of a sparse square matrix by a dense one.
+    fn multiply_transposed(
+        rows: &[Vec<(usize, f32)>],
+        dense: &[Vec<f32>],
+        length: usize,
+    ) -> Vec<Vec<f32>> {
+        let width = dense.first().map_or(0, Vec::len);
+        let mut product = vec![vec![0.0_f32; width]; length];
+        rows.iter().enumerate().for_each(|(row, entries)| {
+            entries.iter().for_each(|(column, value)| {
+                product[*column]
+                    .iter_mut()
+                    .zip(&dense[row])
+                    .for_each(|(cell, other)| *cell += value * other)
+            })
+        });
+        product
+    }
+
+    /// Makes the columns of a matrix orthonormal with the modified Gram-Schmidt process, columns
+    /// dependent on the previous ones become zero.
+    fn orthonormalize(mut matrix: Vec<Vec<f32>>) -> Vec<Vec<f32>> {
+        let width = matrix.first().map_or(0, Vec::len);
+
+        for column in 0..width {
+            for previous in 0..column {
+                let dot = matrix
+                    .iter()
+                    .map(|row| row[column] * row[previous])
+                    .sum::<f32>();
+                matrix
+                    .iter_mut()
+                    .for_each(|row| row[column] -= dot * row[previous]);
+            }
+
+            let norm = matrix
+                .iter()
+                .map(|row| row[column] * row[column])
+                .sum::<f32>()
+                .sqrt();
+            matrix.iter_mut().for_each(|row| {
+                row[column] = if norm > SVD_TOLERANCE {
+                    row[column] / norm
+                } else {
+                    0.0
+                }
+            });
+        }
+
+        matrix
+    }
+
+    /// The eigenvalues and eigenvectors, as columns, of a symmetric matrix with the cyclic Jacobi
+    /// method.
+    fn get_eigen_decomposition(mut matrix: Vec<Vec<f32>>) -> (Vec<f32>, Vec<Vec<f32>>) {
+        let size = matrix.len();
+        let mut eigenvectors = (0..size)
+            .map(|i| (0..size).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+            .collect::<Vec<Vec<f32>>>();
+
+        for _ in 0..JACOBI_SWEEPS {
+            let off_diagonal = (0..size)
+                .flat_map(|i| (0..size).filter(move |j| *j != i).map(move |j| (i, j)))
+                .map(|(i, j)| matrix[i][j] * matrix[i][j])
+                .sum::<f32>();
+
+            if off_diagonal < SVD_TOLERANCE {
+                break;
+            }
+
+            for p in 0..size {
+                for q in p + 1..size {
+                    if matrix[p][q].abs() < f32::MIN_POSITIVE {
+                        continue;
+                    }
+
+                    let theta = (matrix[q][q] - matrix[p][p]) / (2.0 * matrix[p][q]);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                    let c = 1.0 / (t * t + 1.0).sqrt();
+                    let s = t * c;
+
+                    let rotate_columns = |rows: &mut Vec<Vec<f32>>| {
+                        rows.iter_mut().for_each(|row| {
+                            let (vp, vq) = (row[p], row[q]);
+                            row[p] = c * vp - s * vq;
+                            row[q] = s * vp + c * vq;
+                        })
+                    };
+                    rotate_columns(&mut matrix);
+                    rotate_columns(&mut eigenvectors);
+                    let (upper, lower) = matrix.split_at_mut(q);
+                    upper[p]
+                        .iter_mut()
+                        .zip(lower[0].iter_mut())
+                        .for_each(|(pk, qk)| {
+                            let (vp, vq) = (*pk, *qk);
+                            *pk = c * vp - s * vq;
+                            *qk = s * vp + c * vq;
+                        });
+                }
+            }
+        }
+
+        ((0..size).map(|i| matrix[i][i]).collect(), eigenvectors)
+    }
+
+    fn get_window_range(
+        window_size: usize,
+        index: usize,
+        words_length: usize,
+        directed: bool,
+    ) -> Range<usize> {
+        let window_start = if directed {
+            index + 1
+        } else {
+            index.saturating_sub(window_size)
+        };
+        let window_end = (index + window_size + 1).min(words_length);
+        window_start..window_end
+    }
+}
@@ -0,0 +1,522 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, mem};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "memmap2")]
+use std::{fs::OpenOptions, io, marker::PhantomData, ops::Range, path::Path};
+
+#[cfg(feature = "memmap2")]
+use memmap2::MmapMut;
+
+use super::{CoOccurrenceWeighting, MatrixStorage};
+
+/// Vocabularies above this size are stored sparsely with `MatrixStorage::Auto`, a dense matrix of
+/// them taking hundreds of megabytes.
+const SPARSE_THRESHOLD: usize = 5_000;
+
+/// A stored co-occurrence count.
+trait Count: Copy + Default + PartialEq {
+    /// The size in bytes of a count of a memory-mapped matrix.
+    #[cfg(feature = "memmap2")]
+    const SIZE: usize;
+
+    /// Adds a weight to the count, `None` when the sum overflows the count.
+    fn add_weight(self, weight: f32) -> Option<Self>;
+    fn to_weight(self) -> f32;
+    #[cfg(feature = "memmap2")]
+    fn from_bytes(bytes: &[u8]) -> Self;
+    #[cfg(feature = "memmap2")]
+    fn to_bytes(self, bytes: &mut [u8]);
+}
+
+impl Count for u16 {
+    #[cfg(feature = "memmap2")]
+    const SIZE: usize = 2;
+
+    fn add_weight(self, weight: f32) -> Option<Self> {
+        u32::from(self)
+            .checked_add(weight as u32)
+            .and_then(|count| u16::try_from(count).ok())
+    }
+
+    fn to_weight(self) -> f32 {
+        self as f32
+    }
+
+    #[cfg(feature = "memmap2")]
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let mut array = [0_u8; 2];
+        array.copy_from_slice(bytes);
+        u16::from_ne_bytes(array)
+    }
+
+    #[cfg(feature = "memmap2")]
+    fn to_bytes(self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&self.to_ne_bytes());
+    }
+}
+
+impl Count for u32 {
+    #[cfg(feature = "memmap2")]
+    const SIZE: usize = 4;
+
+    fn add_weight(self, weight: f32) -> Option<Self> {
+        Some(self.saturating_add(weight as u32))
+    }
+
+    fn to_weight(self) -> f32 {
+        self as f32
+    }
+
+    #[cfg(feature = "memmap2")]
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let mut array = [0_u8; 4];
+        array.copy_from_slice(bytes);
+        u32::from_ne_bytes(array)
+    }
+
+    #[cfg(feature = "memmap2")]
+    fn to_bytes(self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&self.to_ne_bytes());
+    }
+}
+
+impl Count for f32 {
+    #[cfg(feature = "memmap2")]
+    const SIZE: usize = 4;
+
+    fn add_weight(self, weight: f32) -> Option<Self> {
+        Some(self + weight)
+    }
+
+    fn to_weight(self) -> f32 {
+        self
+    }
+
+    #[cfg(feature = "memmap2")]
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let mut array = [0_u8; 4];
+        array.copy_from_slice(bytes);
+        f32::from_ne_bytes(array)
+    }
+
+    #[cfg(feature = "memmap2")]
+    fn to_bytes(self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&self.to_ne_bytes());
+    }
+}
+
+/// A dense matrix of counts in a memory-mapped file, so only the pages in use are held in memory.
+/// The range of the columns written in every row is kept to only read these columns.
+#[cfg(feature = "memmap2")]
+#[derive(Debug)]
+struct MappedCells<T> {
+    map: MmapMut,
+    length: usize,
+    columns: Vec<Range<usize>>,
+    count: PhantomData<T>,
+}
+
+#[cfg(feature = "memmap2")]
+impl<T: Count> MappedCells<T> {
+    /// Creates, or truncates, the file at the given path with room for every cell, `length` squared
+    /// times the size of a count, which most file systems store sparsely until written.
+    fn new(path: &Path, length: usize) -> io::Result<Self> {
+        let size = length
+            .checked_mul(length)
+            .and_then(|cells| cells.checked_mul(T::SIZE))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "The matrix is too large to be memory-mapped",
+                )
+            })?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(size as u64)?;
+
+        // SAFETY: the file was just created for the matrix and is only accessed through the map.
+        let map = unsafe { MmapMut::map_mut(&file)? };
+
+        Ok(Self {
+            map,
+            length,
+            columns: vec![0..0; length],
+            count: PhantomData,
+        })
+    }
+
+    fn get_offset(&self, row: usize, column: usize) -> usize {
+        (row * self.length + column) * T::SIZE
+    }
+
+    fn get(&self, row: usize, column: usize) -> T {
+        let offset = self.get_offset(row, column);
+        T::from_bytes(&self.map[offset..offset + T::SIZE])
+    }
+
+    fn set(&mut self, row: usize, column: usize, count: T) {
+        let offset = self.get_offset(row, column);
+        count.to_bytes(&mut self.map[offset..offset + T::SIZE]);
+
+        let columns = &mut self.columns[row];
+        *columns = if columns.start == columns.end {
+            column..column + 1
+        } else {
+            columns.start.min(column)..columns.end.max(column + 1)
+        };
+    }
+
+    /// Gets the non-zero counts of a row sorted by column, skipping the columns never written and
+    /// the zero cells without decoding them.
+    fn get_row_entries(&self, row: usize) -> Vec<(usize, T)> {
+        let columns = self.columns[row].clone();
+        let start = self.get_offset(row, columns.start);
+        let end = self.get_offset(row, columns.end);
+
+        self.map[start..end]
+            .chunks_exact(T::SIZE)
+            .zip(columns)
+            .filter(|(bytes, _)| bytes.iter().any(|byte| *byte != 0))
+            .map(|(bytes, column)| (column, T::from_bytes(bytes)))
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum Cells<T> {
+    Dense(Vec<Vec<T>>),
+    Sparse(Vec<HashMap<usize, T>>),
+    /// Serializing it returns an error, the file already being the saved matrix.
+    #[cfg(feature = "memmap2")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Mapped(MappedCells<T>),
+}
+
+/// A memory-mapped matrix is cloned into sparse rows in memory, as the clone has no file of its own.
+impl<T: Count> Clone for Cells<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Dense(rows) => Self::Dense(rows.clone()),
+            Self::Sparse(rows) => Self::Sparse(rows.clone()),
+            #[cfg(feature = "memmap2")]
+            Self::Mapped(cells) => Self::Sparse(
+                (0..cells.length)
+                    .map(|row| cells.get_row_entries(row).into_iter().collect())
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl<T: Count> Cells<T> {
+    fn new(length: usize, sparse: bool) -> Self {
+        if sparse {
+            Self::Sparse(vec![HashMap::new(); length])
+        } else {
+            Self::Dense(vec![vec![T::default(); length]; length])
+        }
+    }
+
+    /// Adds to the count of a cell and returns the new count, `None` when it overflows.
+    fn add(&mut self, row: usize, column: usize, weight: f32) -> Option<f32> {
+        let cell = match self {
+            Self::Dense(rows) => &mut rows[row][column],
+            Self::Sparse(rows) => rows[row].entry(column).or_default(),
+            #[cfg(feature = "memmap2")]
+            Self::Mapped(cells) => {
+                let count = cells.get(row, column).add_weight(weight)?;
+                cells.set(row, column, count);
+                return Some(count.to_weight());
+            }
+        };
+        *cell = cell.add_weight(weight)?;
+        Some(cell.to_weight())
+    }
+
+    fn get(&self, row: usize, column: usize) -> f32 {
+        match self {
+            Self::Dense(rows) => rows[row][column].to_weight(),
+            Self::Sparse(rows) => rows[row]
+                .get(&column)
+                .map_or(0.0, |count| count.to_weight()),
+            #[cfg(feature = "memmap2")]
+            Self::Mapped(cells) => cells.get(row, column).to_weight(),
+        }
+    }
+
+    fn get_row_entries(&self, row: usize) -> Vec<(usize, f32)> {
+        let zero = T::default();
+
+        match self {
+            Self::Dense(rows) => rows[row]
+                .iter()
+                .enumerate()
+                .filter(|(_, count)| **count != zero)
+                .map(|(column, count)| (column, count.to_weight()))
+                .collect(),
+            Self::Sparse(rows) => {
+                let mut entries = rows[row]
+                    .iter()
+                    .filter(|(_, count)| **count != zero)
+                    .map(|(column, count)| (*column, count.to_weight()))
+                    .collect::<Vec<(usize, f32)>>();
+                entries.sort_by_key(|(column, _)| *column);
+                entries
+            }
+            #[cfg(feature = "memmap2")]
+            Self::Mapped(cells) => cells
+                .get_row_entries(row)
+                .into_iter()
+                .filter(|(_, count)| *count != zero)
+                .map(|(column, count)| (column, count.to_weight()))
+                .collect(),
+        }
+    }
+}
+
+impl Cells<u16> {
+    /// Widens the counts to `u32`, once one of them overflows. Whole counts are never memory-mapped
+    /// as `u16`, the file being created for `u32` counts.
+    fn promote(self) -> Cells<u32> {
+        match self {
+            Self::Dense(rows) => Cells::Dense(
+                rows.into_iter()
+                    .map(|row| row.into_iter().map(u32::from).collect())
+                    .collect(),
+            ),
+            Self::Sparse(rows) => Cells::Sparse(
+                rows.into_iter()
+                    .map(|row| {
+                        row.into_iter()
+                            .map(|(column, count)| (column, u32::from(count)))
+                            .collect()
+                    })
+                    .collect(),
+            ),
+            #[cfg(feature = "memmap2")]
+            Self::Mapped(cells) => Cells::Sparse(
+                (0..cells.length)
+                    .map(|row| {
+                        cells
+                            .get_row_entries(row)
+                            .into_iter()
+                            .map(|(column, count)| (column, u32::from(count)))
+                            .collect()
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// The counts are whole numbers with `WindowWeighting::Flat` and fractions with the other window
+/// weightings. Whole counts start as `u16`, half the size of an `f32`, and are widened to `u32` when
+/// one of them overflows.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum Counts {
+    Short(Cells<u16>),
+    Integer(Cells<u32>),
+    Fractional(Cells<f32>),
+}
+
+/// The square matrix of the co-occurrence counts, either dense or as a map of the non-zero counts of
+/// every row. Whole counts are stored as integers, free of float accumulation errors, and only turned
+/// into `f32` weights when read. The totals needed by the weightings are kept up to date as counts are
+/// added.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoOccurrenceMatrix {
+    counts: Counts,
+    row_sums: Vec<f64>,
+    column_sums: Vec<f64>,
+    total: f64,
+    max: f32,
+}
+
+impl CoOccurrenceMatrix {
+    pub fn new(length: usize, storage: MatrixStorage, fractional: bool) -> Self {
+        let sparse = match storage {
+            MatrixStorage::Auto => length > SPARSE_THRESHOLD,
+            MatrixStorage::Dense => false,
+            MatrixStorage::Sparse => true,
+        };
+
+        Self {
+            counts: if fractional {
+                Counts::Fractional(Cells::new(length, sparse))
+            } else {
+                Counts::Short(Cells::new(length, sparse))
+            },
+            row_sums: vec![0.0; length],
+            column_sums: vec![0.0; length],
+            total: 0.0,
+            max: 0.0,
+        }
+    }
+
+    /// Creates a dense matrix backed by a memory-mapped file at the given path, for vocabularies too
+    /// large to hold their matrix in memory.
+    #[cfg(feature = "memmap2")]
+    pub fn with_memory_map(length: usize, fractional: bool, path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            counts: if fractional {
+                Counts::Fractional(Cells::Mapped(MappedCells::new(path, length)?))
+            } else {
+                Counts::Integer(Cells::Mapped(MappedCells::new(path, length)?))
+            },
+            row_sums: vec![0.0; length],
+            column_sums: vec![0.0; length],
+            total: 0.0,
+            max: 0.0,
+        })
+    }
+
+    #[cfg(feature = "memmap2")]
+    pub fn is_memory_mapped(&self) -> bool {
+        matches!(
+            self.counts,
+            Counts::Short(Cells::Mapped(_))
+                | Counts::Integer(Cells::Mapped(_))
+                | Counts::Fractional(Cells::Mapped(_))
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.row_sums.len()
+    }
+
+    pub fn is_sparse(&self) -> bool {
+        matches!(
+            self.counts,
+            Counts::Short(Cells::Sparse(_))
+                | Counts::Integer(Cells::Sparse(_))
+                | Counts::Fractional(Cells::Sparse(_))
+        )
+    }
+
+    /// Adds to the count of a cell.
+    pub fn add(&mut self, row: usize, column: usize, count: f32) {
+        let cell = match &mut self.counts {
+            Counts::Short(cells) => cells.add(row, column, count),
+            Counts::Integer(cells) => cells.add(row, column, count),
+            Counts::Fractional(cells) => cells.add(row, column, count),
+        };
+        let Some(cell) = cell else {
+            self.promote_counts();
+            return self.add(row, column, count);
+        };
+        self.max = self.max.max(cell);
+        self.row_sums[row] += count as f64;
+        self.column_sums[column] += count as f64;
+        self.total += count as f64;
+    }
+
+    /// Widens the `u16` counts to `u32`.
+    fn promote_counts(&mut self) {
+        if let Counts::Short(cells) = &mut self.counts {
+            let cells = mem::replace(cells, Cells::Sparse(Vec::new()));
+            self.counts = Counts::Integer(cells.promote());
+        }
+    }
+
+    fn get_count(&self, row: usize, column: usize) -> f32 {
+        match &self.counts {
+            Counts::Short(cells) => cells.get(row, column),
+            Counts::Integer(cells) => cells.get(row, column),
+            Counts::Fractional(cells) => cells.get(row, column),
+        }
+    }
+
+    /// Turns the count of a cell into the weight of the given variant.
+    fn weigh(
+        &self,
+        row: usize,
+        column: usize,
+        count: f32,
+        weighting: CoOccurrenceWeighting,
+    ) -> f32 {
+        if count == 0.0 {
+            return 0.0;
+        }
+
+        let count = count as f64;
+        let weight = match weighting {
+            CoOccurrenceWeighting::Raw => count,
+            CoOccurrenceWeighting::MaxNormalized => count / self.max as f64,
+            CoOccurrenceWeighting::RowNormalized => count / self.row_sums[row],
+            CoOccurrenceWeighting::Pmi | CoOccurrenceWeighting::Npmi => {
+                let joint = count / self.total;
+                let pmi = (joint
+                    / (self.row_sums[row] / self.total * self.column_sums[column] / self.total))
+                    .ln();
+
+                match (weighting, joint < 1.0) {
+                    (CoOccurrenceWeighting::Npmi, true) => pmi / -joint.ln(),
+                    (CoOccurrenceWeighting::Npmi, false) => 1.0,
+                    _ => pmi,
+                }
+            }
+        };
+
+        weight as f32
+    }
+
+    pub fn get(&self, row: usize, column: usize, weighting: CoOccurrenceWeighting) -> f32 {
+        self.weigh(row, column, self.get_count(row, column), weighting)
+    }
+
+    pub fn get_row(&self, row: usize, weighting: CoOccurrenceWeighting) -> Vec<f32> {
+        let mut values = vec![0.0_f32; self.len()];
+        self.get_row_entries(row, weighting)
+            .into_iter()
+            .for_each(|(column, value)| values[column] = value);
+        values
+    }
+
+    /// Gets the non-zero weights of a row sorted by column.
+    pub fn get_row_entries(
+        &self,
+        row: usize,
+        weighting: CoOccurrenceWeighting,
+    ) -> Vec<(usize, f32)> {
+        let mut entries = match &self.counts {
+            Counts::Short(cells) => cells.get_row_entries(row),
+            Counts::Integer(cells) => cells.get_row_entries(row),
+            Counts::Fractional(cells) => cells.get_row_entries(row),
+        };
+        entries
+            .iter_mut()
+            .for_each(|(column, value)| *value = self.weigh(row, *column, *value, weighting));
+        entries.retain(|(_, value)| *value != 0.0);
+        entries
+    }
+
+    pub fn to_dense(&self, weighting: CoOccurrenceWeighting) -> Vec<Vec<f32>> {
+        (0..self.len())
+            .map(|row| self.get_row(row, weighting))
+            .collect()
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How the co-occurrence matrix is stored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MatrixStorage {
+    /// Dense for small vocabularies and sparse for large ones.
+    #[default]
+    Auto,
+    /// A `Vec<Vec<f32>>` of every word pair, taking O(V²) memory.
+    Dense,
+    /// A map of the non-zero values of every row, for large vocabularies.
+    Sparse,
+}
+
+/// What the values of the co-occurrence matrix are.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CoOccurrenceWeighting {
+    /// The number of co-occurrences, comparable across corpora.
+    Raw,
+    /// The counts divided by the highest count, between 0 and 1.
+    #[default]
+    MaxNormalized,
+    /// The counts divided by the total of their row, the probability of a word co-occurring with the
+    /// word of the row.
+    RowNormalized,
+    /// The pointwise mutual information `ln(p(a, b) / (p(a) * p(b)))`, how much more often two words
+    /// co-occur than by chance.
+    Pmi,
+    /// The PMI normalized by `-ln(p(a, b))`, between -1 and 1.
+    Npmi,
+}
+
+/// How much a co-occurrence within the window counts, by the distance between the words.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WindowWeighting {
+    /// Every co-occurrence counts 1.0.
+    #[default]
+    Flat,
+    /// A co-occurrence counts `1 / distance`.
+    InverseDistance,
+    /// A co-occurrence counts `exp(-rate * (distance - 1))`, 1.0 for adjacent words.
+    Exponential(f32),
+}
+
+impl WindowWeighting {
+    pub(crate) fn weight(&self, distance: usize) -> f32 {
+        match self {
+            Self::Flat => 1.0,
+            Self::InverseDistance => 1.0 / distance as f32,
+            Self::Exponential(rate) => (-rate * (distance as f32 - 1.0)).exp(),
+        }
+    }
+}
+
+/// What context two words have to share to co-occur.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ContextScope {
+    /// Within the window size of each other.
+    #[default]
+    Window,
+    /// In the same sentence of a document.
+    Sentence,
+    /// In the same paragraph, or line, of a document.
+    Paragraph,
+    /// In the same document.
+    Document,
+}
+
+/// The options of the co-occurrence matrix.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoOccurrenceOptions {
+    /// How the matrix is stored, defaults to `MatrixStorage::Auto`.
+    pub storage: MatrixStorage,
+    /// What the values of the matrix are, defaults to `CoOccurrenceWeighting::MaxNormalized`.
+    pub weighting: CoOccurrenceWeighting,
+    /// How much a co-occurrence counts by the distance of the words, defaults to
+    /// `WindowWeighting::Flat`.
+    pub window_weighting: WindowWeighting,
+    /// Only counts the words following each word within the context, so the row of a word holds the
+    /// words after it and its column the words before it, defaults to false.
+    pub directed: bool,
+    /// What context two words have to share to co-occur, defaults to `ContextScope::Window`. Outside of
+    /// the window every pair of words in a context counts once, whatever their distance.
+    pub context_scope: ContextScope,
+}
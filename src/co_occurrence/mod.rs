@@ -0,0 +1,481 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{cmp::Reverse, collections::HashMap, sync::OnceLock};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ndarray")]
+use ndarray::Array2;
+
+#[cfg(feature = "memmap2")]
+use std::{io, path::Path};
+
+mod co_occurrence_logic;
+mod co_occurrence_matrix;
+pub mod co_occurrence_params;
+mod word_embeddings;
+use co_occurrence_logic::CoOccurrenceLogic;
+use co_occurrence_matrix::CoOccurrenceMatrix;
+pub use co_occurrence_params::{
+    CoOccurrenceOptions, CoOccurrenceWeighting, ContextScope, MatrixStorage, WindowWeighting,
+};
+pub use word_embeddings::WordEmbeddings;
+
+pub use crate::common::GraphFormat;
+use crate::{
+    common::{export_graph, sparse_cosine_similarity, Documents, WindowSize},
+    tokenizer::{Tokenize, Tokenizer},
+};
+
+type Words<'a> = &'a [String];
+
+/// A co-occurrence matrix of words, with the `serde` feature it can be saved and loaded to reuse a matrix
+/// built from a large corpus.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoOccurrence {
+    matrix: CoOccurrenceMatrix,
+    words: Vec<String>,
+    words_indexes: HashMap<String, usize>,
+    window_size: WindowSize,
+    options: CoOccurrenceOptions,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    dense: OnceLock<Vec<Vec<f32>>>,
+}
+
+impl CoOccurrence {
+    /// Create a new CoOccurrence instance. The documents and words are tokenized by a `Tokenizer`
+    /// without stop words, so casing and punctuation do not prevent a match. The words can be
+    /// multi-word phrases, matched as a whole inside the phrases of the documents, longest phrase
+    /// first, to build keyphrase networks.
+    pub fn new(documents: Documents, words: Words, window_size: WindowSize) -> Self {
+        Self::with_options(
+            documents,
+            words,
+            window_size,
+            CoOccurrenceOptions::default(),
+        )
+    }
+
+    /// Create a new CoOccurrence instance with the given options.
+    pub fn with_options(
+        documents: Documents,
+        words: Words,
+        window_size: WindowSize,
+        options: CoOccurrenceOptions,
+    ) -> Self {
+        let mut co_occurrence = Self {
+            matrix: CoOccurrenceMatrix::new(
+                words.len(),
+                options.storage,
+                options.window_weighting != WindowWeighting::Flat,
+            ),
+            words: words.to_vec(),
+            words_indexes: CoOccurrenceLogic::create_words_indexes(words),
+            window_size,
+            options,
+            dense: OnceLock::new(),
+        };
+        co_occurrence.add_documents(documents);
+        co_occurrence
+    }
+
+    /// Create a new CoOccurrence instance splitting the documents, and the words, with a custom
+    /// tokenizer built for every text, e.g. to drop stop words or use phrase boundaries of its own.
+    /// The words are matched inside the phrases of the tokenizer, see `CoOccurrence::new`.
+    pub fn with_tokenizer<T, F>(
+        documents: Documents,
+        words: Words,
+        window_size: WindowSize,
+        options: CoOccurrenceOptions,
+        tokenizer: F,
+    ) -> Self
+    where
+        T: Tokenize,
+        F: Fn(&str) -> T + Sync,
+    {
+        let mut co_occurrence = Self::with_options(&[], words, window_size, options);
+        co_occurrence.add_documents_with_tokenizer(documents, tokenizer);
+        co_occurrence
+    }
+
+    /// Create a new CoOccurrence instance whose matrix is stored densely in a memory-mapped file at
+    /// the given path, created or truncated, for vocabularies of hundreds of thousands of words whose
+    /// matrix doesn't fit in memory. The storage option is ignored. A clone holds the matrix in memory
+    /// as sparse rows, and serializing the instance returns an error, the file being the saved matrix.
+    #[cfg(feature = "memmap2")]
+    pub fn with_memory_map(
+        documents: Documents,
+        words: Words,
+        window_size: WindowSize,
+        options: CoOccurrenceOptions,
+        path: &Path,
+    ) -> io::Result<Self> {
+        let mut co_occurrence = Self {
+            matrix: CoOccurrenceMatrix::with_memory_map(
+                words.len(),
+                options.window_weighting != WindowWeighting::Flat,
+                path,
+            )?,
+            words: words.to_vec(),
+            words_indexes: CoOccurrenceLogic::create_words_indexes(words),
+            window_size,
+            options,
+            dense: OnceLock::new(),
+        };
+        co_occurrence.add_documents(documents);
+        Ok(co_occurrence)
+    }
+
+    /// Add the co-occurrences of new documents to the matrix without rebuilding it, the weights
+    /// are computed when read.
+    pub fn add_documents(&mut self, documents: Documents) {
+        self.add_documents_with_tokenizer(documents, |text| Tokenizer::new(text, &[], None));
+    }
+
+    /// Add the co-occurrences of new documents split with a custom tokenizer, see
+    /// `CoOccurrence::with_tokenizer`.
+    pub fn add_documents_with_tokenizer<T, F>(&mut self, documents: Documents, tokenizer: F)
+    where
+        T: Tokenize,
+        F: Fn(&str) -> T + Sync,
+    {
+        CoOccurrenceLogic::count_documents(
+            &mut self.matrix,
+            documents,
+            &self.words_indexes,
+            self.window_size,
+            &self.options,
+            tokenizer,
+        );
+        self.dense = OnceLock::new();
+    }
+
+    /// Get the numeric label of a word.
+    pub fn get_label(&self, word: &str) -> Option<usize> {
+        self.words_indexes.get(word).map(|w| w.to_owned())
+    }
+
+    /// Get the word of a numeric label.
+    pub fn get_word(&self, label: usize) -> Option<String> {
+        self.words.get(label).map(|w| w.to_owned())
+    }
+
+    /// Get the matrix of the co-occurrence, densified on the first call and cached until new
+    /// documents are added. Prefer `get_matrix_row_entries` or `to_triplets` for large vocabularies
+    /// stored sparsely or memory-mapped.
+    pub fn get_matrix(&self) -> &Vec<Vec<f32>> {
+        self.dense
+            .get_or_init(|| self.matrix.to_dense(self.options.weighting))
+    }
+
+    /// Build an owned dense copy of the matrix, of `words.len()` squared values.
+    pub fn to_dense(&self) -> Vec<Vec<f32>> {
+        self.matrix.to_dense(self.options.weighting)
+    }
+
+    /// Get the non-zero values of the matrix as `(row, column, value)` triplets, sorted by row and
+    /// column.
+    pub fn to_triplets(&self) -> Vec<(usize, usize, f32)> {
+        (0..self.words.len())
+            .flat_map(|row| {
+                self.matrix
+                    .get_row_entries(row, self.options.weighting)
+                    .into_iter()
+                    .map(move |(column, value)| (row, column, value))
+            })
+            .collect()
+    }
+
+    /// Get the matrix as a dense `ndarray::Array2`.
+    #[cfg(feature = "ndarray")]
+    pub fn to_array2(&self) -> Array2<f32> {
+        let length = self.words.len();
+        let mut array = Array2::zeros((length, length));
+        self.to_triplets()
+            .into_iter()
+            .for_each(|(row, column, value)| array[[row, column]] = value);
+        array
+    }
+
+    /// Export the word network to the given format for tools like Gephi or Cytoscape, with the edges
+    /// of a weight of at least `min_weight` and the summed weight of these edges as the node scores.
+    /// The edges are directed in the directed mode.
+    pub fn export_graph(&self, format: GraphFormat, min_weight: f32) -> String {
+        let edges = self
+            .to_triplets()
+            .into_iter()
+            .filter(|(row, column, weight)| {
+                row != column
+                    && *weight > 0.0
+                    && *weight >= min_weight
+                    && (self.options.directed || row < column)
+            })
+            .collect::<Vec<(usize, usize, f32)>>();
+        let mut scores = vec![0.0_f32; self.words.len()];
+        edges.iter().for_each(|(row, column, weight)| {
+            scores[*row] += weight;
+            if !self.options.directed {
+                scores[*column] += weight;
+            }
+        });
+        let nodes = self
+            .words
+            .iter()
+            .cloned()
+            .zip(scores)
+            .collect::<Vec<(String, f32)>>();
+        let edges = edges
+            .into_iter()
+            .map(|(row, column, weight)| {
+                (
+                    self.words[row].to_owned(),
+                    self.words[column].to_owned(),
+                    weight,
+                )
+            })
+            .collect::<Vec<(String, String, f32)>>();
+
+        export_graph(
+            "co_occurrence",
+            &nodes,
+            &edges,
+            format,
+            self.options.directed,
+        )
+    }
+
+    /// Whether the matrix is stored sparsely.
+    pub fn is_sparse(&self) -> bool {
+        self.matrix.is_sparse()
+    }
+
+    /// Whether the matrix is stored in a memory-mapped file.
+    #[cfg(feature = "memmap2")]
+    pub fn is_memory_mapped(&self) -> bool {
+        self.matrix.is_memory_mapped()
+    }
+
+    /// Get the labels of the co-occurrence.
+    pub fn get_labels(&self) -> &HashMap<String, usize> {
+        &self.words_indexes
+    }
+
+    /// Get all relations of a given word.
+    pub fn get_relations(&self, word: &str) -> Option<Vec<(String, f32)>> {
+        let label = self.get_label(word)?;
+
+        #[cfg(feature = "parallel")]
+        {
+            Some(
+                self.matrix
+                    .get_row_entries(label, self.options.weighting)
+                    .into_par_iter()
+                    .filter_map(|(i, v)| {
+                        if v > 0.0 {
+                            if let Some(w) = self.get_word(i) {
+                                return Some((w, v));
+                            }
+                        }
+
+                        None
+                    })
+                    .collect::<Vec<(String, f32)>>(),
+            )
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            Some(
+                self.matrix
+                    .get_row_entries(label, self.options.weighting)
+                    .into_iter()
+                    .filter_map(|(i, v)| {
+                        if v > 0.0 {
+                            if let Some(w) = self.get_word(i) {
+                                return Some((w, v));
+                            }
+                        }
+
+                        None
+                    })
+                    .collect::<Vec<(String, f32)>>(),
+            )
+        }
+    }
+
+    /// Get the k strongest relations of a given word sorted by weight, optionally only the ones with
+    /// at least a minimum weight.
+    pub fn get_top_relations(
+        &self,
+        word: &str,
+        k: usize,
+        min_weight: Option<f32>,
+    ) -> Option<Vec<(String, f32)>> {
+        let mut relations = self.get_relations(word)?;
+        relations.retain(|(_, weight)| min_weight.map_or(true, |min_weight| *weight >= min_weight));
+        relations.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        relations.truncate(k);
+        Some(relations)
+    }
+
+    /// Get the row of a given word.
+    pub fn get_matrix_row(&self, word: &str) -> Option<Vec<f32>> {
+        let label = self.get_label(word)?;
+        Some(self.matrix.get_row(label, self.options.weighting))
+    }
+
+    /// Get the non-zero values of the row of a given word as `(column, value)` pairs, sorted by
+    /// column, without densifying the row.
+    pub fn get_matrix_row_entries(&self, word: &str) -> Option<Vec<(usize, f32)>> {
+        let label = self.get_label(word)?;
+        Some(self.matrix.get_row_entries(label, self.options.weighting))
+    }
+
+    /// Get the cosine similarity of the rows of two words, how alike the words they co-occur with are.
+    pub fn similarity(&self, word1: &str, word2: &str) -> Option<f32> {
+        let label1 = self.get_label(word1)?;
+        let label2 = self.get_label(word2)?;
+        Some(sparse_cosine_similarity(
+            &self.matrix.get_row_entries(label1, self.options.weighting),
+            &self.matrix.get_row_entries(label2, self.options.weighting),
+        ))
+    }
+
+    /// Get the second-order co-occurrence matrix, the matrix multiplied by its transpose, where two
+    /// words are related by how much the words they co-occur with overlap rather than by co-occurring
+    /// themselves.
+    pub fn get_second_order_matrix(&self) -> Vec<Vec<f32>> {
+        CoOccurrenceLogic::multiply_by_transpose(
+            &(0..self.words.len())
+                .map(|row| self.matrix.get_row_entries(row, self.options.weighting))
+                .collect::<Vec<Vec<(usize, f32)>>>(),
+        )
+    }
+
+    /// Get the second-order relation between two words, the dot product of their rows.
+    pub fn get_second_order_relation(&self, word1: &str, word2: &str) -> Option<f32> {
+        let label1 = self.get_label(word1)?;
+        let label2 = self.get_label(word2)?;
+        let row2 = self.matrix.get_row(label2, self.options.weighting);
+        Some(
+            self.matrix
+                .get_row_entries(label1, self.options.weighting)
+                .into_iter()
+                .map(|(column, value)| value * row2[column])
+                .sum(),
+        )
+    }
+
+    /// Group the words into topical communities with the Louvain method over the co-occurrence graph,
+    /// whose edges weigh the mean of the positive relations in both directions. A resolution above 1.0
+    /// gives more and smaller communities, below 1.0 fewer and larger ones. The communities are sorted
+    /// from the largest to the smallest, and their words by label.
+    pub fn communities(&self, resolution: f32) -> Vec<Vec<String>> {
+        let mut weights = vec![HashMap::<usize, f32>::new(); self.words.len()];
+        self.to_triplets()
+            .into_iter()
+            .filter(|(row, column, value)| row != column && *value > 0.0)
+            .for_each(|(row, column, value)| {
+                *weights[row].entry(column).or_insert(0.0) += value / 2.0;
+                *weights[column].entry(row).or_insert(0.0) += value / 2.0;
+            });
+        let adjacency = weights
+            .into_iter()
+            .map(|neighbours| {
+                let mut neighbours = neighbours.into_iter().collect::<Vec<(usize, f32)>>();
+                neighbours.sort_by_key(|(neighbour, _)| *neighbour);
+                neighbours
+            })
+            .collect::<Vec<Vec<(usize, f32)>>>();
+        let labels = CoOccurrenceLogic::detect_communities(&adjacency, resolution);
+        let mut communities =
+            vec![Vec::<String>::new(); labels.iter().max().map_or(0, |max| max + 1)];
+        labels
+            .into_iter()
+            .enumerate()
+            .for_each(|(word, community)| communities[community].push(self.words[word].to_owned()));
+        communities.sort_by_key(|community| Reverse(community.len()));
+        communities
+    }
+
+    /// Get word embeddings of the given number of dimensions by factorizing the matrix with a
+    /// randomized truncated SVD, usually weighted with `CoOccurrenceWeighting::Pmi`. The vectors are
+    /// the left singular vectors scaled by the square root of their singular values.
+    pub fn get_embeddings(&self, dimensions: usize) -> WordEmbeddings {
+        let vectors = CoOccurrenceLogic::factorize(
+            &(0..self.words.len())
+                .map(|row| self.matrix.get_row_entries(row, self.options.weighting))
+                .collect::<Vec<Vec<(usize, f32)>>>(),
+            dimensions,
+        );
+        let dimensions = dimensions.min(self.words.len());
+
+        WordEmbeddings::new(
+            self.words.iter().cloned().zip(vectors).collect(),
+            dimensions,
+        )
+    }
+
+    /// Get the k words with the most similar rows to a given word and their cosine similarity, from the
+    /// most to the least similar.
+    pub fn most_similar(&self, word: &str, k: usize) -> Option<Vec<(String, f32)>> {
+        let label = self.get_label(word)?;
+        let row = self.matrix.get_row_entries(label, self.options.weighting);
+        let similarity = |other: usize| {
+            if other == label {
+                return None;
+            }
+
+            let similarity = sparse_cosine_similarity(
+                &row,
+                &self.matrix.get_row_entries(other, self.options.weighting),
+            );
+            (similarity > 0.0).then_some((other, similarity))
+        };
+
+        #[cfg(feature = "parallel")]
+        let mut similarities = (0..self.words.len())
+            .into_par_iter()
+            .filter_map(similarity)
+            .collect::<Vec<(usize, f32)>>();
+
+        #[cfg(not(feature = "parallel"))]
+        let mut similarities = (0..self.words.len())
+            .filter_map(similarity)
+            .collect::<Vec<(usize, f32)>>();
+
+        similarities.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        similarities.truncate(k);
+        Some(
+            similarities
+                .into_iter()
+                .map(|(other, similarity)| (self.words[other].to_owned(), similarity))
+                .collect(),
+        )
+    }
+
+    /// Get the relation between two words.
+    pub fn get_relation(&self, word1: &str, word2: &str) -> Option<f32> {
+        let label1 = self.get_label(word1)?;
+        let label2 = self.get_label(word2)?;
+        Some(self.matrix.get(label1, label2, self.options.weighting))
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "embed_rank")]
+use crate::embed_rank::Embedder;
+
+/// Low-dimensional word vectors factorized from a co-occurrence matrix.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WordEmbeddings {
+    vectors: HashMap<String, Vec<f32>>,
+    dimensions: usize,
+}
+
+impl WordEmbeddings {
+    /// Create word embeddings from the vectors of every word.
+    pub fn new(vectors: HashMap<String, Vec<f32>>, dimensions: usize) -> Self {
+        Self {
+            vectors,
+            dimensions,
+        }
+    }
+
+    /// Get the number of dimensions of the vectors.
+    pub fn get_dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Get the vector of a word.
+    pub fn get_vector(&self, word: &str) -> Option<&Vec<f32>> {
+        self.vectors.get(word)
+    }
+
+    /// Get the vectors of every word.
+    pub fn get_vectors(&self) -> &HashMap<String, Vec<f32>> {
+        &self.vectors
+    }
+
+    /// Get the cosine similarity of the vectors of two words.
+    pub fn similarity(&self, word1: &str, word2: &str) -> Option<f32> {
+        let vector1 = self.get_vector(word1)?;
+        let vector2 = self.get_vector(word2)?;
+        let dot = vector1
+            .iter()
+            .zip(vector2)
+            .map(|(value1, value2)| value1 * value2)
+            .sum::<f32>();
+        let norm = |vector: &Vec<f32>| vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+        let norms = norm(vector1) * norm(vector2);
+
+        if norms == 0.0 {
+            return Some(0.0);
+        }
+
+        Some(dot / norms)
+    }
+}
+
+#[cfg(feature = "embed_rank")]
+impl Embedder for WordEmbeddings {
+    /// Embeds a text as the mean of the vectors of its words, words without a vector are skipped.
+    fn embed(&self, text: &str) -> Vec<f32> {
+        let mut embedding = vec![0.0_f32; self.dimensions];
+        let vectors = text
+            .split_whitespace()
+            .filter_map(|word| self.get_vector(&word.to_lowercase()))
+            .collect::<Vec<&Vec<f32>>>();
+
+        vectors.iter().for_each(|vector| {
+            embedding
+                .iter_mut()
+                .zip(vector.iter())
+                .for_each(|(value, word_value)| *value += word_value / vectors.len() as f32)
+        });
+
+        embedding
+    }
+}
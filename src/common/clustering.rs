@@ -0,0 +1,85 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+
+/// Jaccard distance between two sets of words.
+pub fn jaccard_distance(words1: &HashSet<&str>, words2: &HashSet<&str>) -> f32 {
+    let union = words1.union(words2).count();
+
+    if union == 0 {
+        return 0.0;
+    }
+
+    1.0 - words1.intersection(words2).count() as f32 / union as f32
+}
+
+/// Hierarchical agglomerative clustering with average linkage over a symmetric distance matrix.
+/// Clusters keep being merged while the closest pair is strictly below the threshold.
+/// Returns the clusters as lists of item indexes, each sorted and ordered by their first item.
+pub fn average_linkage_clustering(distances: &[Vec<f32>], threshold: f32) -> Vec<Vec<usize>> {
+    let mut clusters = (0..distances.len())
+        .map(|i| Some(vec![i]))
+        .collect::<Vec<Option<Vec<usize>>>>();
+    let mut cluster_distances = distances.to_vec();
+
+    loop {
+        let closest = (0..clusters.len())
+            .filter(|i| clusters[*i].is_some())
+            .flat_map(|i| {
+                (i + 1..clusters.len())
+                    .filter(|j| clusters[*j].is_some())
+                    .map(move |j| (i, j))
+            })
+            .map(|(i, j)| (i, j, cluster_distances[i][j]))
+            .filter(|(_, _, distance)| *distance < threshold)
+            .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal));
+        let (i, j) = match closest {
+            Some((i, j, _)) => (i, j),
+            None => break,
+        };
+        let cluster_j = clusters[j].take().unwrap_or_default();
+        let (size_i, size_j) = (
+            clusters[i].as_ref().map_or(0, |c| c.len()) as f32,
+            cluster_j.len() as f32,
+        );
+
+        // Lance-Williams update for average linkage.
+        (0..clusters.len())
+            .filter(|k| *k != i && clusters[*k].is_some())
+            .for_each(|k| {
+                let distance = (size_i * cluster_distances[k][i]
+                    + size_j * cluster_distances[k][j])
+                    / (size_i + size_j);
+                cluster_distances[k][i] = distance;
+                cluster_distances[i][k] = distance;
+            });
+
+        if let Some(cluster_i) = clusters[i].as_mut() {
+            cluster_i.extend(cluster_j);
+        }
+    }
+
+    let mut clusters = clusters
+        .into_iter()
+        .flatten()
+        .map(|mut cluster| {
+            cluster.sort_unstable();
+            cluster
+        })
+        .collect::<Vec<Vec<usize>>>();
+    clusters.sort_by_key(|cluster| cluster[0]);
+    clusters
+}
@@ -17,3 +17,95 @@ pub const PUNCTUATION: [&str; 33] = [
     "!", "\"", "#", "$", "%", "&", "'", "(", ")", "*", "+", ",", ";", ".", "/", ":", ",", "<", "=",
     ">", "?", "@", "[", "\\", "]", "^", "_", "`", "{", "|", "}", "~", "-",
 ];
+
+/// The emoticons replaced with the emoji they stand for, unless emoji are kept.
+pub const EMOTICONS: [(&str, &str); 16] = [
+    (":)", "🙂"),
+    (":-)", "🙂"),
+    (":(", "🙁"),
+    (":-(", "🙁"),
+    (":D", "😀"),
+    (":-D", "😀"),
+    (";)", "😉"),
+    (";-)", "😉"),
+    (":P", "😛"),
+    (":p", "😛"),
+    (":-P", "😛"),
+    (":O", "😮"),
+    (":o", "😮"),
+    (":'(", "😢"),
+    (":/", "😕"),
+    ("<3", "❤"),
+];
+
+/// The names of common emoji, without variation selectors, used by `EmojiPolicy::Name`.
+pub const EMOJI_NAMES: [(&str, &str); 24] = [
+    ("🙂", "slightly_smiling_face"),
+    ("🙁", "slightly_frowning_face"),
+    ("😀", "grinning_face"),
+    ("😃", "grinning_face_with_big_eyes"),
+    ("😄", "grinning_face_with_smiling_eyes"),
+    ("😁", "beaming_face"),
+    ("😂", "face_with_tears_of_joy"),
+    ("🤣", "rolling_on_the_floor_laughing"),
+    ("😊", "smiling_face_with_smiling_eyes"),
+    ("😍", "smiling_face_with_heart_eyes"),
+    ("😉", "winking_face"),
+    ("😛", "face_with_tongue"),
+    ("😮", "face_with_open_mouth"),
+    ("😢", "crying_face"),
+    ("😭", "loudly_crying_face"),
+    ("😕", "confused_face"),
+    ("😡", "enraged_face"),
+    ("🤔", "thinking_face"),
+    ("❤", "red_heart"),
+    ("👍", "thumbs_up"),
+    ("👎", "thumbs_down"),
+    ("👏", "clapping_hands"),
+    ("🔥", "fire"),
+    ("🎉", "party_popper"),
+];
+
+/// The English contractions expanded into their words, keyed by their lowercase form.
+pub const CONTRACTIONS: [(&str, &str); 40] = [
+    ("don't", "do not"),
+    ("doesn't", "does not"),
+    ("didn't", "did not"),
+    ("can't", "cannot"),
+    ("won't", "will not"),
+    ("isn't", "is not"),
+    ("aren't", "are not"),
+    ("wasn't", "was not"),
+    ("weren't", "were not"),
+    ("haven't", "have not"),
+    ("hasn't", "has not"),
+    ("hadn't", "had not"),
+    ("couldn't", "could not"),
+    ("shouldn't", "should not"),
+    ("wouldn't", "would not"),
+    ("mustn't", "must not"),
+    ("it's", "it is"),
+    ("that's", "that is"),
+    ("there's", "there is"),
+    ("what's", "what is"),
+    ("who's", "who is"),
+    ("he's", "he is"),
+    ("she's", "she is"),
+    ("let's", "let us"),
+    ("i'm", "i am"),
+    ("you're", "you are"),
+    ("we're", "we are"),
+    ("they're", "they are"),
+    ("i've", "i have"),
+    ("you've", "you have"),
+    ("we've", "we have"),
+    ("they've", "they have"),
+    ("i'll", "i will"),
+    ("you'll", "you will"),
+    ("he'll", "he will"),
+    ("she'll", "she will"),
+    ("we'll", "we will"),
+    ("they'll", "they will"),
+    ("i'd", "i would"),
+    ("you'd", "you would"),
+];
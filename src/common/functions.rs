@@ -14,16 +14,30 @@
 // along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
 
 use std::{
+    borrow::Cow,
     cmp::Ordering,
     collections::{hash_map::RandomState, HashMap, HashSet},
+    fmt,
+    sync::{Arc, OnceLock},
 };
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use regex::Regex;
+use regex::{Regex, RegexSet};
 use unicode_segmentation::UnicodeSegmentation;
 
+use super::{WordBoundaryRules, CONTRACTIONS, EMOJI_NAMES, EMOTICONS};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "stemming")]
+use rust_stemmers::{Algorithm, Stemmer};
+
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization as _};
+
 #[cfg(not(feature = "parallel"))]
 fn basic_sort<'a>(map: &'a HashMap<String, f32, RandomState>) -> Vec<(&'a String, &'a f32)> {
     let mut map_values = map.iter().collect::<Vec<(&'a String, &'a f32)>>();
@@ -106,25 +120,817 @@ pub fn get_ranked_scores(map: &HashMap<String, f32, RandomState>, n: usize) -> V
     }
 }
 
-pub fn get_special_char_regex() -> Regex {
-    Regex::new(r"('s|,|\.)").unwrap()
+/// How phrases are reduced to a normalized form when merging equivalent ones.
+#[derive(Clone, Copy)]
+pub enum PhraseNormalization {
+    /// Harman's S-stemmer applied to every word, which merges regular English plurals.
+    SStemmer,
+    /// A custom function mapping a phrase to its normalized form.
+    Custom(fn(&str) -> String),
+}
+
+pub fn s_stem(word: &str) -> String {
+    if word.ends_with("ies") && !word.ends_with("eies") && !word.ends_with("aies") {
+        return format!("{}y", &word[..word.len() - 3]);
+    }
+    if word.ends_with("es")
+        && !word.ends_with("aes")
+        && !word.ends_with("ees")
+        && !word.ends_with("oes")
+    {
+        return word[..word.len() - 1].to_string();
+    }
+    if word.ends_with('s') && !word.ends_with("us") && !word.ends_with("ss") {
+        return word[..word.len() - 1].to_string();
+    }
+
+    word.to_string()
+}
+
+impl PhraseNormalization {
+    /// Returns the normalized form of a phrase.
+    pub fn normalize(&self, phrase: &str) -> String {
+        match self {
+            PhraseNormalization::SStemmer => phrase
+                .split_whitespace()
+                .map(s_stem)
+                .collect::<Vec<String>>()
+                .join(" "),
+            PhraseNormalization::Custom(normalize) => normalize(phrase),
+        }
+    }
+}
+
+/// Merges entries sharing the same normalized form, keeping the one with the highest score.
+pub fn deduplicate_ranked_map(
+    map: &HashMap<String, f32, RandomState>,
+    normalization: PhraseNormalization,
+) -> HashMap<String, f32> {
+    map.iter()
+        .fold(
+            HashMap::<String, (&String, f32)>::new(),
+            |mut acc, (phrase, score)| {
+                let best = acc
+                    .entry(normalization.normalize(phrase))
+                    .or_insert((phrase, *score));
+
+                if *score > best.1 || (*score == best.1 && phrase < best.0) {
+                    *best = (phrase, *score);
+                }
+
+                acc
+            },
+        )
+        .into_values()
+        .map(|(phrase, score)| (phrase.to_string(), score))
+        .collect()
+}
+
+#[cfg(any(feature = "rake", feature = "yake"))]
+fn get_offset_words(text: &str, special_char_regex: &Regex) -> Vec<(String, usize, usize)> {
+    let mut position = 0_usize;
+
+    text.split_word_bounds()
+        .filter_map(|w| {
+            let start = position;
+            position += w.chars().count();
+            let word = special_char_regex.replace_all(w, "").to_lowercase();
+
+            if word.chars().any(|c| c.is_alphanumeric()) {
+                Some((word, start, position))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(any(feature = "rake", feature = "yake"))]
+/// Finds the character offsets, as `(start, end)` with an exclusive end, of every occurrence of the
+/// phrases in the original text. Words are compared in their normalized form, so casing,
+/// possessives and punctuation between words do not prevent a match.
+pub fn get_phrase_offsets<'a>(
+    text: &str,
+    phrases: impl Iterator<Item = &'a String>,
+) -> HashMap<String, Vec<(usize, usize)>> {
+    let special_char_regex = get_special_char_regex();
+    let mut offsets = HashMap::<String, Vec<(usize, usize)>>::new();
+    let mut normalized_phrases = HashMap::<String, Vec<&String>>::new();
+    let mut max_length = 0_usize;
+
+    phrases.for_each(|phrase| {
+        let words = get_offset_words(phrase, special_char_regex)
+            .into_iter()
+            .map(|(word, _, _)| word)
+            .collect::<Vec<String>>();
+        max_length = max_length.max(words.len());
+        offsets.insert(phrase.to_string(), Vec::new());
+
+        if !words.is_empty() {
+            normalized_phrases
+                .entry(words.join(" "))
+                .or_default()
+                .push(phrase);
+        }
+    });
+
+    let words = get_offset_words(text, special_char_regex);
+
+    (0..words.len()).for_each(|i| {
+        let mut key = String::new();
+
+        words
+            .iter()
+            .skip(i)
+            .take(max_length)
+            .for_each(|(word, _, end)| {
+                if !key.is_empty() {
+                    key.push(' ');
+                }
+                key.push_str(word);
+
+                if let Some(phrases) = normalized_phrases.get(&key) {
+                    phrases.iter().for_each(|phrase| {
+                        if let Some(phrase_offsets) = offsets.get_mut(phrase.as_str()) {
+                            phrase_offsets.push((words[i].1, *end));
+                        }
+                    });
+                }
+            });
+    });
+
+    offsets
+}
+
+#[cfg(any(feature = "tf_idf", feature = "co_occurrence"))]
+/// The cosine similarity of two sparse vectors sorted by id.
+pub fn sparse_cosine_similarity(a: &[(usize, f32)], b: &[(usize, f32)]) -> f32 {
+    let (mut i, mut j, mut dot) = (0_usize, 0_usize, 0.0_f32);
+
+    while i < a.len() && j < b.len() {
+        match a[i].0.cmp(&b[j].0) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                dot += a[i].1 * b[j].1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    let norm = |vector: &[(usize, f32)]| vector.iter().map(|(_, w)| w * w).sum::<f32>().sqrt();
+    let norms = norm(a) * norm(b);
+
+    if norms == 0.0 {
+        return 0.0;
+    }
+
+    dot / norms
+}
+
+/// Removes possessives and the commas and periods around a word, keeping the ones inside it, e.g. of
+/// decimal numbers ("3.14") and abbreviations ("u.s").
+pub fn get_special_char_regex() -> &'static Regex {
+    static SPECIAL_CHAR_REGEX: OnceLock<Regex> = OnceLock::new();
+    SPECIAL_CHAR_REGEX.get_or_init(|| Regex::new(r"(['’]s$|^[.,]+|[.,]+$)").unwrap())
 }
 
 pub fn is_punctuation(word: &str, punctuation: &HashSet<String>) -> bool {
     word.is_empty() || ((word.graphemes(true).count() == 1) && punctuation.contains(word))
 }
 
+/// The Unicode normalization forms merging the different code point sequences of the same text.
+#[cfg(feature = "unicode-normalization")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UnicodeNormalization {
+    /// Canonical composition, e.g. "e" followed by a combining acute accent becomes "é".
+    Nfc,
+    /// Compatibility composition, which also folds compatibility characters, e.g. the full-width
+    /// "ｒｕｓｔ" becomes "rust" and the "ﬁ" ligature "fi".
+    Nfkc,
+}
+
+/// The word every number is replaced with by `NumberPolicy::Placeholder`.
+pub const NUMBER_PLACEHOLDER: &str = "<number>";
+
+/// How the numbers, e.g. figures, years and IDs, are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NumberPolicy {
+    /// Numbers are kept as words.
+    #[default]
+    Keep,
+    /// Numbers are dropped like stopwords.
+    Drop,
+    /// Numbers are replaced with `NUMBER_PLACEHOLDER`, so they are counted as a single word.
+    Placeholder,
+}
+
+/// How the words longer than the maximum word length, e.g. base64 blobs and minified code, are
+/// handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LongWordPolicy {
+    /// Long words are dropped like stopwords.
+    #[default]
+    Drop,
+    /// Long words are cut to the maximum word length.
+    Truncate,
+}
+
+/// Whether a word is a number, i.e. digits with the separators of decimals, dates and times.
+pub(crate) fn is_number(word: &str) -> bool {
+    word.chars().any(|c| c.is_numeric())
+        && word
+            .chars()
+            .all(|c| c.is_numeric() || matches!(c, '.' | ',' | ':' | '/' | '-'))
+}
+
+/// The word the emoji without a name are replaced with by `EmojiPolicy::Name`.
+pub const EMOJI_PLACEHOLDER: &str = "<emoji>";
+
+/// How the emoji and emoticons, e.g. ":)", are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EmojiPolicy {
+    /// Emoji are kept as words, and emoticons as punctuation.
+    #[default]
+    Keep,
+    /// Emoji and emoticons are dropped like stopwords.
+    Strip,
+    /// Emoji and emoticons are replaced with the names of `EMOJI_NAMES`, e.g. "thumbs_up", or with
+    /// `EMOJI_PLACEHOLDER` if they have none.
+    Name,
+}
+
+/// Whether a word is an emoji, including its modifiers and zero width joiner sequences.
+fn is_emoji(word: &str) -> bool {
+    let is_pictographic =
+        |c: char| matches!(c as u32, 0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF);
+
+    word.chars().any(is_pictographic)
+        && word
+            .chars()
+            .all(|c| is_pictographic(c) || matches!(c, '\u{200D}' | '\u{FE0F}' | '\u{20E3}'))
+}
+
+/// Gets the name of an emoji, ignoring its variation selectors.
+fn get_emoji_name(emoji: &str) -> String {
+    let emoji = emoji.replace('\u{FE0F}', "");
+
+    EMOJI_NAMES
+        .iter()
+        .find(|(candidate, _)| *candidate == emoji)
+        .map_or(EMOJI_PLACEHOLDER, |(_, name)| name)
+        .to_string()
+}
+
+/// How the hyphenated words, e.g. "state-of-the-art", are split.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HyphenPolicy {
+    /// The parts are split at the hyphens, which break phrases like punctuation.
+    #[default]
+    Split,
+    /// The parts are kept joined as a single word.
+    Join,
+    /// The joined word is kept, followed by its split parts.
+    Both,
+}
+
+fn is_hyphen(token: &str) -> bool {
+    matches!(token, "-" | "\u{2010}" | "\u{2011}")
+}
+
+pub(crate) fn is_word_token(token: &str) -> bool {
+    token.chars().any(|c| c.is_alphanumeric())
+}
+
+/// The kinds of web and social media tokens recognized as a single word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EntityKind {
+    /// URLs starting with "http://", "https://" or "www.".
+    Url,
+    /// Email addresses, e.g. "jane@example.com".
+    Email,
+    /// Hashtags, e.g. "#rustlang".
+    Hashtag,
+    /// Mentions, e.g. "@rustlang".
+    Mention,
+}
+
+/// How a kind of web and social media token is handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EntityPolicy {
+    /// The token is not recognized, and is split on its word bounds like any other text.
+    #[default]
+    Split,
+    /// The token is dropped like a stopword.
+    Drop,
+    /// The token is kept as a single word as it is written.
+    Verbatim,
+    /// The token is kept as a single lowercased word, without the scheme, "www." and trailing slash
+    /// of URLs and the "#" and "@" of hashtags and mentions.
+    Normalized,
+}
+
+/// The policies of every kind of web and social media token, all split by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EntityPolicies {
+    pub url: EntityPolicy,
+    pub email: EntityPolicy,
+    pub hashtag: EntityPolicy,
+    pub mention: EntityPolicy,
+}
+
+impl EntityPolicies {
+    pub fn get(&self, kind: EntityKind) -> EntityPolicy {
+        match kind {
+            EntityKind::Url => self.url,
+            EntityKind::Email => self.email,
+            EntityKind::Hashtag => self.hashtag,
+            EntityKind::Mention => self.mention,
+        }
+    }
+
+    pub fn set(&mut self, kind: EntityKind, policy: EntityPolicy) {
+        match kind {
+            EntityKind::Url => self.url = policy,
+            EntityKind::Email => self.email = policy,
+            EntityKind::Hashtag => self.hashtag = policy,
+            EntityKind::Mention => self.mention = policy,
+        }
+    }
+
+    fn is_split(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn is_handle(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Gets the kind of a web or social media token, if it is one.
+fn get_entity_kind(token: &str) -> Option<EntityKind> {
+    let lowercase = token.to_lowercase();
+
+    if ["http://", "https://", "www."]
+        .iter()
+        .any(|scheme| lowercase.len() > scheme.len() && lowercase.starts_with(scheme))
+    {
+        return Some(EntityKind::Url);
+    }
+    if let Some(name) = token.strip_prefix('#') {
+        return is_handle(name).then_some(EntityKind::Hashtag);
+    }
+    if let Some(name) = token.strip_prefix('@') {
+        return is_handle(name).then_some(EntityKind::Mention);
+    }
+
+    let (local, domain) = token.split_once('@')?;
+    let is_email = local
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '.' | '_' | '+' | '-'))
+        && domain.contains('.')
+        && domain
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '-'));
+
+    is_email.then_some(EntityKind::Email)
+}
+
+/// Gets the normalized form of a web or social media token.
+fn normalize_entity(kind: EntityKind, token: &str) -> String {
+    let token = token.to_lowercase();
+
+    match kind {
+        EntityKind::Url => {
+            let url = ["https://", "http://"]
+                .iter()
+                .find_map(|scheme| token.strip_prefix(scheme))
+                .unwrap_or(&token);
+            let url = url.strip_prefix("www.").unwrap_or(url);
+            url.trim_end_matches('/').to_string()
+        }
+        EntityKind::Email => token,
+        EntityKind::Hashtag | EntityKind::Mention => token[1..].to_string(),
+    }
+}
+
+/// Splits a chunk of text without whitespace into its leading punctuation, the possible web or
+/// social media token, and its trailing punctuation.
+fn split_entity_chunk(chunk: &str) -> (&str, &str, &str) {
+    let core = chunk.trim_start_matches(['(', '[', '"', '\'']);
+    let start = chunk.len() - core.len();
+    let core = core.trim_end_matches(|c: char| {
+        matches!(
+            c,
+            '.' | ',' | ';' | ':' | '!' | '?' | ')' | ']' | '"' | '\''
+        )
+    });
+    let end = start + core.len();
+
+    (&chunk[..start], core, &chunk[end..])
+}
+
+/// A custom normalization of a lowercased word, e.g. a lemmatizer.
+pub type WordNormalizerFn = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// A custom filter of a cleaned word, which is replaced with the returned word or dropped if none.
+pub type WordFilterFn = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// How the words are filtered and normalized once lowercased and checked against the stopwords.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WordNormalizer {
+    /// A custom normalization applied to every word before stemming, not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub custom: Option<WordNormalizerFn>,
+    /// A custom filter applied to every cleaned word before the stopwords are checked, not
+    /// serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub filter: Option<WordFilterFn>,
+    /// The language of the Snowball stemmer applied to every word.
+    #[cfg(feature = "stemming")]
+    pub stemming: Option<Algorithm>,
+    /// The Unicode normalization form of the texts, applied before they are split.
+    #[cfg(feature = "unicode-normalization")]
+    pub unicode_normalization: Option<UnicodeNormalization>,
+    /// Whether the accents of the texts are removed before they are split, e.g. "café" becomes
+    /// "cafe".
+    #[cfg(feature = "unicode-normalization")]
+    pub fold_diacritics: bool,
+    /// The minimum number of characters of a word, shorter words are dropped like stopwords.
+    pub min_word_length: usize,
+    /// The maximum number of characters of a word, if any.
+    pub max_word_length: Option<usize>,
+    /// How the words longer than `max_word_length` are handled.
+    pub long_word_policy: LongWordPolicy,
+    /// How the numbers are handled.
+    pub number_policy: NumberPolicy,
+    /// How the emoji and emoticons are handled.
+    pub emoji_policy: EmojiPolicy,
+    /// How the hyphenated words are split.
+    pub hyphen_policy: HyphenPolicy,
+    /// The contractions expanded into their words, e.g. "don't" into "do not", keyed by their
+    /// lowercase form with straight apostrophes.
+    pub contractions: Option<HashMap<String, String>>,
+    /// How the URLs, emails, hashtags and mentions are handled.
+    pub entity_policies: EntityPolicies,
+    /// The adjustments of the Unicode word bounds the texts are split on.
+    pub word_boundary_rules: WordBoundaryRules,
+    /// The patterns of the words dropped like stopwords, e.g. CSS lengths or hashes, not
+    /// serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub stopword_patterns: Option<RegexSet>,
+}
+
+impl fmt::Debug for WordNormalizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("WordNormalizer");
+        debug.field(
+            "custom",
+            &self.custom.as_ref().map(|_| "Fn(&str) -> String"),
+        );
+        debug.field(
+            "filter",
+            &self.filter.as_ref().map(|_| "Fn(&str) -> Option<String>"),
+        );
+        #[cfg(feature = "stemming")]
+        debug.field("stemming", &self.stemming);
+        #[cfg(feature = "unicode-normalization")]
+        debug.field("unicode_normalization", &self.unicode_normalization);
+        #[cfg(feature = "unicode-normalization")]
+        debug.field("fold_diacritics", &self.fold_diacritics);
+        debug.field("min_word_length", &self.min_word_length);
+        debug.field("max_word_length", &self.max_word_length);
+        debug.field("long_word_policy", &self.long_word_policy);
+        debug.field("number_policy", &self.number_policy);
+        debug.field("emoji_policy", &self.emoji_policy);
+        debug.field("hyphen_policy", &self.hyphen_policy);
+        debug.field("contractions", &self.contractions);
+        debug.field("entity_policies", &self.entity_policies);
+        debug.field("word_boundary_rules", &self.word_boundary_rules);
+        debug.field("stopword_patterns", &self.stopword_patterns);
+        debug.finish()
+    }
+}
+
+impl WordNormalizer {
+    /// Gets the contractions of `CONTRACTIONS`, replaced or extended by the given ones.
+    pub fn get_contractions(overrides: &[(&str, &str)]) -> HashMap<String, String> {
+        CONTRACTIONS
+            .iter()
+            .chain(overrides.iter())
+            .map(|(contraction, expansion)| {
+                (
+                    contraction.to_lowercase().replace('’', "'"),
+                    expansion.to_string(),
+                )
+            })
+            .collect()
+    }
+
+    /// Whether `normalize_text` changes the texts, and so the stopwords must be normalized too.
+    pub fn is_text_normalized(&self) -> bool {
+        #[cfg(feature = "unicode-normalization")]
+        if self.unicode_normalization.is_some() || self.fold_diacritics {
+            return true;
+        }
+
+        self.emoji_policy != EmojiPolicy::Keep || self.contractions.is_some()
+    }
+
+    /// Prepares a text before it is split, replacing its emoticons with emoji unless they are kept,
+    /// expanding its contractions, if any, applying the Unicode normalization form, if any, and
+    /// removing its accents if they are folded.
+    pub fn normalize_text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = self.replace_emoticons(text);
+
+        if let Some(expanded) = self.expand_contractions(&text) {
+            text = Cow::Owned(expanded);
+        }
+
+        #[cfg(feature = "unicode-normalization")]
+        match self.unicode_normalization {
+            Some(UnicodeNormalization::Nfc) => text = Cow::Owned(text.nfc().collect()),
+            Some(UnicodeNormalization::Nfkc) => text = Cow::Owned(text.nfkc().collect()),
+            None => {}
+        }
+
+        #[cfg(feature = "unicode-normalization")]
+        if self.fold_diacritics {
+            text = Cow::Owned(
+                text.nfd()
+                    .filter(|c| !is_combining_mark(*c))
+                    .nfc()
+                    .collect(),
+            );
+        }
+
+        text
+    }
+
+    /// Replaces the emoticons separated by whitespace with the emoji they stand for, as the word
+    /// bounds split them into punctuation.
+    fn replace_emoticons<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.emoji_policy == EmojiPolicy::Keep {
+            return Cow::Borrowed(text);
+        }
+
+        Cow::Owned(
+            text.split_inclusive(char::is_whitespace)
+                .map(|chunk| {
+                    let emoticon = chunk.trim_end();
+
+                    match EMOTICONS
+                        .iter()
+                        .find(|(candidate, _)| *candidate == emoticon)
+                    {
+                        Some((_, emoji)) => format!("{}{}", emoji, &chunk[emoticon.len()..]),
+                        None => chunk.to_string(),
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Gets the kind and policy of a web or social media token, if it is one that is not split.
+    fn get_entity(&self, token: &str) -> Option<(EntityKind, EntityPolicy)> {
+        if self.entity_policies.is_split() {
+            return None;
+        }
+
+        get_entity_kind(token)
+            .map(|kind| (kind, self.entity_policies.get(kind)))
+            .filter(|(_, policy)| *policy != EntityPolicy::Split)
+    }
+
+    /// Splits a text on its word bounds, keeping the web and social media tokens that are not split
+    /// as single tokens.
+    fn split_tokens<'a>(&self, text: &'a str) -> Vec<(usize, &'a str)> {
+        let rules = &self.word_boundary_rules;
+
+        if self.entity_policies.is_split() {
+            return rules.split_word_bound_indices(0, text);
+        }
+
+        let mut tokens = Vec::new();
+        let mut start = 0;
+        text.split_inclusive(char::is_whitespace).for_each(|chunk| {
+            let (leading, core, _) = split_entity_chunk(chunk.trim_end());
+
+            if self.get_entity(core).is_some() {
+                let core_start = start + leading.len();
+                tokens.extend(rules.split_word_bound_indices(start, leading));
+                tokens.push((core_start, core));
+                tokens.extend(rules.split_word_bound_indices(
+                    core_start + core.len(),
+                    &chunk[leading.len() + core.len()..],
+                ));
+            } else {
+                tokens.extend(rules.split_word_bound_indices(start, chunk));
+            }
+
+            start += chunk.len();
+        });
+
+        tokens
+    }
+
+    /// Cleans a token into a lowercased word, removing the special characters, unless it is a web
+    /// or social media token.
+    pub fn clean_word(&self, token: &str, special_char_regex: &Regex) -> String {
+        let token = token.trim();
+
+        let word = match self.get_entity(token) {
+            Some((_, EntityPolicy::Verbatim)) => token.to_string(),
+            Some((kind, EntityPolicy::Normalized)) => normalize_entity(kind, token),
+            Some(_) => token.to_lowercase(),
+            None => special_char_regex.replace_all(token, "").to_lowercase(),
+        };
+
+        self.truncate_word(word)
+    }
+
+    /// Cuts a word to the maximum word length if long words are truncated.
+    fn truncate_word(&self, mut word: String) -> String {
+        if let (Some(max_word_length), LongWordPolicy::Truncate) =
+            (self.max_word_length, self.long_word_policy)
+        {
+            if let Some((end, _)) = word.char_indices().nth(max_word_length) {
+                word.truncate(end);
+            }
+        }
+
+        word
+    }
+
+    /// Splits a text on its word bounds, joining the hyphenated words unless they are split.
+    pub fn split_words<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.split_word_indices(text)
+            .into_iter()
+            .map(|(_, token)| token)
+            .collect()
+    }
+
+    /// Splits a text like `split_words`, with the byte offset of every token.
+    pub fn split_word_indices<'a>(&self, text: &'a str) -> Vec<(usize, &'a str)> {
+        let tokens = self.split_tokens(text);
+
+        if self.hyphen_policy == HyphenPolicy::Split {
+            return tokens;
+        }
+
+        let mut words = Vec::with_capacity(tokens.len());
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let (start, token) = tokens[i];
+            let mut end = i;
+
+            while is_word_token(tokens[end].1)
+                && end + 2 < tokens.len()
+                && is_hyphen(tokens[end + 1].1)
+                && is_word_token(tokens[end + 2].1)
+            {
+                end += 2;
+            }
+
+            if end == i {
+                words.push((start, token));
+                i += 1;
+                continue;
+            }
+
+            let (last_start, last) = tokens[end];
+            words.push((start, &text[start..last_start + last.len()]));
+
+            if self.hyphen_policy == HyphenPolicy::Both {
+                // the hyphen after the joined word breaks it from its parts
+                words.push(tokens[i + 1]);
+                words.extend_from_slice(&tokens[i..=end]);
+            }
+
+            i = end + 1;
+        }
+
+        words
+    }
+
+    /// Expands the contractions of a text, none if they are not expanded.
+    fn expand_contractions(&self, text: &str) -> Option<String> {
+        let contractions = self.contractions.as_ref()?;
+
+        Some(
+            text.split_word_bounds()
+                .map(|token| {
+                    contractions
+                        .get(&token.to_lowercase().replace('’', "'"))
+                        .map_or(token, |expansion| expansion.as_str())
+                })
+                .collect(),
+        )
+    }
+
+    /// Applies the custom filter to a cleaned word, if any.
+    pub fn filter_word(&self, word: String) -> Option<String> {
+        match &self.filter {
+            Some(filter) => filter(&word),
+            None => Some(word),
+        }
+    }
+
+    /// Whether a lowercased word is dropped like a stopword, matching a stopword pattern, being too
+    /// short, a dropped number or a stripped emoji.
+    pub fn is_dropped(&self, word: &str) -> bool {
+        if self
+            .stopword_patterns
+            .as_ref()
+            .is_some_and(|patterns| patterns.is_match(word))
+        {
+            return true;
+        }
+
+        if let Some((_, policy)) = self.get_entity(word) {
+            return policy == EntityPolicy::Drop;
+        }
+
+        if is_emoji(word) {
+            match self.emoji_policy {
+                EmojiPolicy::Strip => return true,
+                EmojiPolicy::Name => return false,
+                EmojiPolicy::Keep => {}
+            }
+        }
+
+        if is_number(word) {
+            match self.number_policy {
+                NumberPolicy::Drop => return true,
+                NumberPolicy::Placeholder => return false,
+                NumberPolicy::Keep => {}
+            }
+        }
+
+        let length = word.chars().count();
+
+        length < self.min_word_length
+            || self
+                .max_word_length
+                .is_some_and(|max_word_length| length > max_word_length)
+    }
+
+    pub fn normalize(&self, word: String) -> String {
+        if self.get_entity(&word).is_some() {
+            return word;
+        }
+
+        if self.number_policy == NumberPolicy::Placeholder && is_number(&word) {
+            return NUMBER_PLACEHOLDER.to_string();
+        }
+
+        if self.emoji_policy == EmojiPolicy::Name && is_emoji(&word) {
+            return get_emoji_name(&word);
+        }
+
+        let word = match &self.custom {
+            Some(normalize) => normalize(&word),
+            None => word,
+        };
+
+        #[cfg(feature = "stemming")]
+        if let Some(language) = self.stemming {
+            return Stemmer::create(language).stem(&word).into_owned();
+        }
+
+        word
+    }
+}
+
 pub fn process_word(
     w: &str,
     special_char_regex: &Regex,
     stopwords: &HashSet<String>,
     punctuation: &HashSet<String>,
+    normalizer: &WordNormalizer,
 ) -> Option<String> {
-    let word = special_char_regex.replace_all(w.trim(), "").to_lowercase();
+    let word = normalizer.clean_word(w, special_char_regex);
+
+    if is_punctuation(&word, punctuation) {
+        return None;
+    }
+
+    let word = normalizer.filter_word(word)?;
 
-    if is_punctuation(&word, punctuation) || stopwords.contains(&word) {
+    if stopwords.contains(&word) || normalizer.is_dropped(&word) {
         return None;
     }
 
-    Some(word)
+    Some(normalizer.normalize(word))
 }
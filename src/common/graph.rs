@@ -0,0 +1,209 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+/// The formats a weighted graph can be exported to.
+#[derive(Clone, Copy)]
+pub enum GraphFormat {
+    /// Graphviz DOT language.
+    Dot,
+    /// GraphML XML format, readable by Gephi, Cytoscape and most graph libraries.
+    GraphMl,
+    /// GEXF XML format, Gephi's native format.
+    Gexf,
+    /// A `source,target,weight` CSV edge list, importable as a table by Gephi and Cytoscape.
+    EdgeListCsv,
+}
+
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn get_edge_type(directed: bool) -> &'static str {
+    if directed {
+        "directed"
+    } else {
+        "undirected"
+    }
+}
+
+fn export_dot(
+    name: &str,
+    nodes: &[(String, f32)],
+    edges: &[(String, String, f32)],
+    directed: bool,
+) -> String {
+    let (graph, edge_operator) = if directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+    let mut dot = format!("{} \"{}\" {{\n", graph, escape_dot(name));
+
+    nodes.iter().for_each(|(node, score)| {
+        dot.push_str(&format!("  \"{}\" [score={}];\n", escape_dot(node), score));
+    });
+    edges.iter().for_each(|(source, target, weight)| {
+        dot.push_str(&format!(
+            "  \"{}\" {} \"{}\" [weight={}];\n",
+            escape_dot(source),
+            edge_operator,
+            escape_dot(target),
+            weight
+        ));
+    });
+
+    dot.push_str("}\n");
+    dot
+}
+
+fn export_graph_ml(
+    name: &str,
+    nodes: &[(String, f32)],
+    edges: &[(String, String, f32)],
+    directed: bool,
+) -> String {
+    let mut graph_ml = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"score\" for=\"node\" attr.name=\"score\" attr.type=\"float\"/>\n",
+        "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"float\"/>\n",
+    ));
+    graph_ml.push_str(&format!(
+        "  <graph id=\"{}\" edgedefault=\"{}\">\n",
+        escape_xml(name),
+        get_edge_type(directed)
+    ));
+
+    nodes.iter().for_each(|(node, score)| {
+        graph_ml.push_str(&format!(
+            "    <node id=\"{}\"><data key=\"score\">{}</data></node>\n",
+            escape_xml(node),
+            score
+        ));
+    });
+    edges.iter().for_each(|(source, target, weight)| {
+        graph_ml.push_str(&format!(
+            "    <edge source=\"{}\" target=\"{}\"><data key=\"weight\">{}</data></edge>\n",
+            escape_xml(source),
+            escape_xml(target),
+            weight
+        ));
+    });
+
+    graph_ml.push_str("  </graph>\n</graphml>\n");
+    graph_ml
+}
+
+fn export_gexf(
+    name: &str,
+    nodes: &[(String, f32)],
+    edges: &[(String, String, f32)],
+    directed: bool,
+) -> String {
+    let mut gexf = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n",
+    ));
+    gexf.push_str(&format!(
+        "  <meta><description>{}</description></meta>\n",
+        escape_xml(name)
+    ));
+    gexf.push_str(&format!(
+        "  <graph defaultedgetype=\"{}\">\n",
+        get_edge_type(directed)
+    ));
+    gexf.push_str(concat!(
+        "    <attributes class=\"node\">\n",
+        "      <attribute id=\"score\" title=\"score\" type=\"float\"/>\n",
+        "    </attributes>\n",
+        "    <nodes>\n",
+    ));
+
+    nodes.iter().for_each(|(node, score)| {
+        gexf.push_str(&format!(
+            concat!(
+                "      <node id=\"{0}\" label=\"{0}\">",
+                "<attvalues><attvalue for=\"score\" value=\"{1}\"/></attvalues></node>\n"
+            ),
+            escape_xml(node),
+            score
+        ));
+    });
+    gexf.push_str("    </nodes>\n    <edges>\n");
+    edges
+        .iter()
+        .enumerate()
+        .for_each(|(id, (source, target, weight))| {
+            gexf.push_str(&format!(
+                "      <edge id=\"{}\" source=\"{}\" target=\"{}\" weight=\"{}\"/>\n",
+                id,
+                escape_xml(source),
+                escape_xml(target),
+                weight
+            ));
+        });
+
+    gexf.push_str("    </edges>\n  </graph>\n</gexf>\n");
+    gexf
+}
+
+fn export_edge_list_csv(edges: &[(String, String, f32)]) -> String {
+    let mut csv = String::from("source,target,weight\n");
+
+    edges.iter().for_each(|(source, target, weight)| {
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            escape_csv(source),
+            escape_csv(target),
+            weight
+        ));
+    });
+
+    csv
+}
+
+/// Serializes a weighted graph with scored nodes into the given format, the edge list only holding
+/// the edges.
+pub fn export_graph(
+    name: &str,
+    nodes: &[(String, f32)],
+    edges: &[(String, String, f32)],
+    format: GraphFormat,
+    directed: bool,
+) -> String {
+    match format {
+        GraphFormat::Dot => export_dot(name, nodes, edges, directed),
+        GraphFormat::GraphMl => export_graph_ml(name, nodes, edges, directed),
+        GraphFormat::Gexf => export_gexf(name, nodes, edges, directed),
+        GraphFormat::EdgeListCsv => export_edge_list_csv(edges),
+    }
+}
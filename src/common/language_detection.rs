@@ -0,0 +1,161 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+#[cfg(feature = "stemming")]
+use rust_stemmers::Algorithm;
+pub use whatlang::Lang as Language;
+
+use super::StopwordSet;
+
+/// The language detected in a text, with the stopwords and the stemmer selected for it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetectedLanguage {
+    /// The ISO 639-3 language.
+    pub language: Language,
+    /// The confidence of the detection, from 0.0 to 1.0.
+    pub confidence: f64,
+    /// The Stopwords ISO list of the language, empty if it has none.
+    pub stopwords: StopwordSet,
+    /// The Snowball stemmer of the language, if any.
+    #[cfg(feature = "stemming")]
+    pub stemming: Option<Algorithm>,
+}
+
+impl DetectedLanguage {
+    /// Detects the language of a text, none if it has no letters to detect it from.
+    pub fn detect(text: &str) -> Option<Self> {
+        whatlang::detect(text).map(|info| Self {
+            confidence: info.confidence(),
+            ..Self::from_language(info.lang())
+        })
+    }
+
+    /// Selects the stopwords and the stemmer of a known language.
+    pub fn from_language(language: Language) -> Self {
+        Self {
+            language,
+            confidence: 1.0,
+            stopwords: get_language_stopwords(language),
+            #[cfg(feature = "stemming")]
+            stemming: get_language_stemmer(language),
+        }
+    }
+}
+
+/// Gets the Stopwords ISO list of a language, parsed once and shared.
+fn get_language_stopwords(language: Language) -> StopwordSet {
+    static STOPWORDS: OnceLock<Mutex<HashMap<Language, StopwordSet>>> = OnceLock::new();
+
+    let Some(code) = get_iso_code(language) else {
+        return StopwordSet::default();
+    };
+
+    STOPWORDS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|error| error.into_inner())
+        .entry(language)
+        .or_insert_with(|| stop_words::get(code).into_iter().collect())
+        .clone()
+}
+
+/// The ISO 639-1 codes of the languages with a Stopwords ISO list.
+fn get_iso_code(language: Language) -> Option<&'static str> {
+    let code = match language {
+        Language::Afr => "af",
+        Language::Ara => "ar",
+        Language::Ben => "bn",
+        Language::Bul => "bg",
+        Language::Cat => "ca",
+        Language::Ces => "cs",
+        Language::Cmn => "zh",
+        Language::Dan => "da",
+        Language::Deu => "de",
+        Language::Ell => "el",
+        Language::Eng => "en",
+        Language::Epo => "eo",
+        Language::Est => "et",
+        Language::Fin => "fi",
+        Language::Fra => "fr",
+        Language::Guj => "gu",
+        Language::Heb => "he",
+        Language::Hin => "hi",
+        Language::Hrv => "hr",
+        Language::Hun => "hu",
+        Language::Hye => "hy",
+        Language::Ind => "id",
+        Language::Ita => "it",
+        Language::Jpn => "ja",
+        Language::Kor => "ko",
+        Language::Lat => "la",
+        Language::Lav => "lv",
+        Language::Lit => "lt",
+        Language::Mar => "mr",
+        Language::Nld => "nl",
+        Language::Nob => "no",
+        Language::Pes => "fa",
+        Language::Pol => "pl",
+        Language::Por => "pt",
+        Language::Ron => "ro",
+        Language::Rus => "ru",
+        Language::Slk => "sk",
+        Language::Slv => "sl",
+        Language::Spa => "es",
+        Language::Swe => "sv",
+        Language::Tgl => "tl",
+        Language::Tha => "th",
+        Language::Tur => "tr",
+        Language::Ukr => "uk",
+        Language::Urd => "ur",
+        Language::Vie => "vi",
+        Language::Zul => "zu",
+        _ => return None,
+    };
+
+    Some(code)
+}
+
+/// The Snowball stemmers of the languages.
+#[cfg(feature = "stemming")]
+fn get_language_stemmer(language: Language) -> Option<Algorithm> {
+    let stemmer = match language {
+        Language::Ara => Algorithm::Arabic,
+        Language::Dan => Algorithm::Danish,
+        Language::Deu => Algorithm::German,
+        Language::Ell => Algorithm::Greek,
+        Language::Eng => Algorithm::English,
+        Language::Fin => Algorithm::Finnish,
+        Language::Fra => Algorithm::French,
+        Language::Hun => Algorithm::Hungarian,
+        Language::Ita => Algorithm::Italian,
+        Language::Nld => Algorithm::Dutch,
+        Language::Nob => Algorithm::Norwegian,
+        Language::Por => Algorithm::Portuguese,
+        Language::Ron => Algorithm::Romanian,
+        Language::Rus => Algorithm::Russian,
+        Language::Spa => Algorithm::Spanish,
+        Language::Swe => Algorithm::Swedish,
+        Language::Tam => Algorithm::Tamil,
+        Language::Tur => Algorithm::Turkish,
+        _ => return None,
+    };
+
+    Some(stemmer)
+}
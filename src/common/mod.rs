@@ -13,10 +13,28 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
 
+#[cfg(feature = "topic_rank")]
+pub mod clustering;
 pub mod constants;
 pub mod functions;
+#[cfg(any(feature = "text_rank", feature = "co_occurrence"))]
+pub mod graph;
+#[cfg(feature = "lang-detect")]
+pub mod language_detection;
+pub mod stopword_set;
 pub mod types;
+pub mod word_boundary_rules;
+pub mod word_piece;
 
+#[cfg(feature = "topic_rank")]
+pub use clustering::*;
 pub use constants::*;
 pub use functions::*;
+#[cfg(any(feature = "text_rank", feature = "co_occurrence"))]
+pub use graph::*;
+#[cfg(feature = "lang-detect")]
+pub use language_detection::*;
+pub use stopword_set::*;
 pub use types::*;
+pub use word_boundary_rules::*;
+pub use word_piece::*;
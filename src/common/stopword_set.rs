@@ -0,0 +1,161 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    ops::Deref,
+    path::Path,
+    sync::Arc,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "stop-words")]
+pub use stop_words::LANGUAGE as StopwordLanguage;
+
+use super::WordNormalizer;
+
+/// A set of stopwords built once and shared, cheap to clone, by the tokenizers of many documents.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(from = "HashSet<String>", into = "HashSet<String>")
+)]
+pub struct StopwordSet(Arc<HashSet<String>>);
+
+/// The formats of the stopword lists read by `StopwordSet::from_reader`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StopwordFormat {
+    /// One stopword per line, without the blank lines and the comments starting with "#".
+    #[default]
+    Lines,
+    /// A JSON array of stopwords.
+    #[cfg(feature = "serde_json")]
+    Json,
+}
+
+impl StopwordSet {
+    pub fn new(stopwords: &[String]) -> Self {
+        stopwords.iter().cloned().collect()
+    }
+
+    /// Gets the stopwords of a language from the `stop-words` crate, its Stopwords ISO list unless
+    /// another one is enabled with the features of that crate.
+    #[cfg(feature = "stop-words")]
+    pub fn from_language(language: StopwordLanguage) -> Self {
+        stop_words::get(language).into_iter().collect()
+    }
+
+    /// Adds stopwords to the set, copying it first if it is shared.
+    pub fn add_stopwords(&mut self, stopwords: &[String]) {
+        Arc::make_mut(&mut self.0).extend(stopwords.iter().cloned());
+    }
+
+    /// Removes stopwords from the set, copying it first if it is shared.
+    pub fn remove_stopwords(&mut self, stopwords: &[String]) {
+        let set = Arc::make_mut(&mut self.0);
+        stopwords.iter().for_each(|stopword| {
+            set.remove(stopword);
+        });
+    }
+
+    /// Normalizes the stopwords like the text of a tokenizer, copying the set only if one of them
+    /// changes, so a set built with normalized stopwords stays shared.
+    pub(crate) fn normalize(&mut self, normalizer: &WordNormalizer) {
+        let (stopwords, normalized): (Vec<String>, Vec<String>) = self
+            .iter()
+            .filter_map(|stopword| {
+                let normalized = normalizer.normalize_text(stopword);
+                (normalized != stopword.as_str())
+                    .then(|| (stopword.clone(), normalized.into_owned()))
+            })
+            .unzip();
+
+        if !stopwords.is_empty() {
+            self.remove_stopwords(&stopwords);
+            self.add_stopwords(&normalized);
+        }
+    }
+
+    /// Adds stopwords to the set, see `StopwordSet::add_stopwords`.
+    pub fn with_extra_stopwords(mut self, stopwords: &[String]) -> Self {
+        self.add_stopwords(stopwords);
+        self
+    }
+
+    /// Reads a list of stopwords in the given format.
+    pub fn from_reader<R: Read>(reader: R, format: StopwordFormat) -> io::Result<Self> {
+        match format {
+            StopwordFormat::Lines => BufReader::new(reader)
+                .lines()
+                .filter_map(|line| {
+                    line.map(|line| {
+                        let stopword = line.split('#').next().unwrap_or_default().trim();
+                        (!stopword.is_empty()).then(|| stopword.to_string())
+                    })
+                    .transpose()
+                })
+                .collect(),
+            #[cfg(feature = "serde_json")]
+            StopwordFormat::Json => Ok(serde_json::from_reader::<_, Vec<String>>(reader)?
+                .into_iter()
+                .collect()),
+        }
+    }
+
+    /// Reads a stopword file, a JSON array if its extension is ".json" with the `serde_json`
+    /// feature, one stopword per line otherwise.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        #[cfg(feature = "serde_json")]
+        let format = match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => StopwordFormat::Json,
+            _ => StopwordFormat::Lines,
+        };
+        #[cfg(not(feature = "serde_json"))]
+        let format = StopwordFormat::Lines;
+
+        Self::from_reader(File::open(path)?, format)
+    }
+}
+
+impl Deref for StopwordSet {
+    type Target = HashSet<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromIterator<String> for StopwordSet {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        Self(Arc::new(iter.into_iter().collect()))
+    }
+}
+
+impl From<HashSet<String>> for StopwordSet {
+    fn from(stopwords: HashSet<String>) -> Self {
+        Self(Arc::new(stopwords))
+    }
+}
+
+impl From<StopwordSet> for HashSet<String> {
+    fn from(stopwords: StopwordSet) -> Self {
+        Arc::try_unwrap(stopwords.0).unwrap_or_else(|stopwords| (*stopwords).clone())
+    }
+}
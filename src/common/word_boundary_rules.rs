@@ -0,0 +1,168 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{fmt, sync::Arc};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::functions::is_word_token;
+
+/// A custom split of a text into its tokens and their byte offsets, replacing the Unicode word
+/// bounds.
+pub type WordSplitterFn = Arc<dyn for<'a> Fn(&'a str) -> Vec<(usize, &'a str)> + Send + Sync>;
+
+/// Adjustments of the Unicode word segmentation, for the scripts and domain texts it mishandles.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WordBoundaryRules {
+    /// A custom split of the text into tokens, instead of its Unicode word bounds, not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub splitter: Option<WordSplitterFn>,
+    /// The characters splitting the tokens they are in, as tokens of their own, e.g. the
+    /// apostrophes of the French elisions "l'homme" and "qu'il".
+    pub split_chars: Vec<char>,
+    /// The characters joining the words around them into a single token, e.g. the ampersand of
+    /// "AT&T" and "R&D".
+    pub join_chars: Vec<char>,
+}
+
+impl fmt::Debug for WordBoundaryRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WordBoundaryRules")
+            .field(
+                "splitter",
+                &self
+                    .splitter
+                    .as_ref()
+                    .map(|_| "Fn(&str) -> Vec<(usize, &str)>"),
+            )
+            .field("split_chars", &self.split_chars)
+            .field("join_chars", &self.join_chars)
+            .finish()
+    }
+}
+
+impl WordBoundaryRules {
+    /// Sets a custom split of the text into tokens and their byte offsets.
+    pub fn with_splitter<F>(mut self, splitter: F) -> Self
+    where
+        F: for<'a> Fn(&'a str) -> Vec<(usize, &'a str)> + Send + Sync + 'static,
+    {
+        self.splitter = Some(Arc::new(splitter));
+        self
+    }
+
+    /// Sets the characters splitting the tokens they are in.
+    pub fn with_split_chars(mut self, split_chars: &[char]) -> Self {
+        self.split_chars = split_chars.to_vec();
+        self
+    }
+
+    /// Sets the characters joining the words around them.
+    pub fn with_join_chars(mut self, join_chars: &[char]) -> Self {
+        self.join_chars = join_chars.to_vec();
+        self
+    }
+
+    /// Whether the Unicode word bounds are kept as they are.
+    pub fn is_default(&self) -> bool {
+        self.splitter.is_none() && self.split_chars.is_empty() && self.join_chars.is_empty()
+    }
+
+    /// Splits a text into its tokens and their byte offsets, offset by the start of the text.
+    pub fn split_word_bound_indices<'a>(
+        &self,
+        offset: usize,
+        text: &'a str,
+    ) -> Vec<(usize, &'a str)> {
+        let tokens = match &self.splitter {
+            Some(splitter) => splitter(text),
+            None => text.split_word_bound_indices().collect(),
+        };
+        let tokens = self.join_tokens(self.split_tokens(tokens), text);
+
+        tokens
+            .into_iter()
+            .map(|(i, token)| (offset + i, token))
+            .collect()
+    }
+
+    fn split_tokens<'a>(&self, tokens: Vec<(usize, &'a str)>) -> Vec<(usize, &'a str)> {
+        if self.split_chars.is_empty() {
+            return tokens;
+        }
+
+        let mut split = Vec::with_capacity(tokens.len());
+        tokens.into_iter().for_each(|(start, token)| {
+            let mut last = 0;
+
+            token
+                .match_indices(self.split_chars.as_slice())
+                .for_each(|(i, split_char)| {
+                    if i > last {
+                        split.push((start + last, &token[last..i]));
+                    }
+                    split.push((start + i, split_char));
+                    last = i + split_char.len();
+                });
+
+            if last < token.len() {
+                split.push((start + last, &token[last..]));
+            }
+        });
+
+        split
+    }
+
+    fn join_tokens<'a>(
+        &self,
+        tokens: Vec<(usize, &'a str)>,
+        text: &'a str,
+    ) -> Vec<(usize, &'a str)> {
+        if self.join_chars.is_empty() {
+            return tokens;
+        }
+
+        let is_join_token = |token: &str| {
+            let mut chars = token.chars();
+            chars
+                .next()
+                .is_some_and(|c| chars.next().is_none() && self.join_chars.contains(&c))
+        };
+        let mut joined = Vec::with_capacity(tokens.len());
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let start = tokens[i].0;
+            let mut end = i;
+
+            while is_word_token(tokens[end].1)
+                && end + 2 < tokens.len()
+                && is_join_token(tokens[end + 1].1)
+                && is_word_token(tokens[end + 2].1)
+            {
+                end += 2;
+            }
+
+            let (last_start, last) = tokens[end];
+            joined.push((start, &text[start..last_start + last.len()]));
+            i = end + 1;
+        }
+
+        joined
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    sync::Arc,
+};
+
+/// The prefix of the WordPiece subwords continuing a word.
+pub const CONTINUING_SUBWORD_PREFIX: &str = "##";
+
+/// The subword the words that cannot be split into known subwords are replaced with.
+pub const UNKNOWN_SUBWORD: &str = "[UNK]";
+
+/// A WordPiece vocabulary, e.g. the `vocab.txt` of a BERT model, splitting words into the subwords
+/// of transformer-based systems. Cheap to clone.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WordPieceVocabulary {
+    ids: Arc<HashMap<String, usize>>,
+    max_word_length: usize,
+}
+
+impl WordPieceVocabulary {
+    /// Creates a vocabulary from its subwords, whose ids are their indexes.
+    pub fn new(subwords: &[String]) -> Self {
+        Self {
+            ids: Arc::new(
+                subwords
+                    .iter()
+                    .enumerate()
+                    .map(|(id, subword)| (subword.to_string(), id))
+                    .collect(),
+            ),
+            max_word_length: 100,
+        }
+    }
+
+    /// Reads a vocabulary with one subword per line, whose ids are their line numbers.
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        let subwords = reader
+            .lines()
+            .map(|line| line.map(|subword| subword.trim_end().to_string()))
+            .collect::<io::Result<Vec<String>>>()?;
+
+        Ok(Self::new(&subwords))
+    }
+
+    /// Reads a vocabulary file with one subword per line, see `WordPieceVocabulary::from_reader`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Sets the maximum number of characters of a word, longer words are unknown, 100 by default.
+    pub fn with_max_word_length(mut self, max_word_length: usize) -> Self {
+        self.max_word_length = max_word_length;
+        self
+    }
+
+    /// Gets the id of a subword of the vocabulary.
+    pub fn get_id(&self, subword: &str) -> Option<usize> {
+        self.ids.get(subword).copied()
+    }
+
+    /// Splits a word into the longest subwords of the vocabulary from its start, the continuing
+    /// ones prefixed with `CONTINUING_SUBWORD_PREFIX`, or into `UNKNOWN_SUBWORD` if it cannot be
+    /// split.
+    pub fn split_word(&self, word: &str) -> Vec<String> {
+        if word.chars().count() > self.max_word_length {
+            return vec![UNKNOWN_SUBWORD.to_string()];
+        }
+
+        let mut subwords = Vec::new();
+        let mut start = 0;
+
+        while start < word.len() {
+            let subword = word[start..]
+                .char_indices()
+                .map(|(i, c)| start + i + c.len_utf8())
+                .rev()
+                .find_map(|end| {
+                    let subword = match start {
+                        0 => word[..end].to_string(),
+                        _ => format!("{}{}", CONTINUING_SUBWORD_PREFIX, &word[start..end]),
+                    };
+                    self.ids.contains_key(&subword).then_some((subword, end))
+                });
+
+            match subword {
+                Some((subword, end)) => {
+                    subwords.push(subword);
+                    start = end;
+                }
+                None => return vec![UNKNOWN_SUBWORD.to_string()],
+            }
+        }
+
+        subwords
+    }
+}
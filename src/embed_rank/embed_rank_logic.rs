@@ -0,0 +1,103 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet};
+
+use super::Embedder;
+
+pub struct EmbedRankLogic;
+
+impl EmbedRankLogic {
+    pub fn build_embed_rank(
+        text: &str,
+        phrases: Vec<String>,
+        embedder: &dyn Embedder,
+    ) -> (HashMap<String, f32>, HashMap<String, Vec<f32>>) {
+        let mut seen = HashSet::<&str>::new();
+        let candidates = phrases
+            .iter()
+            .map(|phrase| phrase.as_str())
+            .filter(|phrase| seen.insert(phrase))
+            .collect::<Vec<&str>>();
+        let document_embedding = embedder.embed(text);
+        let embeddings = candidates
+            .iter()
+            .zip(embedder.embed_batch(&candidates))
+            .map(|(phrase, embedding)| (phrase.to_string(), embedding))
+            .collect::<HashMap<String, Vec<f32>>>();
+        let phrase_rank = embeddings
+            .iter()
+            .map(|(phrase, embedding)| {
+                (
+                    phrase.to_string(),
+                    Self::cosine_similarity(embedding, &document_embedding),
+                )
+            })
+            .collect::<HashMap<String, f32>>();
+
+        (phrase_rank, embeddings)
+    }
+
+    pub fn cosine_similarity(vector1: &[f32], vector2: &[f32]) -> f32 {
+        let (dot, norm1, norm2) = vector1.iter().zip(vector2.iter()).fold(
+            (0.0_f32, 0.0_f32, 0.0_f32),
+            |(dot, norm1, norm2), (a, b)| (dot + a * b, norm1 + a * a, norm2 + b * b),
+        );
+
+        if norm1 == 0.0 || norm2 == 0.0 {
+            return 0.0;
+        }
+
+        dot / (norm1.sqrt() * norm2.sqrt())
+    }
+
+    /// Selects n phrases with Maximal Marginal Relevance: at every step the phrase that maximises
+    /// `(1 - diversity) * similarity to the document - diversity * max similarity to the selected phrases`.
+    pub fn maximal_marginal_relevance(
+        ranked_phrases: Vec<(String, f32)>,
+        embeddings: &HashMap<String, Vec<f32>>,
+        n: usize,
+        diversity: f32,
+    ) -> Vec<(String, f32)> {
+        let mut candidates = ranked_phrases;
+        let mut selected = Vec::<(String, f32)>::with_capacity(n.min(candidates.len()));
+
+        while selected.len() < n && !candidates.is_empty() {
+            let (best_index, best_score) = candidates
+                .iter()
+                .enumerate()
+                .map(|(i, (phrase, similarity))| {
+                    let redundancy = selected
+                        .iter()
+                        .map(|(other, _)| {
+                            Self::cosine_similarity(&embeddings[phrase], &embeddings[other])
+                        })
+                        .fold(0.0_f32, f32::max);
+                    (i, (1.0 - diversity) * similarity - diversity * redundancy)
+                })
+                .fold((0, f32::NEG_INFINITY), |best, current| {
+                    if current.1 > best.1 {
+                        current
+                    } else {
+                        best
+                    }
+                });
+            let (phrase, _) = candidates.remove(best_index);
+            selected.push((phrase, best_score));
+        }
+
+        selected
+    }
+}
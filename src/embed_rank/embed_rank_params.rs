@@ -0,0 +1,83 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::common::{PhraseLength, Punctuation, Stopwords, Text};
+
+use super::Embedder;
+
+type TextEmbedder<'a> = &'a dyn Embedder;
+
+/// The parameters to be used in the EmbedRank algorithm.
+pub enum EmbedRankParams<'a> {
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `embedder` - The embedding provider used for the document and the candidate phrases.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `phrase_length` - The maximum length of the candidate phrases, defaults to no limit.
+    WithDefaults(Text<'a>, Stopwords<'a>, TextEmbedder<'a>),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `embedder` - The embedding provider used for the document and the candidate phrases.
+    /// 4. `phrase_length` - Optional maximum length of the candidate phrases.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    WithDefaultsAndPhraseLength(Text<'a>, Stopwords<'a>, TextEmbedder<'a>, PhraseLength),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `punctuation` - Optional list of punctuation symbols.
+    /// 4. `embedder` - The embedding provider used for the document and the candidate phrases.
+    /// 5. `phrase_length` - Optional maximum length of the candidate phrases.
+    All(
+        Text<'a>,
+        Stopwords<'a>,
+        Punctuation<'a>,
+        TextEmbedder<'a>,
+        PhraseLength,
+    ),
+}
+
+impl<'a> EmbedRankParams<'a> {
+    /// Returns the params to be used in the EmbedRank algorithm.
+    pub fn get_params(
+        &self,
+    ) -> (
+        Text<'_>,
+        Stopwords<'_>,
+        Punctuation<'_>,
+        TextEmbedder<'_>,
+        PhraseLength,
+    ) {
+        match self {
+            EmbedRankParams::WithDefaults(text, stop_words, embedder) => {
+                (text, stop_words, None, *embedder, None)
+            }
+            EmbedRankParams::WithDefaultsAndPhraseLength(
+                text,
+                stop_words,
+                embedder,
+                phrase_length,
+            ) => (text, stop_words, None, *embedder, *phrase_length),
+            EmbedRankParams::All(text, stop_words, punctuation, embedder, phrase_length) => {
+                (text, stop_words, *punctuation, *embedder, *phrase_length)
+            }
+        }
+    }
+}
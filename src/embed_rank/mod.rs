@@ -0,0 +1,97 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+mod embed_rank_logic;
+pub mod embed_rank_params;
+mod embedder;
+use embed_rank_logic::EmbedRankLogic;
+pub use embed_rank_params::EmbedRankParams;
+pub use embedder::Embedder;
+
+use crate::{
+    common::{get_ranked_scores, get_ranked_strings},
+    tokenizer::Tokenizer,
+};
+
+pub struct EmbedRank {
+    phrase_rank: HashMap<String, f32>,
+    embeddings: HashMap<String, Vec<f32>>,
+}
+
+impl EmbedRank {
+    /// Create a new EmbedRank instance.
+    pub fn new(params: EmbedRankParams) -> Self {
+        let (text, stop_words, punctuation, embedder, phrase_length) = params.get_params();
+        let tokenizer = Tokenizer::new(text, stop_words, punctuation);
+        let (phrase_rank, embeddings) = EmbedRankLogic::build_embed_rank(
+            text,
+            tokenizer.sync_split_into_phrases(phrase_length),
+            embedder,
+        );
+
+        Self {
+            phrase_rank,
+            embeddings,
+        }
+    }
+
+    /// Gets the cosine similarity between a phrase and the document.
+    pub fn get_phrase_score(&self, phrase: &str) -> f32 {
+        *self.phrase_rank.get(phrase).unwrap_or(&0.0)
+    }
+
+    /// Gets the top n phrases most similar to the document.
+    pub fn get_ranked_phrases(&self, n: usize) -> Vec<String> {
+        get_ranked_strings(&self.phrase_rank, n)
+    }
+
+    /// Gets the top n phrases most similar to the document and their score.
+    pub fn get_ranked_phrase_scores(&self, n: usize) -> Vec<(String, f32)> {
+        get_ranked_scores(&self.phrase_rank, n)
+    }
+
+    /// Gets the top n phrases diversified with Maximal Marginal Relevance (EmbedRank++),
+    /// `diversity` goes from 0.0 (same as ranked phrases) to 1.0 (most diverse).
+    pub fn get_diverse_phrases(&self, n: usize, diversity: f32) -> Vec<String> {
+        self.get_diverse_phrase_scores(n, diversity)
+            .into_iter()
+            .map(|(phrase, _)| phrase)
+            .collect()
+    }
+
+    /// Gets the top n phrases diversified with Maximal Marginal Relevance and their MMR score.
+    pub fn get_diverse_phrase_scores(&self, n: usize, diversity: f32) -> Vec<(String, f32)> {
+        EmbedRankLogic::maximal_marginal_relevance(
+            get_ranked_scores(&self.phrase_rank, self.phrase_rank.len()),
+            &self.embeddings,
+            n,
+            diversity,
+        )
+    }
+
+    /// Gets the embedding of a candidate phrase.
+    pub fn get_phrase_embedding(&self, phrase: &str) -> Option<&[f32]> {
+        self.embeddings
+            .get(phrase)
+            .map(|embedding| embedding.as_slice())
+    }
+
+    /// Gets the phrase scores map.
+    pub fn get_phrase_scores_map(&self) -> &HashMap<String, f32> {
+        &self.phrase_rank
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::co_occurrence::CoOccurrence;
+
+pub struct KCoreLogic;
+
+impl KCoreLogic {
+    pub fn build_k_core(
+        words: Vec<String>,
+        window_size: usize,
+        weighted: bool,
+    ) -> HashMap<String, f32> {
+        let mut seen = HashSet::<&str>::new();
+        let vocabulary = words
+            .iter()
+            .filter(|word| seen.insert(word.as_str()))
+            .map(|word| word.to_string())
+            .collect::<Vec<String>>();
+        let co_occurrence = CoOccurrence::new(&[words.join(" ")], &vocabulary, window_size);
+        let core_numbers =
+            Self::get_core_numbers(vocabulary.len(), &co_occurrence.to_triplets(), weighted);
+
+        vocabulary.into_iter().zip(core_numbers).collect()
+    }
+
+    /// Peels the graph by repeatedly removing the node with the smallest (weighted) degree, the core
+    /// number of a node being the highest degree threshold reached when it is removed.
+    /// The graph is read from the non-zero `(row, column, value)` triplets of the matrix, an edge
+    /// weighing the largest of its two directions.
+    fn get_core_numbers(n: usize, triplets: &[(usize, usize, f32)], weighted: bool) -> Vec<f32> {
+        let mut neighbours = vec![HashMap::<usize, f32>::new(); n];
+        triplets
+            .iter()
+            .filter(|(row, column, value)| row != column && *value > 0.0)
+            .for_each(|(row, column, value)| {
+                let weight = if weighted { *value } else { 1.0 };
+                [(*row, *column), (*column, *row)]
+                    .iter()
+                    .for_each(|(i, j)| {
+                        let edge = neighbours[*i].entry(*j).or_insert(0.0);
+                        *edge = edge.max(weight);
+                    });
+            });
+        let mut degrees = neighbours
+            .iter()
+            .map(|edges| edges.values().sum::<f32>())
+            .collect::<Vec<f32>>();
+        let mut removed = vec![false; n];
+        let mut core_numbers = vec![0.0_f32; n];
+        let mut k = 0.0_f32;
+
+        for _ in 0..n {
+            let node = (0..n)
+                .filter(|i| !removed[*i])
+                .min_by(|a, b| degrees[*a].total_cmp(&degrees[*b]))
+                .unwrap();
+            k = k.max(degrees[node]);
+            core_numbers[node] = k;
+            removed[node] = true;
+            neighbours[node]
+                .iter()
+                .filter(|(j, _)| !removed[**j])
+                .for_each(|(j, weight)| degrees[*j] -= weight);
+        }
+
+        core_numbers
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::common::{Punctuation, Stopwords, Text, WindowSize};
+
+type Weighted = bool;
+
+/// The parameters to be used in the graph-of-words k-core algorithm.
+pub enum KCoreParams<'a> {
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `window_size` - The size of the co-occurrence window, defaults to 4.
+    /// * `weighted` - Whether the degrees are the sum of the co-occurrence weights, defaults to false.
+    WithDefaults(Text<'a>, Stopwords<'a>),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `punctuation` - Optional list of punctuation symbols.
+    /// 4. `window_size` - The size of the co-occurrence window.
+    /// 5. `weighted` - Whether the degrees are the sum of the co-occurrence weights (weighted k-core).
+    All(
+        Text<'a>,
+        Stopwords<'a>,
+        Punctuation<'a>,
+        WindowSize,
+        Weighted,
+    ),
+}
+
+impl<'a> KCoreParams<'a> {
+    /// Returns the params to be used in the graph-of-words k-core algorithm.
+    pub fn get_params(
+        &self,
+    ) -> (
+        Text<'_>,
+        Stopwords<'_>,
+        Punctuation<'_>,
+        WindowSize,
+        Weighted,
+    ) {
+        match self {
+            KCoreParams::WithDefaults(text, stop_words) => (text, stop_words, None, 4, false),
+            KCoreParams::All(text, stop_words, punctuation, window_size, weighted) => {
+                (text, stop_words, *punctuation, *window_size, *weighted)
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+mod k_core_logic;
+pub mod k_core_params;
+use k_core_logic::KCoreLogic;
+pub use k_core_params::KCoreParams;
+
+use crate::{
+    common::{get_ranked_scores, get_ranked_strings},
+    tokenizer::Tokenizer,
+};
+
+pub struct KCore(HashMap<String, f32>);
+
+impl KCore {
+    /// Create a new KCore instance.
+    pub fn new(params: KCoreParams) -> Self {
+        let (text, stop_words, punctuation, window_size, weighted) = params.get_params();
+        let tokenizer = Tokenizer::new(text, stop_words, punctuation);
+
+        Self(KCoreLogic::build_k_core(
+            tokenizer.sync_split_into_words(),
+            window_size,
+            weighted,
+        ))
+    }
+
+    /// Gets the core number of a word.
+    pub fn get_core_number(&self, word: &str) -> f32 {
+        *self.0.get(word).unwrap_or(&0.0)
+    }
+
+    /// Gets the words of the main core (the core with the highest core number), the keywords of the text.
+    pub fn get_main_core(&self) -> Vec<String> {
+        let max = self.0.values().fold(0.0_f32, |max, core| max.max(*core));
+        let mut words = self
+            .0
+            .iter()
+            .filter(|(_, core)| **core == max)
+            .map(|(word, _)| word.to_string())
+            .collect::<Vec<String>>();
+        words.sort_unstable();
+        words
+    }
+
+    /// Gets the top n words with the highest core number.
+    pub fn get_ranked_words(&self, n: usize) -> Vec<String> {
+        get_ranked_strings(&self.0, n)
+    }
+
+    /// Gets the top n words with the highest core number and their core number.
+    pub fn get_ranked_word_scores(&self, n: usize) -> Vec<(String, f32)> {
+        get_ranked_scores(&self.0, n)
+    }
+
+    /// Gets the core numbers map.
+    pub fn get_word_scores_map(&self) -> &HashMap<String, f32> {
+        &self.0
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub struct KpMinerLogic;
+
+impl KpMinerLogic {
+    pub fn build_kp_miner(
+        phrases: Vec<String>,
+        documents: Option<&[String]>,
+        lasf: usize,
+        cutoff: usize,
+        sigma: f32,
+        alpha: f32,
+    ) -> HashMap<String, f32> {
+        let candidates = Self::get_candidates(phrases, lasf, cutoff);
+        let boost = Self::get_boosting_factor(&candidates, sigma, alpha);
+        let idf = Self::calculate_idf(&candidates, documents);
+
+        candidates
+            .into_iter()
+            .map(|(candidate, frequency)| {
+                let idf = *idf.get(&candidate).unwrap_or(&1.0);
+                let weight = if candidate.contains(' ') {
+                    frequency * boost * idf
+                } else {
+                    frequency * idf
+                };
+                (candidate, weight)
+            })
+            .collect::<HashMap<String, f32>>()
+    }
+
+    /// Counts the candidates, dropping those that first appear after the cutoff word position
+    /// or that are seen less than the least allowable seen frequency.
+    fn get_candidates(phrases: Vec<String>, lasf: usize, cutoff: usize) -> HashMap<String, f32> {
+        let mut position = 0_usize;
+        let mut candidates = HashMap::<String, f32>::new();
+
+        phrases.into_iter().for_each(|phrase| {
+            let length = phrase.split_whitespace().count();
+
+            if let Some(frequency) = candidates.get_mut(&phrase) {
+                *frequency += 1.0;
+            } else if position <= cutoff {
+                candidates.insert(phrase, 1.0);
+            }
+
+            position += length;
+        });
+
+        candidates.retain(|_, frequency| *frequency >= lasf as f32);
+        candidates
+    }
+
+    /// The boosting factor of multi-word candidates: the number of candidate occurrences over
+    /// the number of multi-word candidate occurrences times alpha, bounded by sigma.
+    fn get_boosting_factor(candidates: &HashMap<String, f32>, sigma: f32, alpha: f32) -> f32 {
+        let (total, compound) = candidates.iter().fold(
+            (0.0_f32, 0.0_f32),
+            |(total, compound), (candidate, frequency)| {
+                if candidate.contains(' ') {
+                    (total + frequency, compound + frequency)
+                } else {
+                    (total + frequency, compound)
+                }
+            },
+        );
+
+        (total / (compound.max(1.0) * alpha)).min(sigma)
+    }
+
+    /// The idf of every candidate is `log2(N / df)`, where the analyzed text counts as one of the N
+    /// documents and as one of the documents containing the candidate.
+    fn calculate_idf(
+        candidates: &HashMap<String, f32>,
+        documents: Option<&[String]>,
+    ) -> HashMap<String, f32> {
+        let documents = match documents {
+            Some(documents) if !documents.is_empty() => documents,
+            _ => return HashMap::new(),
+        };
+        let documents = documents
+            .iter()
+            .map(|document| format!(" {} ", document))
+            .collect::<Vec<String>>();
+        let total = documents.len() as f32 + 1.0;
+
+        #[cfg(feature = "parallel")]
+        {
+            candidates
+                .par_iter()
+                .map(|(candidate, _)| {
+                    let pattern = format!(" {} ", candidate);
+                    let df = documents.iter().filter(|d| d.contains(&pattern)).count();
+                    (candidate.to_string(), (total / (df as f32 + 1.0)).log2())
+                })
+                .collect::<HashMap<String, f32>>()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            candidates
+                .keys()
+                .map(|candidate| {
+                    let pattern = format!(" {} ", candidate);
+                    let df = documents.iter().filter(|d| d.contains(&pattern)).count();
+                    (candidate.to_string(), (total / (df as f32 + 1.0)).log2())
+                })
+                .collect::<HashMap<String, f32>>()
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::common::{Documents, PhraseLength, Punctuation, Stopwords, Text};
+
+type LeastAllowableSeenFrequency = usize;
+type CutoffPosition = usize;
+type Sigma = f32;
+type Alpha = f32;
+
+/// The parameters to be used in the KP-Miner algorithm.
+pub enum KpMinerParams<'a> {
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `documents` - The reference documents used for the document frequencies, defaults to none (every idf is 1.0).
+    /// * `lasf` - The least allowable seen frequency of a candidate, defaults to 3.
+    /// * `cutoff` - The word position after which new candidates are ignored, defaults to 400.
+    /// * `sigma` - The upper bound of the multi-word boosting factor, defaults to 3.0.
+    /// * `alpha` - The weight dividing the multi-word boosting factor, defaults to 2.3.
+    /// * `phrase_length` - The maximum length of the candidate phrases, defaults to no limit.
+    WithDefaults(Text<'a>, Stopwords<'a>),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `documents` - The unprocessed reference documents used for the document frequencies.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `lasf` - The least allowable seen frequency of a candidate, defaults to 3.
+    /// * `cutoff` - The word position after which new candidates are ignored, defaults to 400.
+    /// * `sigma` - The upper bound of the multi-word boosting factor, defaults to 3.0.
+    /// * `alpha` - The weight dividing the multi-word boosting factor, defaults to 2.3.
+    /// * `phrase_length` - The maximum length of the candidate phrases, defaults to no limit.
+    WithDefaultsAndDocuments(Text<'a>, Stopwords<'a>, Documents<'a>),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `punctuation` - Optional list of punctuation symbols.
+    /// 4. `documents` - Optional unprocessed reference documents used for the document frequencies.
+    /// 5. `lasf` - The least allowable seen frequency of a candidate.
+    /// 6. `cutoff` - The word position after which new candidates are ignored.
+    /// 7. `sigma` - The upper bound of the multi-word boosting factor.
+    /// 8. `alpha` - The weight dividing the multi-word boosting factor.
+    /// 9. `phrase_length` - Optional maximum length of the candidate phrases.
+    All(
+        Text<'a>,
+        Stopwords<'a>,
+        Punctuation<'a>,
+        Option<Documents<'a>>,
+        LeastAllowableSeenFrequency,
+        CutoffPosition,
+        Sigma,
+        Alpha,
+        PhraseLength,
+    ),
+}
+
+impl<'a> KpMinerParams<'a> {
+    /// Returns the params to be used in the KP-Miner algorithm.
+    pub fn get_params(
+        &self,
+    ) -> (
+        Text<'_>,
+        Stopwords<'_>,
+        Punctuation<'_>,
+        Option<Documents<'_>>,
+        LeastAllowableSeenFrequency,
+        CutoffPosition,
+        Sigma,
+        Alpha,
+        PhraseLength,
+    ) {
+        match self {
+            KpMinerParams::WithDefaults(text, stop_words) => {
+                (text, stop_words, None, None, 3, 400, 3.0, 2.3, None)
+            }
+            KpMinerParams::WithDefaultsAndDocuments(text, stop_words, documents) => (
+                text,
+                stop_words,
+                None,
+                Some(documents),
+                3,
+                400,
+                3.0,
+                2.3,
+                None,
+            ),
+            KpMinerParams::All(
+                text,
+                stop_words,
+                punctuation,
+                documents,
+                lasf,
+                cutoff,
+                sigma,
+                alpha,
+                phrase_length,
+            ) => (
+                text,
+                stop_words,
+                *punctuation,
+                *documents,
+                *lasf,
+                *cutoff,
+                *sigma,
+                *alpha,
+                *phrase_length,
+            ),
+        }
+    }
+}
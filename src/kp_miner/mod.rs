@@ -0,0 +1,70 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+mod kp_miner_logic;
+pub mod kp_miner_params;
+use kp_miner_logic::KpMinerLogic;
+pub use kp_miner_params::KpMinerParams;
+
+use crate::{
+    common::{get_ranked_scores, get_ranked_strings},
+    tf_idf::DocumentProcessor,
+    tokenizer::Tokenizer,
+};
+
+pub struct KpMiner(HashMap<String, f32>);
+
+impl KpMiner {
+    /// Create a new KpMiner instance.
+    pub fn new(params: KpMinerParams) -> Self {
+        let (text, stop_words, punctuation, documents, lasf, cutoff, sigma, alpha, phrase_length) =
+            params.get_params();
+        let tokenizer = Tokenizer::new(text, stop_words, punctuation);
+        let documents = documents.map(|documents| {
+            DocumentProcessor::new(stop_words, punctuation).process_documents(documents)
+        });
+
+        Self(KpMinerLogic::build_kp_miner(
+            tokenizer.sync_split_into_phrases(phrase_length),
+            documents.as_deref(),
+            lasf,
+            cutoff,
+            sigma,
+            alpha,
+        ))
+    }
+
+    /// Gets the score of a phrase.
+    pub fn get_phrase_score(&self, phrase: &str) -> f32 {
+        *self.0.get(phrase).unwrap_or(&0.0)
+    }
+
+    /// Gets the top n phrases with the highest score.
+    pub fn get_ranked_phrases(&self, n: usize) -> Vec<String> {
+        get_ranked_strings(&self.0, n)
+    }
+
+    /// Gets the top n phrases with the highest score and their score.
+    pub fn get_ranked_phrase_scores(&self, n: usize) -> Vec<(String, f32)> {
+        get_ranked_scores(&self.0, n)
+    }
+
+    /// Gets the phrase scores map.
+    pub fn get_phrase_scores_map(&self) -> &HashMap<String, f32> {
+        &self.0
+    }
+}
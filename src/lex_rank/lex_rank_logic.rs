@@ -0,0 +1,156 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::text_rank::{Graph, TextRankLogic};
+
+/// A sentence, as written in the original text, with the term frequencies of its words.
+type SentenceVector<'a> = (&'a str, HashMap<&'a str, f32>);
+
+pub struct LexRankLogic;
+
+impl LexRankLogic {
+    pub fn build_lex_rank(
+        sentences: &[(String, String)],
+        threshold: f32,
+        damping: f32,
+        tol: f32,
+    ) -> HashMap<String, f32> {
+        let (sentence_rank, _) = TextRankLogic::rank_graph(
+            &Self::create_graph(sentences, threshold),
+            damping,
+            tol,
+            None,
+        );
+        sentence_rank
+    }
+
+    /// Creates the term frequency vector of every non empty sentence, and the idf of every word,
+    /// with each sentence as a document.
+    fn get_sentence_vectors(
+        sentences: &[(String, String)],
+    ) -> (Vec<SentenceVector<'_>>, HashMap<&str, f32>) {
+        let vectors = sentences
+            .iter()
+            .filter(|(_, processed)| !processed.is_empty())
+            .map(|(sentence, processed)| {
+                (
+                    sentence.as_str(),
+                    processed
+                        .split_whitespace()
+                        .fold(HashMap::new(), |mut acc, word| {
+                            *acc.entry(word).or_insert(0.0) += 1.0;
+                            acc
+                        }),
+                )
+            })
+            .collect::<Vec<SentenceVector>>();
+        let total = vectors.len() as f32;
+        let idf = vectors
+            .iter()
+            .flat_map(|(_, tf)| tf.keys())
+            .fold(HashMap::<&str, f32>::new(), |mut acc, word| {
+                *acc.entry(word).or_insert(0.0) += 1.0;
+                acc
+            })
+            .into_iter()
+            .map(|(word, df)| (word, (total / df).ln()))
+            .collect::<HashMap<&str, f32>>();
+
+        (vectors, idf)
+    }
+
+    /// The idf-modified cosine similarity between two sentences.
+    fn idf_modified_cosine(
+        tf1: &HashMap<&str, f32>,
+        tf2: &HashMap<&str, f32>,
+        idf: &HashMap<&str, f32>,
+    ) -> f32 {
+        let norm = |tf: &HashMap<&str, f32>| {
+            tf.iter()
+                .map(|(word, count)| (count * idf[word]).powi(2))
+                .sum::<f32>()
+                .sqrt()
+        };
+        let denominator = norm(tf1) * norm(tf2);
+
+        if denominator == 0.0 {
+            return 0.0;
+        }
+
+        tf1.iter()
+            .filter_map(|(word, count1)| {
+                tf2.get(word)
+                    .map(|count2| count1 * count2 * idf[word].powi(2))
+            })
+            .sum::<f32>()
+            / denominator
+    }
+
+    fn create_graph(sentences: &[(String, String)], threshold: f32) -> Graph {
+        let (vectors, idf) = Self::get_sentence_vectors(sentences);
+        let mut graph = vectors
+            .iter()
+            .map(|(sentence, _)| (sentence.to_string(), HashMap::new()))
+            .collect::<Graph>();
+
+        #[cfg(feature = "parallel")]
+        let edges = (0..vectors.len())
+            .into_par_iter()
+            .flat_map_iter(|i| (i + 1..vectors.len()).map(move |j| (i, j)))
+            .filter(|(i, j)| vectors[*i].0 != vectors[*j].0)
+            .map(|(i, j)| {
+                (
+                    i,
+                    j,
+                    Self::idf_modified_cosine(&vectors[i].1, &vectors[j].1, &idf),
+                )
+            })
+            .filter(|(_, _, similarity)| *similarity > 0.0 && *similarity >= threshold)
+            .collect::<Vec<(usize, usize, f32)>>();
+
+        #[cfg(not(feature = "parallel"))]
+        let edges = (0..vectors.len())
+            .flat_map(|i| (i + 1..vectors.len()).map(move |j| (i, j)))
+            .filter(|(i, j)| vectors[*i].0 != vectors[*j].0)
+            .map(|(i, j)| {
+                (
+                    i,
+                    j,
+                    Self::idf_modified_cosine(&vectors[i].1, &vectors[j].1, &idf),
+                )
+            })
+            .filter(|(_, _, similarity)| *similarity > 0.0 && *similarity >= threshold)
+            .collect::<Vec<(usize, usize, f32)>>();
+
+        edges.into_iter().for_each(|(i, j, similarity)| {
+            let (sentence1, sentence2) = (vectors[i].0, vectors[j].0);
+            graph
+                .entry(sentence1.to_string())
+                .or_default()
+                .insert(sentence2.to_string(), similarity);
+            graph
+                .entry(sentence2.to_string())
+                .or_default()
+                .insert(sentence1.to_string(), similarity);
+        });
+
+        graph
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::common::{Punctuation, Stopwords, Text};
+
+type SimilarityThreshold = f32;
+type DampingFactor = f32;
+type Tolerance = f32;
+
+/// The parameters to be used in the LexRank algorithm.
+pub enum LexRankParams<'a> {
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `threshold` - The minimum TF-IDF cosine similarity for two sentences to be linked, defaults to 0.1.
+    /// * `damping_factor` - The damping factor to be used in the graph, defaults to 0.85.
+    /// * `tolerance` - The minimum difference between iterations to stop the algorithm, defaults to 0.00005.
+    WithDefaults(Text<'a>, Stopwords<'a>),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `threshold` - The minimum TF-IDF cosine similarity for two sentences to be linked.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `damping_factor` - The damping factor to be used in the graph, defaults to 0.85.
+    /// * `tolerance` - The minimum difference between iterations to stop the algorithm, defaults to 0.00005.
+    WithDefaultsAndThreshold(Text<'a>, Stopwords<'a>, SimilarityThreshold),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `punctuation` - Optional list of punctuation symbols.
+    /// 4. `threshold` - The minimum TF-IDF cosine similarity for two sentences to be linked.
+    /// 5. `damping_factor` - The damping factor to be used in the graph.
+    /// 6. `tolerance` - The minimum difference between iterations to stop the algorithm.
+    All(
+        Text<'a>,
+        Stopwords<'a>,
+        Punctuation<'a>,
+        SimilarityThreshold,
+        DampingFactor,
+        Tolerance,
+    ),
+}
+
+impl<'a> LexRankParams<'a> {
+    /// Returns the params to be used in the LexRank algorithm.
+    pub fn get_params(
+        &self,
+    ) -> (
+        Text<'_>,
+        Stopwords<'_>,
+        Punctuation<'_>,
+        SimilarityThreshold,
+        DampingFactor,
+        Tolerance,
+    ) {
+        match self {
+            LexRankParams::WithDefaults(text, stop_words) => {
+                (text, stop_words, None, 0.1, 0.85, 0.00005)
+            }
+            LexRankParams::WithDefaultsAndThreshold(text, stop_words, threshold) => {
+                (text, stop_words, None, *threshold, 0.85, 0.00005)
+            }
+            LexRankParams::All(
+                text,
+                stop_words,
+                punctuation,
+                threshold,
+                damping_factor,
+                tolerance,
+            ) => (
+                text,
+                stop_words,
+                *punctuation,
+                *threshold,
+                *damping_factor,
+                *tolerance,
+            ),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+mod lex_rank_logic;
+pub mod lex_rank_params;
+use lex_rank_logic::LexRankLogic;
+pub use lex_rank_params::LexRankParams;
+
+use crate::{
+    common::{get_ranked_scores, get_ranked_strings},
+    tokenizer::Tokenizer,
+};
+
+pub struct LexRank(HashMap<String, f32>);
+
+impl LexRank {
+    /// Create a new LexRank instance.
+    pub fn new(params: LexRankParams) -> Self {
+        let (text, stop_words, punctuation, threshold, damping, tol) = params.get_params();
+        let tokenizer = Tokenizer::new(text, stop_words, punctuation);
+        let sentences = text
+            .unicode_sentences()
+            .map(|s| s.trim().to_string())
+            .zip(tokenizer.sync_split_into_sentences())
+            .collect::<Vec<(String, String)>>();
+
+        Self(LexRankLogic::build_lex_rank(
+            &sentences, threshold, damping, tol,
+        ))
+    }
+
+    /// Gets the score of a sentence, as written in the original text.
+    pub fn get_sentence_score(&self, sentence: &str) -> f32 {
+        *self.0.get(sentence).unwrap_or(&0.0)
+    }
+
+    /// Gets the top n sentences with the highest score.
+    pub fn get_ranked_sentences(&self, n: usize) -> Vec<String> {
+        get_ranked_strings(&self.0, n)
+    }
+
+    /// Gets the top n sentences with the highest score and their score.
+    pub fn get_ranked_sentence_scores(&self, n: usize) -> Vec<(String, f32)> {
+        get_ranked_scores(&self.0, n)
+    }
+
+    /// Gets the sentence scores map.
+    pub fn get_sentence_scores_map(&self) -> &HashMap<String, f32> {
+        &self.0
+    }
+}
@@ -15,19 +15,52 @@
 
 mod common;
 
+#[cfg(feature = "bm25")]
+pub mod bm25;
+
 #[cfg(feature = "co_occurrence")]
 pub mod co_occurrence;
 
+#[cfg(feature = "embed_rank")]
+pub mod embed_rank;
+
+#[cfg(feature = "k_core")]
+pub mod k_core;
+
+#[cfg(feature = "kp_miner")]
+pub mod kp_miner;
+
+#[cfg(feature = "lex_rank")]
+pub mod lex_rank;
+
+#[cfg(feature = "multipartite_rank")]
+pub mod multipartite_rank;
+
+#[cfg(feature = "position_rank")]
+pub mod position_rank;
+
 #[cfg(feature = "rake")]
 pub mod rake;
 
+#[cfg(feature = "sg_rank")]
+pub mod sg_rank;
+
+#[cfg(feature = "single_rank")]
+pub mod single_rank;
+
 #[cfg(feature = "text_rank")]
 pub mod text_rank;
 
 #[cfg(feature = "tf_idf")]
 pub mod tf_idf;
 
+#[cfg(feature = "topic_rank")]
+pub mod topic_rank;
+
 pub mod tokenizer;
 
+#[cfg(feature = "yake")]
+pub mod yake;
+
 #[cfg(test)]
 mod tests;
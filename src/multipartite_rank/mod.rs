@@ -0,0 +1,65 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+mod multipartite_rank_logic;
+pub mod multipartite_rank_params;
+use multipartite_rank_logic::MultipartiteRankLogic;
+pub use multipartite_rank_params::MultipartiteRankParams;
+
+use crate::{
+    common::{get_ranked_scores, get_ranked_strings},
+    tokenizer::Tokenizer,
+};
+
+pub struct MultipartiteRank(HashMap<String, f32>);
+
+impl MultipartiteRank {
+    /// Create a new MultipartiteRank instance.
+    pub fn new(params: MultipartiteRankParams) -> Self {
+        let (text, stop_words, punctuation, threshold, alpha, damping, tol, phrase_length) =
+            params.get_params();
+        let tokenizer = Tokenizer::new(text, stop_words, punctuation);
+
+        Self(MultipartiteRankLogic::build_multipartite_rank(
+            tokenizer.sync_split_into_phrases(phrase_length),
+            threshold,
+            alpha,
+            damping,
+            tol,
+        ))
+    }
+
+    /// Gets the score of a phrase.
+    pub fn get_phrase_score(&self, phrase: &str) -> f32 {
+        *self.0.get(phrase).unwrap_or(&0.0)
+    }
+
+    /// Gets the top n phrases with the highest score.
+    pub fn get_ranked_phrases(&self, n: usize) -> Vec<String> {
+        get_ranked_strings(&self.0, n)
+    }
+
+    /// Gets the top n phrases with the highest score and their score.
+    pub fn get_ranked_phrase_scores(&self, n: usize) -> Vec<(String, f32)> {
+        get_ranked_scores(&self.0, n)
+    }
+
+    /// Gets the phrase scores map.
+    pub fn get_phrase_scores_map(&self) -> &HashMap<String, f32> {
+        &self.0
+    }
+}
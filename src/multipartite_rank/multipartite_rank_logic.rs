@@ -0,0 +1,117 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+use crate::{
+    text_rank::{Graph, TextRankLogic},
+    topic_rank::{Candidates, TopicRankLogic},
+};
+
+pub struct MultipartiteRankLogic;
+
+impl MultipartiteRankLogic {
+    pub fn build_multipartite_rank(
+        phrases: Vec<String>,
+        threshold: f32,
+        alpha: f32,
+        damping: f32,
+        tol: f32,
+    ) -> HashMap<String, f32> {
+        let candidates = TopicRankLogic::get_candidates(phrases);
+        let topics = TopicRankLogic::cluster_candidates(&candidates, threshold);
+        let mut graph = Self::create_graph(&topics, &candidates);
+        Self::boost_first_candidates(&mut graph, &topics, &candidates, alpha);
+        let (candidate_rank, _) = TextRankLogic::rank_graph(&graph, damping, tol, None);
+        candidate_rank
+    }
+
+    /// Creates the multipartite graph: every candidate is linked to the candidates of the other
+    /// topics, while candidates of the same topic are never linked.
+    fn create_graph(topics: &[Vec<usize>], candidates: &Candidates) -> Graph {
+        let mut topic_indexes = vec![0_usize; candidates.len()];
+        topics.iter().enumerate().for_each(|(topic, members)| {
+            members.iter().for_each(|i| topic_indexes[*i] = topic);
+        });
+        let mut graph = candidates
+            .iter()
+            .map(|(candidate, _)| (candidate.to_string(), HashMap::new()))
+            .collect::<Graph>();
+
+        (0..candidates.len())
+            .flat_map(|i| (i + 1..candidates.len()).map(move |j| (i, j)))
+            .filter(|(i, j)| topic_indexes[*i] != topic_indexes[*j])
+            .for_each(|(i, j)| {
+                let weight = TopicRankLogic::get_topic_weight(&[i], &[j], candidates);
+
+                if weight > 0.0 {
+                    let (candidate1, candidate2) = (&candidates[i].0, &candidates[j].0);
+                    graph
+                        .entry(candidate1.to_string())
+                        .or_default()
+                        .insert(candidate2.to_string(), weight);
+                    graph
+                        .entry(candidate2.to_string())
+                        .or_default()
+                        .insert(candidate1.to_string(), weight);
+                }
+            });
+
+        graph
+    }
+
+    /// Increases the weight of the edges going into the first appearing candidate of every topic,
+    /// by the weights going into the other candidates of the topic from the same node, scaled by
+    /// `alpha` and the exponential of the inverse position of the first candidate.
+    fn boost_first_candidates(
+        graph: &mut Graph,
+        topics: &[Vec<usize>],
+        candidates: &Candidates,
+        alpha: f32,
+    ) {
+        let boosts = topics
+            .iter()
+            .filter(|topic| topic.len() > 1)
+            .flat_map(|topic| {
+                let (first, first_positions) = &candidates[topic[0]];
+                let position_weight = (1.0 / (1.0 + first_positions[0] as f32)).exp();
+                let graph = &*graph;
+
+                graph[first].keys().filter_map(move |neighbor| {
+                    let boost = topic[1..]
+                        .iter()
+                        .filter_map(|i| graph[&candidates[*i].0].get(neighbor))
+                        .sum::<f32>();
+
+                    if boost > 0.0 {
+                        Some((first, neighbor, boost * alpha * position_weight))
+                    } else {
+                        None
+                    }
+                })
+            })
+            .map(|(first, neighbor, boost)| (first.to_string(), neighbor.to_string(), boost))
+            .collect::<Vec<(String, String, f32)>>();
+
+        boosts.into_iter().for_each(|(first, neighbor, boost)| {
+            if let Some(weight) = graph
+                .get_mut(&first)
+                .and_then(|edges| edges.get_mut(&neighbor))
+            {
+                *weight += boost;
+            }
+        });
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::common::{PhraseLength, Punctuation, Stopwords, Text};
+
+type Alpha = f32;
+type ClusteringThreshold = f32;
+type DampingFactor = f32;
+type Tolerance = f32;
+
+/// The parameters to be used in the MultipartiteRank algorithm.
+pub enum MultipartiteRankParams<'a> {
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `clustering_threshold` - The maximum average Jaccard distance between two topics for them to be merged, defaults to 0.74.
+    /// * `alpha` - The strength of the boost given to the first appearing candidate of every topic, defaults to 1.1.
+    /// * `damping_factor` - The damping factor to be used in the graph, defaults to 0.85.
+    /// * `tolerance` - The minimum difference between iterations to stop the algorithm, defaults to 0.00005.
+    WithDefaults(Text<'a>, Stopwords<'a>),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `phrase_length` - Optional maximum length of the candidate phrases.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `clustering_threshold` - The maximum average Jaccard distance between two topics for them to be merged, defaults to 0.74.
+    /// * `alpha` - The strength of the boost given to the first appearing candidate of every topic, defaults to 1.1.
+    /// * `damping_factor` - The damping factor to be used in the graph, defaults to 0.85.
+    /// * `tolerance` - The minimum difference between iterations to stop the algorithm, defaults to 0.00005.
+    WithDefaultsAndPhraseLength(Text<'a>, Stopwords<'a>, PhraseLength),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `punctuation` - Optional list of punctuation symbols.
+    /// 4. `clustering_threshold` - The maximum average Jaccard distance between two topics for them to be merged.
+    /// 5. `alpha` - The strength of the boost given to the first appearing candidate of every topic.
+    /// 6. `damping_factor` - The damping factor to be used in the graph.
+    /// 7. `tolerance` - The minimum difference between iterations to stop the algorithm.
+    /// 8. `phrase_length` - Optional maximum length of the candidate phrases.
+    All(
+        Text<'a>,
+        Stopwords<'a>,
+        Punctuation<'a>,
+        ClusteringThreshold,
+        Alpha,
+        DampingFactor,
+        Tolerance,
+        PhraseLength,
+    ),
+}
+
+impl<'a> MultipartiteRankParams<'a> {
+    /// Returns the params to be used in the MultipartiteRank algorithm.
+    pub fn get_params(
+        &self,
+    ) -> (
+        Text<'_>,
+        Stopwords<'_>,
+        Punctuation<'_>,
+        ClusteringThreshold,
+        Alpha,
+        DampingFactor,
+        Tolerance,
+        PhraseLength,
+    ) {
+        match self {
+            MultipartiteRankParams::WithDefaults(text, stop_words) => {
+                (text, stop_words, None, 0.74, 1.1, 0.85, 0.00005, None)
+            }
+            MultipartiteRankParams::WithDefaultsAndPhraseLength(
+                text,
+                stop_words,
+                phrase_length,
+            ) => (
+                text,
+                stop_words,
+                None,
+                0.74,
+                1.1,
+                0.85,
+                0.00005,
+                *phrase_length,
+            ),
+            MultipartiteRankParams::All(
+                text,
+                stop_words,
+                punctuation,
+                clustering_threshold,
+                alpha,
+                damping_factor,
+                tolerance,
+                phrase_length,
+            ) => (
+                text,
+                stop_words,
+                *punctuation,
+                *clustering_threshold,
+                *alpha,
+                *damping_factor,
+                *tolerance,
+                *phrase_length,
+            ),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+use crate::text_rank::{EdgeWeighting, TextRankLogic};
+
+pub struct PositionRankLogic;
+
+impl PositionRankLogic {
+    pub fn build_position_rank(
+        words: Vec<String>,
+        phrases: Vec<String>,
+        window_size: usize,
+        damping: f32,
+        tol: f32,
+    ) -> (HashMap<String, f32>, HashMap<String, f32>) {
+        let position_bias = Self::create_position_bias(&words);
+        let (_, word_rank, _, _) = TextRankLogic::build_text_rank(
+            words,
+            Vec::new(),
+            window_size,
+            damping,
+            tol,
+            Some(&position_bias),
+            EdgeWeighting::Uniform,
+        );
+        let phrase_rank = TextRankLogic::rank_phrases_by_sum(phrases, &word_rank);
+        (word_rank, phrase_rank)
+    }
+
+    /// Every occurrence of a word adds the inverse of its (1-based) position to its bias,
+    /// so words appearing early and often get a larger share of the teleport probability.
+    fn create_position_bias(words: &[String]) -> HashMap<String, f32> {
+        words
+            .iter()
+            .enumerate()
+            .fold(HashMap::new(), |mut acc, (i, word)| {
+                *acc.entry(word.to_string()).or_insert(0.0) += 1.0 / (i + 1) as f32;
+                acc
+            })
+    }
+}
@@ -18,25 +18,81 @@ use std::collections::HashMap;
 mod rake_logic;
 pub mod rake_params;
 use rake_logic::RakeLogic;
-pub use rake_params::RakeParams;
+pub use rake_params::{RakeMetric, RakeOptions, RakeParams};
 
-use crate::common::{get_ranked_scores, get_ranked_strings};
+use crate::{
+    common::{get_phrase_offsets, get_ranked_scores, get_ranked_strings},
+    tokenizer::{SurfaceForms, Tokenize, Tokenizer},
+};
 
 pub struct Rake {
     word_scores: HashMap<String, f32>,
     phrase_scores: HashMap<String, f32>,
+    offsets: HashMap<String, Vec<(usize, usize)>>,
+    surface_forms: SurfaceForms,
 }
 
 impl Rake {
     /// Create a new Rake instance.
     pub fn new(params: RakeParams) -> Self {
+        Self::with_options(params, RakeOptions::default())
+    }
+
+    /// Create a new Rake instance with the given scoring options.
+    pub fn with_options(params: RakeParams, options: RakeOptions) -> Self {
         let (text, stopwords, punctuation, phrase_len) = params.get_rake_params();
-        let (word_scores, phrase_scores) =
-            RakeLogic::build_rake(text, stopwords, punctuation, phrase_len);
+        let tokenizer = Tokenizer::new(text, stopwords, punctuation)
+            .with_stop_phrases(options.stop_phrases.unwrap_or(&[]));
+        let (word_scores, phrase_scores) = RakeLogic::build_rake(
+            tokenizer.split_into_phrases(phrase_len),
+            options
+                .adjoining_frequency
+                .map(|min_frequency| tokenizer.split_into_adjoined_phrases(min_frequency)),
+            options.metric,
+            options.min_words,
+            options.max_words,
+        );
+
+        let offsets = get_phrase_offsets(text, word_scores.keys().chain(phrase_scores.keys()));
+
+        Self {
+            phrase_scores,
+            word_scores,
+            offsets,
+            surface_forms: tokenizer.get_surface_forms(),
+        }
+    }
+
+    /// Create a new Rake instance splitting the text into phrases with a custom tokenizer, the stop
+    /// words and punctuation of the parameters are left to it.
+    pub fn with_tokenizer<T: Tokenize>(params: RakeParams, tokenizer: &T) -> Self {
+        Self::with_tokenizer_and_options(params, tokenizer, RakeOptions::default())
+    }
+
+    /// Create a new Rake instance splitting the text with a custom tokenizer, with the given scoring
+    /// options, see `Rake::with_tokenizer`. The stop phrases are left to the tokenizer, and adjoined
+    /// phrases need the stop words in the text, so they are not added.
+    pub fn with_tokenizer_and_options<T: Tokenize>(
+        params: RakeParams,
+        tokenizer: &T,
+        options: RakeOptions,
+    ) -> Self {
+        let (text, _, _, phrase_len) = params.get_rake_params();
+        let (word_scores, phrase_scores) = RakeLogic::build_rake(
+            tokenizer.phrases(phrase_len),
+            None,
+            options.metric,
+            options.min_words,
+            options.max_words,
+        );
+
+        let offsets = get_phrase_offsets(text, word_scores.keys().chain(phrase_scores.keys()));
 
         Self {
             phrase_scores,
             word_scores,
+            offsets,
+            surface_forms: tokenizer.surface_forms(),
         }
     }
 
@@ -60,6 +116,18 @@ impl Rake {
         get_ranked_scores(&self.phrase_scores, n)
     }
 
+    /// Gets the top n phrases with the highest score and their score, written in the most frequent
+    /// form of their words in the text, e.g. "NASA" instead of "nasa".
+    pub fn get_ranked_surface_phrase_scores(&self, n: usize) -> Vec<(String, f32)> {
+        self.surface_forms
+            .apply(get_ranked_scores(&self.phrase_scores, n))
+    }
+
+    /// Gets the most frequent form of every word in the text.
+    pub fn get_surface_forms(&self) -> &SurfaceForms {
+        &self.surface_forms
+    }
+
     /// Gets the score of a word.
     pub fn get_keyword_score(&self, word: &str) -> f32 {
         *self.word_scores.get(word).unwrap_or(&0.0)
@@ -70,6 +138,29 @@ impl Rake {
         *self.phrase_scores.get(phrase).unwrap_or(&0.0)
     }
 
+    /// Gets the character offsets, as `(start, end)` with an exclusive end, of every occurrence of a
+    /// word in the original text.
+    pub fn get_keyword_offsets(&self, word: &str) -> &[(usize, usize)] {
+        self.offsets.get(word).map_or(&[], |offsets| offsets)
+    }
+
+    /// Gets the character offsets, as `(start, end)` with an exclusive end, of every occurrence of a
+    /// phrase in the original text.
+    pub fn get_phrase_offsets(&self, phrase: &str) -> &[(usize, usize)] {
+        self.offsets.get(phrase).map_or(&[], |offsets| offsets)
+    }
+
+    /// Gets the top n phrases with the highest score and the character offsets of their occurrences.
+    pub fn get_ranked_phrase_offsets(&self, n: usize) -> Vec<(String, Vec<(usize, usize)>)> {
+        get_ranked_strings(&self.phrase_scores, n)
+            .into_iter()
+            .map(|phrase| {
+                let offsets = self.get_phrase_offsets(&phrase).to_vec();
+                (phrase, offsets)
+            })
+            .collect()
+    }
+
     /// Gets the base hashmap of words and their score.
     pub fn get_word_scores_map(&self) -> &HashMap<String, f32> {
         &self.word_scores
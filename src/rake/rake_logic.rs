@@ -18,10 +18,13 @@ pub struct RakeLogic;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use crate::common::{PhraseLength, Punctuation, Stopwords, Text};
-use crate::tokenizer::Tokenizer;
 use std::collections::HashMap;
 
+use super::{
+    rake_params::{MaxPhraseWords, MinPhraseWords},
+    RakeMetric,
+};
+
 fn str_to_strig_vector(text: &str) -> Vec<String> {
     text.split_whitespace().map(|w| w.to_string()).collect()
 }
@@ -30,9 +33,15 @@ fn calculate_word_score(
     word: &str,
     frequency: &f32,
     word_degree: &HashMap<&str, f32>,
+    metric: RakeMetric,
 ) -> (String, f32) {
     let degree = word_degree.get(word).unwrap_or(&0.0);
-    (word.to_string(), degree / frequency)
+    let score = match metric {
+        RakeMetric::DegreeToFrequency => degree / frequency,
+        RakeMetric::Degree => degree + frequency,
+        RakeMetric::Frequency => *frequency,
+    };
+    (word.to_string(), score)
 }
 
 fn calculate_phrase_score(phrase: &[String], word_scores: &HashMap<String, f32>) -> (String, f32) {
@@ -44,34 +53,63 @@ fn calculate_phrase_score(phrase: &[String], word_scores: &HashMap<String, f32>)
 }
 
 impl RakeLogic {
+    /// Scores the words and phrases of the given phrases, adding the adjoined phrases if any.
     pub fn build_rake(
-        text: Text,
-        stopwords: Stopwords,
-        punctuation: Punctuation,
-        phrase_len: PhraseLength,
+        phrases: Vec<String>,
+        adjoined_phrases: Option<Vec<String>>,
+        metric: RakeMetric,
+        min_words: MinPhraseWords,
+        max_words: MaxPhraseWords,
     ) -> (HashMap<String, f32>, HashMap<String, f32>) {
-        let phrases = Self::split_into_phrases(text, stopwords, punctuation, phrase_len);
+        let phrases = Self::split_into_phrases(phrases, min_words, max_words);
         let word_scores = Self::calculate_word_scores(
             Self::generate_word_frequency(&phrases),
             Self::generate_word_degree(&phrases),
+            metric,
         );
-        let phrase_scores = Self::calculate_phrase_scores(&phrases, &word_scores);
+        let mut phrase_scores = Self::calculate_phrase_scores(&phrases, &word_scores);
+
+        if let Some(adjoined_phrases) = adjoined_phrases {
+            Self::add_adjoined_phrases(&mut phrase_scores, adjoined_phrases, &word_scores);
+        }
+
         (word_scores, phrase_scores)
     }
 
+    /// Adds the adjoined phrases, scored by the average score of their non stopword words.
+    fn add_adjoined_phrases(
+        phrase_scores: &mut HashMap<String, f32>,
+        adjoined_phrases: Vec<String>,
+        word_scores: &HashMap<String, f32>,
+    ) {
+        adjoined_phrases.into_iter().for_each(|phrase| {
+            let scores = phrase
+                .split_whitespace()
+                .filter_map(|word| word_scores.get(word))
+                .collect::<Vec<&f32>>();
+
+            if !scores.is_empty() {
+                let score = scores.iter().copied().sum::<f32>() / scores.len() as f32;
+                phrase_scores.entry(phrase).or_insert(score);
+            }
+        });
+    }
+
     fn split_into_phrases(
-        text: &str,
-        stopwords: Stopwords,
-        punctuation: Punctuation,
-        length: PhraseLength,
+        phrases: Vec<String>,
+        min_words: MinPhraseWords,
+        max_words: MaxPhraseWords,
     ) -> Vec<Vec<String>> {
-        let phrases = Tokenizer::new(text, stopwords, punctuation).split_into_phrases(length);
+        let is_within_bounds = |phrase: &Vec<String>| {
+            phrase.len() >= min_words && max_words.map_or(true, |max| phrase.len() <= max)
+        };
 
         #[cfg(feature = "parallel")]
         {
             phrases
                 .par_iter()
                 .map(|sentence| str_to_strig_vector(sentence))
+                .filter(is_within_bounds)
                 .collect::<Vec<Vec<String>>>()
         }
 
@@ -80,6 +118,7 @@ impl RakeLogic {
             phrases
                 .iter()
                 .map(|sentence| str_to_strig_vector(sentence))
+                .filter(is_within_bounds)
                 .collect::<Vec<Vec<String>>>()
         }
     }
@@ -111,24 +150,18 @@ impl RakeLogic {
     fn parallel_word_frequency(phrases: &[Vec<String>]) -> HashMap<&str, f32> {
         phrases
             .par_iter()
-            .fold(
-                || HashMap::<&str, f32>::new(),
-                |mut acc, phrase| {
-                    phrase.iter().for_each(|word| {
-                        *acc.entry(word).or_insert(0.0) += 1.0;
-                    });
-                    acc
-                },
-            )
-            .reduce(
-                || HashMap::<&str, f32>::new(),
-                |mut acc, hmap| {
-                    hmap.iter().for_each(|(word, count)| {
-                        *acc.entry(word).or_insert(0.0) += count;
-                    });
-                    acc
-                },
-            )
+            .fold(HashMap::<&str, f32>::new, |mut acc, phrase| {
+                phrase.iter().for_each(|word| {
+                    *acc.entry(word).or_insert(0.0) += 1.0;
+                });
+                acc
+            })
+            .reduce(HashMap::<&str, f32>::new, |mut acc, hmap| {
+                hmap.iter().for_each(|(word, count)| {
+                    *acc.entry(word).or_insert(0.0) += count;
+                });
+                acc
+            })
     }
 
     fn generate_word_degree(phrases: &[Vec<String>]) -> HashMap<&str, f32> {
@@ -161,38 +194,35 @@ impl RakeLogic {
     fn parallel_word_degree(phrases: &[Vec<String>]) -> HashMap<&str, f32> {
         phrases
             .par_iter()
-            .fold(
-                || HashMap::<&str, f32>::new(),
-                |mut acc, phrase| {
-                    let len = phrase.len() as f32 - 1.0;
-                    phrase.iter().for_each(|word| {
-                        acc.entry(word)
-                            .and_modify(|count| *count += len)
-                            .or_insert(len);
-                    });
-                    acc
-                },
-            )
-            .reduce(
-                || HashMap::<&str, f32>::new(),
-                |mut acc, hmap| {
-                    hmap.iter().for_each(|(word, degree)| {
-                        *acc.entry(word).or_insert(0.0) += degree;
-                    });
-                    acc
-                },
-            )
+            .fold(HashMap::<&str, f32>::new, |mut acc, phrase| {
+                let len = phrase.len() as f32 - 1.0;
+                phrase.iter().for_each(|word| {
+                    acc.entry(word)
+                        .and_modify(|count| *count += len)
+                        .or_insert(len);
+                });
+                acc
+            })
+            .reduce(HashMap::<&str, f32>::new, |mut acc, hmap| {
+                hmap.iter().for_each(|(word, degree)| {
+                    *acc.entry(word).or_insert(0.0) += degree;
+                });
+                acc
+            })
     }
 
     fn calculate_word_scores(
         word_frequency: HashMap<&str, f32>,
         word_degree: HashMap<&str, f32>,
+        metric: RakeMetric,
     ) -> HashMap<String, f32> {
         #[cfg(feature = "parallel")]
         {
             word_frequency
                 .par_iter()
-                .map(|(word, frequency)| calculate_word_score(word, frequency, &word_degree))
+                .map(|(word, frequency)| {
+                    calculate_word_score(word, frequency, &word_degree, metric)
+                })
                 .collect::<HashMap<String, f32>>()
         }
 
@@ -200,7 +230,9 @@ impl RakeLogic {
         {
             word_frequency
                 .iter()
-                .map(|(word, frequency)| calculate_word_score(word, frequency, &word_degree))
+                .map(|(word, frequency)| {
+                    calculate_word_score(word, frequency, &word_degree, metric)
+                })
                 .collect::<HashMap<String, f32>>()
         }
     }
@@ -15,6 +15,61 @@
 
 use crate::common::{PhraseLength, Punctuation, Stopwords, Text};
 
+/// The minimum number of times two phrases have to be seen adjoined by stopwords to be joined, e.g.
+/// "axis of evil", `None` disables the adjoining.
+pub type AdjoiningFrequency = Option<usize>;
+
+/// The minimum number of words of a candidate phrase, shorter phrases are dropped.
+pub type MinPhraseWords = usize;
+
+/// Optional maximum number of words of a candidate phrase, longer phrases are dropped instead of split.
+pub type MaxPhraseWords = Option<usize>;
+
+/// Optional list of multi-word stop phrases, e.g. "for example", that break the candidate phrases.
+pub type StopPhrases<'a> = Option<&'a [String]>;
+
+/// The word score metric used by the RAKE algorithm.
+#[derive(Clone, Copy, Default)]
+pub enum RakeMetric {
+    /// `deg(w) / freq(w)`, favours words that mostly appear in long phrases.
+    #[default]
+    DegreeToFrequency,
+    /// `deg(w)`, the number of words of the phrases containing the word, favours words that appear often and in long phrases.
+    Degree,
+    /// `freq(w)`, favours words that appear often, regardless of the phrases length.
+    Frequency,
+}
+
+/// The options of the RAKE algorithm on top of its parameters, see `Rake::with_options`.
+#[derive(Clone, Copy)]
+pub struct RakeOptions<'a> {
+    /// The word score metric, defaults to `RakeMetric::DegreeToFrequency`.
+    pub metric: RakeMetric,
+    /// Optional minimum number of times two phrases have to be seen adjoined by stopwords, in the same
+    /// order, to be added as a phrase with their interior stopwords, defaults to none.
+    pub adjoining_frequency: AdjoiningFrequency,
+    /// The minimum number of words of a candidate phrase, e.g. 2 drops single-word candidates,
+    /// defaults to 1.
+    pub min_words: MinPhraseWords,
+    /// Optional maximum number of words of a candidate phrase, longer candidates are dropped, defaults
+    /// to none.
+    pub max_words: MaxPhraseWords,
+    /// Optional list of multi-word stop phrases, e.g. "for example" or "in order to", defaults to none.
+    pub stop_phrases: StopPhrases<'a>,
+}
+
+impl Default for RakeOptions<'_> {
+    fn default() -> Self {
+        Self {
+            metric: RakeMetric::default(),
+            adjoining_frequency: None,
+            min_words: 1,
+            max_words: None,
+            stop_phrases: None,
+        }
+    }
+}
+
 /// The `RakeParams` enum represents the parameters for the RAKE (Rapid Automatic Keyword Extraction) algorithm.
 /// It has two variants: `WithDefaults` and `All`.
 pub enum RakeParams<'a> {
@@ -0,0 +1,185 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::text_rank::{Graph, TextRankLogic};
+
+/// The candidates of the text, in order of first appearance, with the word positions of every occurrence.
+type Candidates = Vec<(String, Vec<usize>)>;
+
+pub struct SgRankLogic;
+
+impl SgRankLogic {
+    pub fn build_sg_rank(
+        phrases: Vec<String>,
+        min_frequency: usize,
+        max_candidates: usize,
+        window_size: usize,
+        cutoff: usize,
+        damping: f32,
+        tol: f32,
+    ) -> HashMap<String, f32> {
+        let candidates = Self::get_candidates(phrases, min_frequency, cutoff);
+        let weights = Self::get_statistical_weights(&candidates, cutoff);
+        let candidates = Self::select_candidates(candidates, &weights, max_candidates);
+        let (candidate_rank, _) = TextRankLogic::rank_graph(
+            &Self::create_graph(&candidates, &weights, window_size),
+            damping,
+            tol,
+            None,
+        );
+        candidate_rank
+    }
+
+    /// Gets the candidates first seen before the cutoff position and seen at least `min_frequency` times.
+    fn get_candidates(phrases: Vec<String>, min_frequency: usize, cutoff: usize) -> Candidates {
+        let mut position = 0_usize;
+        let mut candidate_indexes = HashMap::<String, usize>::new();
+        let mut candidates = Candidates::new();
+
+        phrases.into_iter().for_each(|phrase| {
+            let length = phrase.split_whitespace().count();
+
+            match candidate_indexes.get(&phrase) {
+                Some(i) => candidates[*i].1.push(position),
+                None if position < cutoff => {
+                    candidate_indexes.insert(phrase.to_string(), candidates.len());
+                    candidates.push((phrase, vec![position]));
+                }
+                None => {}
+            }
+
+            position += length;
+        });
+
+        candidates.retain(|(_, positions)| positions.len() >= min_frequency);
+        candidates
+    }
+
+    /// The statistical weight of every candidate: its frequency, minus the times it is subsumed by
+    /// longer candidates, times the log of the cutoff over its first position, times its length.
+    fn get_statistical_weights(candidates: &Candidates, cutoff: usize) -> HashMap<String, f32> {
+        let padded = candidates
+            .iter()
+            .map(|(candidate, positions)| (format!(" {} ", candidate), positions.len()))
+            .collect::<Vec<(String, usize)>>();
+        let get_weight = |(candidate, positions): &(String, Vec<usize>)| {
+            let pattern = format!(" {} ", candidate);
+            let subsumed = padded
+                .iter()
+                .filter(|(other, _)| other.len() > pattern.len() && other.contains(&pattern))
+                .map(|(_, frequency)| *frequency)
+                .sum::<usize>();
+            let frequency = positions.len().saturating_sub(subsumed).max(1) as f32;
+            let position = (cutoff as f32 / (positions[0] + 1) as f32).ln();
+            let length = candidate.split_whitespace().count() as f32;
+            (candidate.to_string(), frequency * position * length)
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            candidates
+                .par_iter()
+                .map(get_weight)
+                .collect::<HashMap<String, f32>>()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            candidates
+                .iter()
+                .map(get_weight)
+                .collect::<HashMap<String, f32>>()
+        }
+    }
+
+    /// Keeps the `max_candidates` candidates with the highest statistical weight, in order of appearance.
+    fn select_candidates(
+        candidates: Candidates,
+        weights: &HashMap<String, f32>,
+        max_candidates: usize,
+    ) -> Candidates {
+        if candidates.len() <= max_candidates {
+            return candidates;
+        }
+
+        let mut ranked = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, (candidate, _))| (i, weights[candidate]))
+            .collect::<Vec<(usize, f32)>>();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        let mut selected = ranked
+            .into_iter()
+            .take(max_candidates)
+            .map(|(i, _)| i)
+            .collect::<Vec<usize>>();
+        selected.sort_unstable();
+        let mut candidates = candidates.into_iter().map(Some).collect::<Vec<_>>();
+
+        selected
+            .into_iter()
+            .filter_map(|i| candidates[i].take())
+            .collect::<Candidates>()
+    }
+
+    /// Links the candidates occurring within the window, the edge weight is the sum over the
+    /// co-occurrences of the inverse log distance, times the statistical weights of both candidates.
+    fn create_graph(
+        candidates: &Candidates,
+        weights: &HashMap<String, f32>,
+        window_size: usize,
+    ) -> Graph {
+        let mut graph = candidates
+            .iter()
+            .map(|(candidate, _)| (candidate.to_string(), HashMap::new()))
+            .collect::<Graph>();
+
+        (0..candidates.len())
+            .flat_map(|i| (i + 1..candidates.len()).map(move |j| (i, j)))
+            .map(|(i, j)| {
+                let (candidate1, positions1) = &candidates[i];
+                let (candidate2, positions2) = &candidates[j];
+                let proximity = positions1
+                    .iter()
+                    .flat_map(|p1| positions2.iter().map(move |p2| p1.abs_diff(*p2)))
+                    .filter(|distance| *distance > 0 && *distance <= window_size)
+                    .map(|distance| 1.0 / (1.0 + distance as f32).log2())
+                    .sum::<f32>();
+                (
+                    candidate1,
+                    candidate2,
+                    proximity * weights[candidate1] * weights[candidate2],
+                )
+            })
+            .filter(|(_, _, weight)| *weight > 0.0)
+            .for_each(|(candidate1, candidate2, weight)| {
+                graph
+                    .entry(candidate1.to_string())
+                    .or_default()
+                    .insert(candidate2.to_string(), weight);
+                graph
+                    .entry(candidate2.to_string())
+                    .or_default()
+                    .insert(candidate1.to_string(), weight);
+            });
+
+        graph
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::common::{PhraseLength, Punctuation, Stopwords, Text, WindowSize};
+
+type MinFrequency = usize;
+type MaxCandidates = usize;
+type CutoffPosition = usize;
+type DampingFactor = f32;
+type Tolerance = f32;
+
+/// The parameters to be used in the SGRank algorithm.
+pub enum SgRankParams<'a> {
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `min_frequency` - The minimum number of times a candidate has to be seen, defaults to 2.
+    /// * `max_candidates` - The number of candidates with the best statistical score kept in the graph, defaults to 100.
+    /// * `window_size` - The maximum word distance between two linked candidates, defaults to 1500.
+    /// * `cutoff` - The word position after which candidates are ignored, defaults to 3000.
+    /// * `damping_factor` - The damping factor to be used in the graph, defaults to 0.85.
+    /// * `tolerance` - The minimum difference between iterations to stop the algorithm, defaults to 0.00005.
+    /// * `phrase_length` - The maximum length of the candidate phrases, defaults to no limit.
+    WithDefaults(Text<'a>, Stopwords<'a>),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `phrase_length` - Optional maximum length of the candidate phrases.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `min_frequency` - The minimum number of times a candidate has to be seen, defaults to 2.
+    /// * `max_candidates` - The number of candidates with the best statistical score kept in the graph, defaults to 100.
+    /// * `window_size` - The maximum word distance between two linked candidates, defaults to 1500.
+    /// * `cutoff` - The word position after which candidates are ignored, defaults to 3000.
+    /// * `damping_factor` - The damping factor to be used in the graph, defaults to 0.85.
+    /// * `tolerance` - The minimum difference between iterations to stop the algorithm, defaults to 0.00005.
+    WithDefaultsAndPhraseLength(Text<'a>, Stopwords<'a>, PhraseLength),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `punctuation` - Optional list of punctuation symbols.
+    /// 4. `min_frequency` - The minimum number of times a candidate has to be seen.
+    /// 5. `max_candidates` - The number of candidates with the best statistical score kept in the graph.
+    /// 6. `window_size` - The maximum word distance between two linked candidates.
+    /// 7. `cutoff` - The word position after which candidates are ignored.
+    /// 8. `damping_factor` - The damping factor to be used in the graph.
+    /// 9. `tolerance` - The minimum difference between iterations to stop the algorithm.
+    /// 10. `phrase_length` - Optional maximum length of the candidate phrases.
+    All(
+        Text<'a>,
+        Stopwords<'a>,
+        Punctuation<'a>,
+        MinFrequency,
+        MaxCandidates,
+        WindowSize,
+        CutoffPosition,
+        DampingFactor,
+        Tolerance,
+        PhraseLength,
+    ),
+}
+
+impl<'a> SgRankParams<'a> {
+    /// Returns the params to be used in the SGRank algorithm.
+    pub fn get_params(
+        &self,
+    ) -> (
+        Text<'_>,
+        Stopwords<'_>,
+        Punctuation<'_>,
+        MinFrequency,
+        MaxCandidates,
+        WindowSize,
+        CutoffPosition,
+        DampingFactor,
+        Tolerance,
+        PhraseLength,
+    ) {
+        match self {
+            SgRankParams::WithDefaults(text, stop_words) => (
+                text, stop_words, None, 2, 100, 1500, 3000, 0.85, 0.00005, None,
+            ),
+            SgRankParams::WithDefaultsAndPhraseLength(text, stop_words, phrase_length) => (
+                text,
+                stop_words,
+                None,
+                2,
+                100,
+                1500,
+                3000,
+                0.85,
+                0.00005,
+                *phrase_length,
+            ),
+            SgRankParams::All(
+                text,
+                stop_words,
+                punctuation,
+                min_frequency,
+                max_candidates,
+                window_size,
+                cutoff,
+                damping_factor,
+                tolerance,
+                phrase_length,
+            ) => (
+                text,
+                stop_words,
+                *punctuation,
+                *min_frequency,
+                *max_candidates,
+                *window_size,
+                *cutoff,
+                *damping_factor,
+                *tolerance,
+                *phrase_length,
+            ),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+use crate::text_rank::{EdgeWeighting, TextRankLogic};
+
+pub struct SingleRankLogic;
+
+impl SingleRankLogic {
+    /// Ranks words with a PageRank over a graph whose edges are weighted by the number of
+    /// co-occurrences inside the window, and scores phrases by the sum of their word scores.
+    pub fn build_single_rank(
+        words: Vec<String>,
+        phrases: Vec<String>,
+        window_size: usize,
+        damping: f32,
+        tol: f32,
+    ) -> (HashMap<String, f32>, HashMap<String, f32>) {
+        let (_, word_rank, _, _) = TextRankLogic::build_text_rank(
+            words,
+            Vec::new(),
+            window_size,
+            damping,
+            tol,
+            None,
+            EdgeWeighting::Uniform,
+        );
+        let phrase_rank = TextRankLogic::rank_phrases_by_sum(phrases, &word_rank);
+        (word_rank, phrase_rank)
+    }
+}
@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashSet;
+use std::{collections::HashSet, io::BufRead};
 
 use stop_words::{get, LANGUAGE};
 
@@ -60,7 +60,7 @@ If you are passionate about Rust development and looking to kickstart your caree
 "#;
 
 fn get_cs_hashset() -> HashSet<String> {
-    HashSet::from_iter(vec!["c", "computer"].iter().map(|s| s.to_string()))
+    HashSet::from_iter(["c", "computer"].iter().map(|s| s.to_string()))
 }
 
 fn get_stop_words() -> Vec<String> {
@@ -308,128 +308,1752 @@ fn test_tokenize() {
     ));
 }
 
+#[test]
+fn test_tokenize_special_chars() {
+    let text = "Pi is 3.14, the U.S. budget's 1,000 items.";
+
+    let tokenizer = tokenizer::Tokenizer::new(text, &get_stop_words(), None);
+    assert_eq!(
+        tokenizer.sync_split_into_words(),
+        ["pi", "3.14", "u.s", "budget", "1,000", "items"]
+    );
+
+    let tokenizer = tokenizer::Tokenizer::new(text, &get_stop_words(), None)
+        .with_special_char_patterns(&["'s$", "[.,]"])
+        .unwrap();
+    assert_eq!(
+        tokenizer.sync_split_into_words(),
+        ["pi", "314", "budget", "1000", "items"]
+    );
+    assert!(tokenizer::Tokenizer::new(text, &get_stop_words(), None)
+        .with_special_char_patterns(&["("])
+        .is_err());
+}
+
+#[test]
+fn test_tokenize_token_filter() {
+    let tokenizer = tokenizer::Tokenizer::new(
+        "Gene BRCA1 mutations raise cancer risk. Contact jane at 5550100.",
+        &get_stop_words(),
+        None,
+    )
+    .with_token_filter(|word| {
+        if word.starts_with("brca") {
+            None
+        } else if word.chars().any(|c| c.is_ascii_digit()) {
+            Some("<phone>".to_string())
+        } else {
+            Some(word.to_string())
+        }
+    });
+
+    assert_eq!(
+        tokenizer.sync_split_into_phrases(None),
+        [
+            "gene",
+            "mutations raise cancer risk contact jane",
+            "<phone>"
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_max_word_length() {
+    let text = "Payload aGVsbG8gd29ybGQgaGVsbG8gd29ybGQ arrived intact.";
+    let tokenize = |policy| {
+        tokenizer::Tokenizer::new(text, &get_stop_words(), None).with_max_word_length(12, policy)
+    };
+
+    assert_eq!(
+        tokenize(tokenizer::LongWordPolicy::Drop).sync_split_into_phrases(None),
+        ["payload", "arrived intact"]
+    );
+    assert_eq!(
+        tokenize(tokenizer::LongWordPolicy::Truncate).sync_split_into_words(),
+        ["payload", "agvsbg8gd29y", "arrived", "intact"]
+    );
+}
+
+#[test]
+fn test_tokenize_paragraph_split() {
+    let text = "Rust compilers check\nownership rules.\n  \nPython interpreters\nrun scripts.\u{c}\nHaskell types";
+    let tokenize = |paragraph_split| {
+        tokenizer::Tokenizer::new(text, &get_stop_words(), None)
+            .with_paragraph_split(paragraph_split)
+            .sync_split_into_paragraphs()
+    };
+
+    assert_eq!(tokenize(tokenizer::ParagraphSplit::Lines).len(), 5);
+    assert_eq!(
+        tokenize(tokenizer::ParagraphSplit::BlankLines),
+        [
+            "rust compilers check ownership rules",
+            "python interpreters scripts haskell types"
+        ]
+    );
+    assert_eq!(
+        tokenize(tokenizer::ParagraphSplit::delimiters(&["\n  \n", "\u{c}"]).unwrap()),
+        [
+            "rust compilers check ownership rules",
+            "python interpreters scripts",
+            "haskell types"
+        ]
+    );
+    // empty delimiters would match between every character
+    assert_eq!(
+        tokenize(tokenizer::ParagraphSplit::delimiters(&["", "\u{c}"]).unwrap()),
+        [
+            "rust compilers check ownership rules python interpreters scripts",
+            "haskell types"
+        ]
+    );
+    assert_eq!(
+        tokenize(tokenizer::ParagraphSplit::delimiters(&[""]).unwrap()),
+        tokenize(tokenizer::ParagraphSplit::Lines)
+    );
+}
+
+#[test]
+fn test_tokenize_classify_tokens() {
+    use tokenizer::TokenKind::*;
+
+    let tokenizer = tokenizer::Tokenizer::new(
+        "Rust's compiler costs €0, since 2015!",
+        &get_stop_words(),
+        None,
+    );
+
+    assert_eq!(
+        tokenizer.classify_tokens(),
+        [
+            ("rust".to_string(), Word),
+            ("compiler".to_string(), Word),
+            ("costs".to_string(), Word),
+            ("€".to_string(), Symbol),
+            ("0".to_string(), Number),
+            (",".to_string(), Punctuation),
+            ("since".to_string(), Word),
+            ("2015".to_string(), Number),
+            ("!".to_string(), Punctuation),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_word_frequencies() {
+    let tokenizer = tokenizer::Tokenizer::new(
+        "Rust crates depend on crates. Cargo builds Rust crates.",
+        &get_stop_words(),
+        None,
+    );
+    let frequencies = std::collections::HashMap::from([
+        ("rust".to_string(), 2),
+        ("crates".to_string(), 3),
+        ("depend".to_string(), 1),
+        ("cargo".to_string(), 1),
+        ("builds".to_string(), 1),
+    ]);
+
+    assert_eq!(tokenizer.word_frequencies(), frequencies);
+    assert_eq!(tokenizer.sync_word_frequencies(), frequencies);
+}
+
+#[test]
+fn test_tokenize_word_boundary_rules() {
+    let stop_words = ["l", "qu", "il", "et"].map(String::from);
+    let tokenize = |text, rules| {
+        tokenizer::Tokenizer::new(text, &stop_words, None)
+            .with_word_boundary_rules(rules)
+            .sync_split_into_words()
+    };
+    let elisions = tokenizer::WordBoundaryRules::default().with_split_chars(&['\'']);
+
+    assert_eq!(
+        tokenize("L'économie et l'homme qu'il aime", elisions.clone()),
+        ["économie", "homme", "aime"]
+    );
+    assert_eq!(
+        tokenize("AT&T et R&D", elisions.with_join_chars(&['&'])),
+        ["at&t", "r&d"]
+    );
+    assert_eq!(
+        tokenize(
+            "c++ et c#",
+            tokenizer::WordBoundaryRules::default().with_splitter(|text| {
+                text.split(' ')
+                    .map(|token| (token.as_ptr() as usize - text.as_ptr() as usize, token))
+                    .collect()
+            })
+        ),
+        ["c++", "c#"]
+    );
+}
+
+#[test]
+fn test_tokenize_subwords() {
+    let vocabulary = tokenizer::WordPieceVocabulary::from_reader(
+        "[UNK]\nkey\n##word\n##s\nextract\n##ion\nrust\n".as_bytes(),
+    )
+    .unwrap();
+    let tokenizer = tokenizer::Tokenizer::new(
+        "Keywords extraction in Rust, by xylophones.",
+        &get_stop_words(),
+        None,
+    );
+
+    assert_eq!(vocabulary.get_id("##word"), Some(2));
+    assert_eq!(
+        tokenizer.split_into_subwords(&vocabulary),
+        ["key", "##word", "##s", "extract", "##ion", "rust", "[UNK]"]
+    );
+}
+
+#[test]
+fn test_tokenize_paragraph_line_endings() {
+    let text = "Rust compilers check\r\nownership rules.\r\n\r\nPython interpreters\rscripts.\r\r---\r\nHaskell types";
+    let tokenize = |paragraph_split| {
+        tokenizer::Tokenizer::new(text, &get_stop_words(), None)
+            .with_paragraph_split(paragraph_split)
+            .sync_split_into_paragraphs()
+    };
+
+    assert_eq!(
+        tokenize(tokenizer::ParagraphSplit::Lines),
+        [
+            "rust compilers check",
+            "ownership rules",
+            "python interpreters",
+            "scripts",
+            "",
+            "haskell types"
+        ]
+    );
+    assert_eq!(
+        tokenize(tokenizer::ParagraphSplit::BlankLines),
+        [
+            "rust compilers check ownership rules",
+            "python interpreters scripts",
+            "haskell types"
+        ]
+    );
+    assert_eq!(
+        tokenize(tokenizer::ParagraphSplit::delimiters(&["\n---\n"]).unwrap()),
+        [
+            "rust compilers check ownership rules python interpreters scripts",
+            "haskell types"
+        ]
+    );
+}
+
+#[test]
+fn test_stopword_set_from_reader() {
+    let stop_words = "# English\nthe\n\n  and  \nof # articles\n".as_bytes();
+    let stop_word_set =
+        tokenizer::StopwordSet::from_reader(stop_words, tokenizer::StopwordFormat::Lines).unwrap();
+
+    assert_eq!(
+        stop_word_set,
+        ["the", "and", "of"].map(String::from).into_iter().collect()
+    );
+
+    #[cfg(feature = "serde_json")]
+    assert_eq!(
+        tokenizer::StopwordSet::from_reader(
+            r#"["the", "and", "of"]"#.as_bytes(),
+            tokenizer::StopwordFormat::Json
+        )
+        .unwrap(),
+        stop_word_set
+    );
+}
+
+#[cfg(feature = "stop-words")]
+#[test]
+fn test_tokenize_stopword_language() {
+    let text = "Rust developers write well-documented code.";
+    let stop_words = get(LANGUAGE::English);
+
+    assert_eq!(
+        tokenizer::StopwordSet::from_language(tokenizer::StopwordLanguage::English),
+        tokenizer::StopwordSet::new(&stop_words)
+    );
+    assert_eq!(
+        tokenizer::Tokenizer::from_language(text, tokenizer::StopwordLanguage::English, None)
+            .sync_split_into_words(),
+        tokenizer::Tokenizer::new(text, &stop_words, None).sync_split_into_words()
+    );
+}
+
+#[cfg(feature = "lang-detect")]
+#[test]
+fn test_tokenize_detected_language() {
+    let text = "Le chat dort sur le canapé pendant que les enfants jouent dans le jardin.";
+    let detected = tokenizer::DetectedLanguage::detect(text).unwrap();
+
+    assert_eq!(detected.language, tokenizer::Language::Fra);
+    assert!(detected.stopwords.contains("le"));
+
+    #[cfg(not(feature = "stemming"))]
+    assert_eq!(
+        tokenizer::Tokenizer::from_detected_language(text, None).sync_split_into_words(),
+        ["chat", "dort", "canapé", "enfants", "jouent", "jardin"]
+    );
+    #[cfg(feature = "stemming")]
+    assert_eq!(
+        tokenizer::Tokenizer::from_detected_language(text, None).sync_split_into_words(),
+        ["chat", "dort", "canap", "enfant", "jouent", "jardin"]
+    );
+}
+
+#[test]
+fn test_tokenize_stopword_patterns() {
+    let tokenizer = tokenizer::Tokenizer::new(
+        "Padding 12px around commit d41d8cd98f00b204e9800998ecf8427e fixed layout.",
+        &get_stop_words(),
+        None,
+    )
+    .with_stopword_patterns(&[r"^\d+(px|em)$", "^[a-f0-9]{32}$"])
+    .unwrap();
+
+    assert_eq!(
+        tokenizer.sync_split_into_phrases(None),
+        ["padding", "commit", "fixed layout"]
+    );
+    assert!(tokenizer::Tokenizer::new("", &[], None)
+        .with_stopword_patterns(&["(px"])
+        .is_err());
+}
+
+#[test]
+fn test_tokenize_mutable_stopwords() {
+    let stop_word_set = tokenizer::StopwordSet::new(&get_stop_words());
+    let mut tokenizer = tokenizer::Tokenizer::from_stopword_set(
+        "Rust crates compile fast, said the Rust team.",
+        &stop_word_set,
+        None,
+    )
+    .with_extra_stopwords(&["rust".to_string()]);
+
+    assert_eq!(
+        tokenizer.sync_split_into_phrases(None),
+        ["crates compile fast", "team"]
+    );
+
+    tokenizer.remove_stopwords(&["rust".to_string(), "said".to_string()]);
+    tokenizer.add_stopwords(&["team".to_string()]);
+
+    assert_eq!(
+        tokenizer.sync_split_into_phrases(None),
+        ["rust crates compile fast said", "rust"]
+    );
+    assert!(stop_word_set.contains("said"));
+    assert!(!stop_word_set.contains("team"));
+}
+
+#[test]
+fn test_tokenize_min_word_length() {
+    let tokenizer = tokenizer::Tokenizer::new(
+        "Rust ps aux output. Go vet tool checks.",
+        &get_stop_words(),
+        None,
+    )
+    .with_min_word_length(3);
+
+    assert_eq!(
+        tokenizer.sync_split_into_words(),
+        ["rust", "aux", "output", "vet", "tool", "checks"]
+    );
+    assert_eq!(
+        tokenizer.sync_split_into_phrases(None),
+        ["rust", "aux output", "vet tool checks"]
+    );
+}
+
+#[test]
+fn test_tokenize_number_policy() {
+    let text = "Revenue grew 12.5 percent in 2023. Invoice 4521 shipped.";
+    let tokenize = |number_policy| {
+        tokenizer::Tokenizer::new(text, &get_stop_words(), None).with_number_policy(number_policy)
+    };
+
+    assert_eq!(
+        tokenize(tokenizer::NumberPolicy::Keep).sync_split_into_words(),
+        ["revenue", "grew", "12.5", "percent", "2023", "invoice", "4521", "shipped"]
+    );
+    assert_eq!(
+        tokenize(tokenizer::NumberPolicy::Drop).sync_split_into_phrases(None),
+        ["revenue grew", "percent", "invoice", "shipped"]
+    );
+    assert_eq!(
+        tokenize(tokenizer::NumberPolicy::Placeholder).sync_split_into_phrases(None),
+        [
+            "revenue grew <number> percent",
+            "<number> invoice <number> shipped"
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_emoji_policy() {
+    let text = "Solid product 👍 fast delivery :) but slow support 🦀";
+    let tokenize = |emoji_policy| {
+        tokenizer::Tokenizer::new(text, &get_stop_words(), None).with_emoji_policy(emoji_policy)
+    };
+
+    assert_eq!(
+        tokenize(tokenizer::EmojiPolicy::Keep).sync_split_into_phrases(None),
+        ["solid product 👍 fast delivery", "slow support 🦀"]
+    );
+    assert_eq!(
+        tokenize(tokenizer::EmojiPolicy::Strip).sync_split_into_phrases(None),
+        ["solid product", "fast delivery", "slow support"]
+    );
+    assert_eq!(
+        tokenize(tokenizer::EmojiPolicy::Name).sync_split_into_words(),
+        [
+            "solid",
+            "product",
+            "thumbs_up",
+            "fast",
+            "delivery",
+            "slightly_smiling_face",
+            "slow",
+            "support",
+            "<emoji>"
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_hyphen_policy() {
+    let text = "Strong end-to-end encryption protects peer-to-peer networks.";
+    let tokenize = |hyphen_policy| {
+        tokenizer::Tokenizer::new(text, &get_stop_words(), None).with_hyphen_policy(hyphen_policy)
+    };
+
+    assert_eq!(
+        tokenize(tokenizer::HyphenPolicy::Split).sync_split_into_phrases(None),
+        ["strong", "encryption protects peer", "peer networks"]
+    );
+    assert_eq!(
+        tokenize(tokenizer::HyphenPolicy::Join).sync_split_into_phrases(None),
+        ["strong end-to-end encryption protects peer-to-peer networks"]
+    );
+    assert_eq!(
+        tokenize(tokenizer::HyphenPolicy::Both).sync_split_into_words(),
+        [
+            "strong",
+            "end-to-end",
+            "encryption",
+            "protects",
+            "peer-to-peer",
+            "peer",
+            "peer",
+            "networks"
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_contraction_expansion() {
+    let text = "Compilers can't guess. Rust’s borrow checker won't yield, y'all.";
+
+    let tokenizer = tokenizer::Tokenizer::new(text, &get_stop_words(), None);
+    assert_eq!(
+        tokenizer.sync_split_into_words(),
+        [
+            "compilers",
+            "guess",
+            "rust",
+            "borrow",
+            "checker",
+            "yield",
+            "y'all"
+        ]
+    );
+
+    let tokenizer = tokenizer::Tokenizer::new(text, &get_stop_words(), None)
+        .with_contraction_expansion(&[("y'all", "you all")]);
+    assert_eq!(
+        tokenizer.sync_split_into_words(),
+        ["compilers", "guess", "rust", "borrow", "checker", "yield"]
+    );
+}
+
+#[test]
+fn test_tokenize_entity_policies() {
+    let text =
+        "Read https://www.Rust-lang.org/learn/ (or mail Team@Rust-lang.org) #RustLang @ferris!";
+    let tokenize = |policy| {
+        tokenizer::Tokenizer::new(text, &get_stop_words(), None)
+            .with_entity_policy(tokenizer::EntityKind::Url, policy)
+            .with_entity_policy(tokenizer::EntityKind::Email, policy)
+            .with_entity_policy(tokenizer::EntityKind::Hashtag, policy)
+            .with_entity_policy(tokenizer::EntityKind::Mention, policy)
+    };
+
+    assert_eq!(
+        tokenize(tokenizer::EntityPolicy::Split).sync_split_into_words(),
+        [
+            "read", "https", "www.rust", "lang.org", "learn", "mail", "team", "rust", "lang.org",
+            "rustlang", "ferris"
+        ]
+    );
+    assert_eq!(
+        tokenize(tokenizer::EntityPolicy::Drop).sync_split_into_words(),
+        ["read", "mail"]
+    );
+    assert_eq!(
+        tokenize(tokenizer::EntityPolicy::Verbatim).sync_split_into_words(),
+        [
+            "read",
+            "https://www.Rust-lang.org/learn/",
+            "mail",
+            "Team@Rust-lang.org",
+            "#RustLang",
+            "@ferris"
+        ]
+    );
+    assert_eq!(
+        tokenize(tokenizer::EntityPolicy::Normalized).sync_split_into_phrases(None),
+        [
+            "read rust-lang.org/learn",
+            "mail team@rust-lang.org rustlang ferris"
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_offsets() {
+    let text = "Café owners love espresso machines. The machines break.";
+    let tokenizer = tokenizer::Tokenizer::new(text, &get_stop_words(), None);
+
+    let words = tokenizer.split_into_words_with_offsets();
+    assert_eq!(words[0], ("café".to_string(), (0, 5)));
+    words
+        .iter()
+        .for_each(|(word, (start, end))| assert_eq!(text[*start..*end].to_lowercase(), *word));
+
+    assert_eq!(
+        tokenizer.split_into_phrases_with_offsets(None),
+        [
+            ("café owners love espresso machines".to_string(), (0, 35)),
+            ("machines break".to_string(), (41, 55))
+        ]
+    );
+    assert_eq!(
+        tokenizer.split_into_phrases_with_offsets(Some(2))[1],
+        ("love espresso".to_string(), (13, 26))
+    );
+}
+
+#[test]
+fn test_tokenize_sentence_indexes() {
+    let tokenizer = tokenizer::Tokenizer::new(
+        "Espresso machines hum. The grinder spins! Baristas pull shots",
+        &get_stop_words(),
+        None,
+    );
+
+    assert_eq!(
+        tokenizer.split_into_words_with_sentences(),
+        [
+            ("espresso".to_string(), 0),
+            ("machines".to_string(), 0),
+            ("hum".to_string(), 0),
+            ("grinder".to_string(), 1),
+            ("spins".to_string(), 1),
+            ("baristas".to_string(), 2),
+            ("pull".to_string(), 2),
+            ("shots".to_string(), 2)
+        ]
+    );
+    assert_eq!(
+        tokenizer
+            .split_into_phrases_with_sentences(None)
+            .into_iter()
+            .map(|(_, sentence)| sentence)
+            .collect::<Vec<usize>>(),
+        [0, 1]
+    );
+    assert_eq!(tokenizer.sync_split_into_sentences().len(), 3);
+}
+
+#[test]
+fn test_tokenize_from_reader() {
+    let text = "Ferris the crab loves café culture. Espresso machines hum all day long! \
+        Baristas pull shots quickly."
+        .repeat(20);
+    let tokenizer = tokenizer::Tokenizer::new(&text, &get_stop_words(), None);
+
+    let chunks = tokenizer::Tokenizer::from_reader(text.as_bytes(), &get_stop_words(), None)
+        .with_buffer_size(50)
+        .collect::<std::io::Result<Vec<tokenizer::Tokenizer>>>()
+        .unwrap();
+    assert!(chunks.len() > 20);
+    assert_eq!(
+        chunks
+            .iter()
+            .flat_map(|chunk| chunk.sync_split_into_words())
+            .collect::<Vec<String>>(),
+        tokenizer.sync_split_into_words()
+    );
+
+    let invalid = [b"Ferris the crab ".as_slice(), &[0xff, 0xfe]].concat();
+    assert!(
+        tokenizer::Tokenizer::from_reader(invalid.as_slice(), &get_stop_words(), None)
+            .any(|chunk| chunk.is_err())
+    );
+}
+
+#[test]
+fn test_tokenizer_builder() {
+    let texts = [
+        "The 3 fastest state-of-the-art crabs won #RustConf races.",
+        "Slow crabs lost 2 races, the judges said.",
+    ];
+    let builder = tokenizer::Tokenizer::builder()
+        .with_stopwords(&get_stop_words())
+        .with_min_word_length(4)
+        .with_number_policy(tokenizer::NumberPolicy::Drop)
+        .with_hyphen_policy(tokenizer::HyphenPolicy::Join)
+        .with_entity_policy(
+            tokenizer::EntityKind::Hashtag,
+            tokenizer::EntityPolicy::Normalized,
+        );
+
+    texts.iter().for_each(|text| {
+        let tokenizer = tokenizer::Tokenizer::new(text, &get_stop_words(), None)
+            .with_min_word_length(4)
+            .with_number_policy(tokenizer::NumberPolicy::Drop)
+            .with_hyphen_policy(tokenizer::HyphenPolicy::Join)
+            .with_entity_policy(
+                tokenizer::EntityKind::Hashtag,
+                tokenizer::EntityPolicy::Normalized,
+            );
+
+        assert_eq!(
+            builder.build(text).sync_split_into_phrases(None),
+            tokenizer.sync_split_into_phrases(None)
+        );
+    });
+    assert_eq!(
+        builder.build(texts[0]).sync_split_into_words(),
+        ["fastest", "state-of-the-art", "crabs", "rustconf", "races"]
+    );
+    assert_eq!(
+        tokenizer::Tokenizer::builder()
+            .build(texts[1])
+            .sync_split_into_words(),
+        ["slow", "crabs", "lost", "2", "races", "the", "judges", "said"]
+    );
+}
+
+#[test]
+fn test_stopword_set() {
+    let stopwords = tokenizer::StopwordSet::new(&get_stop_words());
+    let texts = [
+        "The crab is in the sea.",
+        "A crab and a lobster walk on the beach.",
+    ];
+
+    assert!(stopwords.contains("the"));
+    texts.iter().for_each(|text| {
+        assert_eq!(
+            tokenizer::Tokenizer::from_stopword_set(text, &stopwords, None)
+                .sync_split_into_phrases(None),
+            tokenizer::Tokenizer::new(text, &get_stop_words(), None).sync_split_into_phrases(None)
+        );
+    });
+    assert_eq!(
+        tokenizer::Tokenizer::builder()
+            .with_stopword_set(&stopwords)
+            .build(texts[1])
+            .sync_split_into_words(),
+        ["crab", "lobster", "walk", "beach"]
+    );
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_min_word_length() {
+    let documents = [
+        "Rust ps aux output.".to_string(),
+        "Go vet tool checks.".to_string(),
+    ];
+    let tf_idf = tf_idf::TfIdf::with_options(
+        tf_idf::TfIdfParams::UnprocessedDocuments(&documents, &get_stop_words(), None),
+        tf_idf::TfIdfOptions {
+            min_word_length: 3,
+            ngram_range: (1, 2),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(tf_idf.document_frequency("ps"), 0);
+    assert_eq!(tf_idf.document_frequency("rust ps"), 0);
+    assert_eq!(tf_idf.document_frequency("aux output"), 1);
+}
+
+#[test]
+fn test_tokenize_ngrams() {
+    let tokenizer = tokenizer::Tokenizer::new(
+        "Rust borrow checker rules. Memory safety without garbage collection, in practice.",
+        &get_stop_words(),
+        None,
+    );
+
+    assert_eq!(
+        tokenizer.sync_split_into_ngrams((1, 2)),
+        [
+            "rust",
+            "borrow",
+            "checker",
+            "rules",
+            "rust borrow",
+            "borrow checker",
+            "checker rules",
+            "memory",
+            "safety",
+            "garbage",
+            "collection",
+            "practice",
+            "memory safety",
+            "garbage collection",
+        ]
+    );
+    assert_eq!(
+        tokenizer.sync_split_into_ngrams((3, 5)),
+        [
+            "rust borrow checker",
+            "borrow checker rules",
+            "rust borrow checker rules"
+        ]
+    );
+    assert_eq!(
+        tokenizer.split_into_ngrams((2, 2)).len(),
+        tokenizer.sync_split_into_ngrams((2, 2)).len()
+    );
+}
+
+#[test]
+fn test_tokenize_surface_forms() {
+    let text =
+        "NASA launched Artemis. Engineers at NASA tested Artemis rockets. The nasa budget grew.";
+    let surface_forms =
+        tokenizer::Tokenizer::new(text, &get_stop_words(), None).get_surface_forms();
+
+    assert_eq!(
+        surface_forms.get_surface_form("nasa artemis"),
+        "NASA Artemis"
+    );
+    assert_eq!(surface_forms.get_surface_form("engineers"), "Engineers");
+    assert_eq!(surface_forms.get_surface_form("unseen"), "unseen");
+    assert_eq!(
+        surface_forms.apply(vec![("artemis rockets".to_string(), 1.0)]),
+        [("Artemis rockets".to_string(), 1.0)]
+    );
+}
+
+#[cfg(all(feature = "rake", feature = "yake", feature = "text_rank"))]
+#[test]
+fn test_ranked_surface_forms() {
+    let text =
+        "NASA launched Artemis. Engineers at NASA tested Artemis rockets. The nasa budget grew.";
+    let stop_words = get_stop_words();
+
+    let rake = rake::Rake::new(rake::RakeParams::WithDefaults(text, &stop_words));
+    assert!(rake
+        .get_ranked_surface_phrase_scores(10)
+        .iter()
+        .any(|(phrase, _)| phrase == "NASA budget grew"));
+
+    let text_rank =
+        text_rank::TextRank::new(text_rank::TextRankParams::WithDefaults(text, &stop_words));
+    assert!(text_rank
+        .get_ranked_surface_phrase_scores(10)
+        .iter()
+        .all(|(phrase, _)| !phrase.contains("nasa")));
+
+    let yake = yake::Yake::new(yake::YakeParams::WithDefaults(text, &stop_words));
+    assert!(yake
+        .get_ranked_surface_keyword_scores(10)
+        .iter()
+        .any(|(keyword, _)| keyword.contains("NASA")));
+}
+
+#[test]
+fn test_tokenize_word_normalizer() {
+    let lemmas = [("mice", "mouse"), ("chased", "chase"), ("geese", "goose")]
+        .iter()
+        .map(|(word, lemma)| (word.to_string(), lemma.to_string()))
+        .collect::<std::collections::HashMap<String, String>>();
+    let tokenizer = tokenizer::Tokenizer::new("Mice chased the geese.", &get_stop_words(), None)
+        .with_word_normalizer(move |word| lemmas.get(word).cloned().unwrap_or(word.to_string()));
+
+    assert_eq!(
+        tokenizer.sync_split_into_words(),
+        ["mouse", "chase", "goose"]
+    );
+    assert_eq!(
+        tokenizer.sync_split_into_phrases(None),
+        ["mouse chase", "goose"]
+    );
+}
+
+#[cfg(feature = "stemming")]
+#[test]
+fn test_tokenize_stemming() {
+    let tokenizer = tokenizer::Tokenizer::new(
+        "The runner kept running. She runs, connected and connecting networks.",
+        &get_stop_words(),
+        None,
+    )
+    .with_stemming(tokenizer::StemmingLanguage::English);
+
+    assert_eq!(
+        tokenizer.sync_split_into_words(),
+        ["runner", "run", "run", "connect", "connect", "network"]
+    );
+    assert_eq!(
+        tokenizer.sync_split_into_phrases(None),
+        ["runner", "run", "run connect", "connect network"]
+    );
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn test_tokenize_unicode_normalization() {
+    let text = "Caf\u{e9} and cafe\u{301}. \u{ff32}\u{ff35}\u{ff33}\u{ff34} and Rust.";
+
+    let tokenizer = tokenizer::Tokenizer::new(text, &get_stop_words(), None)
+        .with_unicode_normalization(tokenizer::UnicodeNormalization::Nfc);
+    assert_eq!(
+        tokenizer.sync_split_into_words(),
+        [
+            "caf\u{e9}",
+            "caf\u{e9}",
+            "\u{ff52}\u{ff55}\u{ff53}\u{ff54}",
+            "rust"
+        ]
+    );
+
+    let tokenizer = tokenizer::Tokenizer::new(text, &get_stop_words(), None)
+        .with_unicode_normalization(tokenizer::UnicodeNormalization::Nfkc);
+    assert_eq!(
+        tokenizer.sync_split_into_words(),
+        ["caf\u{e9}", "caf\u{e9}", "rust", "rust"]
+    );
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn test_tokenize_diacritic_folding() {
+    let tokenizer = tokenizer::Tokenizer::new(
+        "Café culture. Cafe owners and naïve cafe\u{301} critics.",
+        &get_stop_words(),
+        None,
+    )
+    .with_diacritic_folding();
+
+    assert_eq!(
+        tokenizer.sync_split_into_words(),
+        ["cafe", "culture", "cafe", "owners", "naive", "cafe", "critics"]
+    );
+
+    // the stopwords are checked once the words are folded, and are folded too
+    let stop_words = ["a".to_string(), "déjà".to_string()];
+    let tokenizer = tokenizer::Tokenizer::new("Voilà à la carte, deja vu.", &stop_words, None)
+        .with_diacritic_folding();
+    assert_eq!(
+        tokenizer.sync_split_into_words(),
+        ["voila", "la", "carte", "vu"]
+    );
+
+    // a shared set is folded for the builder once, without changing the original
+    let stop_word_set = tokenizer::StopwordSet::new(&stop_words);
+    let builder = tokenizer::Tokenizer::builder()
+        .with_stopword_set(&stop_word_set)
+        .with_diacritic_folding();
+    assert_eq!(
+        builder.build("Déjà vu, deja lu.").sync_split_into_words(),
+        ["vu", "lu"]
+    );
+    assert!(stop_word_set.contains("déjà"));
+}
+
+#[cfg(all(feature = "stemming", feature = "tf_idf"))]
+#[test]
+fn test_tf_idf_stemming() {
+    let documents = [
+        "Connected networks.".to_string(),
+        "A connection between networks.".to_string(),
+    ];
+    let tf_idf = tf_idf::TfIdf::with_options(
+        tf_idf::TfIdfParams::UnprocessedDocuments(&documents, &get_stop_words(), None),
+        tf_idf::TfIdfOptions {
+            stemming: Some(tokenizer::StemmingLanguage::English),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(tf_idf.vocabulary_size(), 2);
+    assert_eq!(tf_idf.document_frequency("connect"), 2);
+    assert_eq!(tf_idf.document_frequency("network"), 2);
+}
+
+#[cfg(all(feature = "stemming", feature = "tf_idf"))]
+#[test]
+fn test_tf_idf_stream_stemming() {
+    let stop_words = get_stop_words();
+    let corpus = "Connected networks are running.
+A connection between networks.
+Connecting connections.
+";
+    let options = tf_idf::TfIdfOptions {
+        stemming: Some(tokenizer::StemmingLanguage::English),
+        ..Default::default()
+    };
+    let documents = corpus
+        .lines()
+        .map(|line| line.to_string())
+        .collect::<Vec<String>>();
+    let fitted = tf_idf::TfIdf::with_options(
+        tf_idf::TfIdfParams::UnprocessedDocuments(&documents, &stop_words, None),
+        options,
+    );
+    let streamed =
+        tf_idf::TfIdf::from_reader(corpus.as_bytes(), &stop_words, None, options).unwrap();
+
+    let mut vocabulary = streamed
+        .get_vocabulary()
+        .keys()
+        .cloned()
+        .collect::<Vec<String>>();
+    vocabulary.sort();
+    assert_eq!(vocabulary, ["connect", "network", "run"]);
+    assert_eq!(streamed.get_vocabulary(), fitted.get_vocabulary());
+}
+
+#[cfg(all(
+    feature = "text_rank",
+    feature = "rake",
+    feature = "yake",
+    feature = "tf_idf"
+))]
+#[test]
+fn test_custom_tokenizer() {
+    /// A tokenizer of texts whose words are separated by `|`, like segmented text without spaces.
+    struct PipeTokenizer(String);
+
+    impl tokenizer::Tokenize for PipeTokenizer {
+        fn words(&self) -> Vec<String> {
+            self.sentences()
+                .iter()
+                .flat_map(|sentence| sentence.split_whitespace().map(|word| word.to_string()))
+                .collect()
+        }
+
+        fn sentences(&self) -> Vec<String> {
+            self.0
+                .split('.')
+                .map(|sentence| {
+                    sentence
+                        .split('|')
+                        .map(|word| word.trim().to_lowercase())
+                        .filter(|word| !word.is_empty())
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                })
+                .filter(|sentence| !sentence.is_empty())
+                .collect()
+        }
+
+        fn phrases(&self, _: Option<usize>) -> Vec<String> {
+            self.sentences()
+        }
+    }
+
+    let text = "Cargo|builds|Rust|crates. Rust|crates|compile|fast. Cargo|fetches|crates.";
+    let tokenizer = PipeTokenizer(text.to_string());
+
+    let text_rank = text_rank::TextRank::with_tokenizer(
+        text_rank::TextRankParams::WithDefaults(text, &[]),
+        &tokenizer,
+    );
+    assert_eq!(text_rank.get_ranked_words(1), ["crates"]);
+    assert!(text_rank.get_phrase_score("cargo builds rust crates") > 0.0);
+
+    let rake = rake::Rake::with_tokenizer(rake::RakeParams::WithDefaults(text, &[]), &tokenizer);
+    assert!(rake.get_phrase_score("rust crates compile fast") > 0.0);
+    assert!(rake.get_keyword_score("fetches") > 0.0);
+
+    let yake = yake::Yake::with_tokenizer(yake::YakeParams::WithDefaults(text, &[]), &tokenizer);
+    assert!(yake.get_keyword_score("crates") > 0.0);
+
+    let tf_idf = tf_idf::TfIdf::with_tokenizers(
+        &[
+            PipeTokenizer("Rust|crates.".to_string()),
+            PipeTokenizer("Python|packages.".to_string()),
+        ],
+        tf_idf::TfIdfOptions::default(),
+    );
+    assert_eq!(tf_idf.vocabulary_size(), 4);
+    assert!(tf_idf.get_idf("python").is_some());
+
+    let stop_words = get_stop_words();
+    let params = text_rank::TextRankParams::WithDefaults(TEXT, &stop_words);
+    assert_eq!(
+        text_rank::TextRank::new(params).get_ranked_words(5),
+        text_rank::TextRank::with_tokenizer(
+            text_rank::TextRankParams::WithDefaults(TEXT, &stop_words),
+            &tokenizer::Tokenizer::new(TEXT, &stop_words, None)
+        )
+        .get_ranked_words(5)
+    );
+}
+
 #[test]
 fn test_tf_idf() {
     let tf_idf = tf_idf::TfIdf::new(tf_idf::TfIdfParams::TextBlock(
         TEXT,
         &get_stop_words(),
         None,
-        tf_idf::TextSplit::Paragraphs,
+        tf_idf::TextSplit::Paragraphs,
+    ));
+    let words_result = tf_idf.get_ranked_words(100);
+    let expected_words = vec![
+        "rust",
+        "development",
+        "environment",
+        "work",
+        "programming",
+        "team",
+        "career",
+        "code",
+        "developer",
+        "dynamic",
+        "experience",
+        "familiarity",
+        "junior",
+        "learn",
+        "skills",
+        "strong",
+        "supportive",
+        "systems",
+        "to",
+        "well",
+        "ability",
+        "actively",
+        "actix",
+        "advancement",
+        "applications",
+        "apply",
+        "assist",
+        "bachelor",
+        "benefits",
+        "candidate",
+        "chance",
+        "clean",
+        "collaborate",
+        "collaborative",
+        "communication",
+        "competitive",
+        "computer",
+        "constructive",
+        "continuously",
+        "control",
+        "core",
+        "cutting",
+        "database",
+        "date",
+        "debugging",
+        "degree",
+        "description",
+        "design",
+        "desire",
+        "documented",
+        "ecosystem",
+        "edge",
+        "efficient",
+        "encourage",
+        "equivalent",
+        "excellent",
+        "features",
+        "feedback",
+        "field",
+        "foundation",
+        "frameworks",
+        "git",
+        "grow",
+        "growing",
+        "growth",
+        "haves",
+        "ideal",
+        "implement",
+        "javascript",
+        "job",
+        "join",
+        "kickstart",
+        "knowledge",
+        "language",
+        "languages",
+        "latest",
+        "like",
+        "looking",
+        "maintenance",
+        "mongodb",
+        "motivated",
+        "new",
+        "nice",
+        "offer",
+        "opportunity",
+        "oriented",
+        "package",
+        "participate",
+        "passion",
+        "passionate",
+        "postgresql",
+        "preferably",
+        "problem",
+        "proficiency",
+        "projects",
+        "provide",
+        "python",
+        "related",
+        "requirements",
+        "responsibilities",
+    ]
+    .iter()
+    .map(|x| x.to_string())
+    .collect::<HashSet<String>>();
+    assert!(is_percent_in_hashset(&words_result, &expected_words, 85.0));
+
+    let document_scores = tf_idf.score_document("A Rust developer writes Rust, not COBOL.");
+    assert!(document_scores["rust"] > document_scores["developer"]);
+    assert!(!document_scores.contains_key("cobol"));
+}
+
+#[cfg(all(feature = "tf_idf", feature = "serde"))]
+#[test]
+fn test_tf_idf_serde() {
+    let tf_idf = tf_idf::TfIdf::new(tf_idf::TfIdfParams::TextBlock(
+        TEXT,
+        &get_stop_words(),
+        None,
+        tf_idf::TextSplit::Paragraphs,
+    ));
+    let serialized = serde_json::to_string(&tf_idf).unwrap();
+    let loaded = serde_json::from_str::<tf_idf::TfIdf>(&serialized).unwrap();
+    assert_eq!(loaded.get_word_scores_map(), tf_idf.get_word_scores_map());
+    assert_eq!(loaded.get_idf_map(), tf_idf.get_idf_map());
+    assert_eq!(
+        loaded.score_document("A Rust developer with Python skills."),
+        tf_idf.score_document("A Rust developer with Python skills.")
+    );
+    // the analyzed documents are not part of the saved model
+    assert!(!serialized.contains("document_word_counts"));
+    assert!(loaded.get_document_vectors().is_empty());
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_ngrams() {
+    let documents = [
+        "The Rust developer writes Rust code.".to_string(),
+        "A senior Rust developer reviews the code.".to_string(),
+    ];
+    let tf_idf = tf_idf::TfIdf::with_options(
+        tf_idf::TfIdfParams::UnprocessedDocuments(&documents, &get_stop_words(), None),
+        tf_idf::TfIdfOptions {
+            ngram_range: (1, 2),
+            ..Default::default()
+        },
+    );
+    assert!(tf_idf.get_score("rust developer") > 0.0);
+    assert!(tf_idf.get_score("rust") > 0.0);
+    // stop words break the n-grams
+    assert_eq!(tf_idf.get_score("reviews code"), 0.0);
+    assert!(tf_idf
+        .score_document("Hiring a Rust developer")
+        .contains_key("rust developer"));
+
+    let bigrams = tf_idf::TfIdf::with_options(
+        tf_idf::TfIdfParams::UnprocessedDocuments(&documents, &get_stop_words(), None),
+        tf_idf::TfIdfOptions {
+            ngram_range: (2, 2),
+            ..Default::default()
+        },
+    );
+    assert_eq!(bigrams.get_score("rust"), 0.0);
+    assert!(bigrams.get_score("senior rust") > 0.0);
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_phrases() {
+    let stop_words = get_stop_words();
+    let options = tf_idf::TfIdfOptions {
+        term_unit: tf_idf::TermUnit::Phrases,
+        ..Default::default()
+    };
+    let documents = [
+        "The junior Rust developer and the systems code.".to_string(),
+        "A junior Rust developer and a senior Python developer.".to_string(),
+    ];
+    let tf_idf = tf_idf::TfIdf::with_options(
+        tf_idf::TfIdfParams::UnprocessedDocuments(&documents, &stop_words, None),
+        options,
+    );
+    assert!(tf_idf.get_idf("junior rust developer").is_some());
+    assert!(tf_idf.get_idf("rust").is_none());
+    assert!(tf_idf.get_idf("junior rust developer") < tf_idf.get_idf("senior python developer"));
+    assert!(tf_idf
+        .score_document("Hiring a junior Rust developer")
+        .contains_key("junior rust developer"));
+
+    let text_block = tf_idf::TfIdf::with_options(
+        tf_idf::TfIdfParams::TextBlock(TEXT, &stop_words, None, tf_idf::TextSplit::Sentences),
+        options,
+    );
+    assert!(text_block
+        .get_ranked_words(10)
+        .iter()
+        .any(|phrase| phrase.contains(' ')));
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_tokenized_documents() {
+    let documents = vec![
+        vec!["C++", "templates", "C++"],
+        vec!["Rust", "traits"],
+        vec!["Rust", "macros", "C++"],
+    ];
+    let tf_idf = tf_idf::TfIdf::new(tf_idf::TfIdfParams::TokenizedDocuments(&documents));
+    // the tokens are kept as they are
+    assert!(tf_idf.get_idf("C++").is_some());
+    assert!(tf_idf.get_idf("rust").is_none());
+    assert_eq!(tf_idf.get_vocabulary().len(), 5);
+    assert_eq!(tf_idf.top_keywords_for(0, 1)[0].0, "C++");
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_weighting() {
+    let documents = [
+        "rust rust rust cargo".to_string(),
+        "rust python".to_string(),
+        "python pip".to_string(),
+    ];
+    let fit = |tf_weighting, idf_weighting| {
+        tf_idf::TfIdf::with_options(
+            tf_idf::TfIdfParams::ProcessedDocuments(&documents),
+            tf_idf::TfIdfOptions {
+                tf_weighting,
+                idf_weighting,
+                ..Default::default()
+            },
+        )
+    };
+
+    let raw = fit(tf_idf::TfWeighting::Raw, tf_idf::IdfWeighting::Smooth);
+    let boolean = fit(tf_idf::TfWeighting::Boolean, tf_idf::IdfWeighting::Smooth);
+    let log = fit(tf_idf::TfWeighting::Log, tf_idf::IdfWeighting::Smooth);
+    assert_eq!(raw.get_ranked_words(1), ["rust"]);
+    assert!(boolean.get_score("rust") < boolean.get_score("cargo"));
+    assert!(
+        log.get_score("rust") / log.get_score("cargo")
+            < raw.get_score("rust") / raw.get_score("cargo")
+    );
+
+    let standard = fit(tf_idf::TfWeighting::Raw, tf_idf::IdfWeighting::Standard);
+    assert!((standard.get_idf("cargo").unwrap() - 3.0_f32.ln()).abs() < 1e-6);
+    let probabilistic = fit(
+        tf_idf::TfWeighting::Raw,
+        tf_idf::IdfWeighting::Probabilistic,
+    );
+    assert_eq!(probabilistic.get_idf("rust"), Some(0.0));
+    assert!((probabilistic.get_idf("pip").unwrap() - 2.0_f32.ln()).abs() < 1e-6);
+
+    let sublinear = tf_idf::TfIdf::with_options(
+        tf_idf::TfIdfParams::ProcessedDocuments(&documents),
+        tf_idf::TfIdfOptions {
+            sublinear_tf: true,
+            l2_normalization: false,
+            ..Default::default()
+        },
+    );
+    let scores = sublinear.top_keywords_for(0, 2);
+    let idf = sublinear.get_idf("rust").unwrap();
+    assert_eq!(scores[0].0, "rust");
+    assert!((scores[0].1 - (1.0 + 3.0_f32.ln()) * idf).abs() < 1e-6);
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_zero_vectors() {
+    let standard = |documents| {
+        tf_idf::TfIdf::with_options(
+            tf_idf::TfIdfParams::ProcessedDocuments(documents),
+            tf_idf::TfIdfOptions {
+                idf_weighting: tf_idf::IdfWeighting::Standard,
+                ..Default::default()
+            },
+        )
+    };
+
+    let document = ["rust cargo crates".to_string()];
+    let single = standard(&document);
+    let ranked = single.get_ranked_word_scores(3);
+    assert_eq!(ranked.len(), 3);
+    assert!(ranked.iter().all(|(_, score)| *score == 0.0));
+    assert!(single
+        .top_keywords_for(0, 3)
+        .iter()
+        .all(|(_, score)| *score == 0.0));
+
+    let documents = [
+        "rust compiler speed".to_string(),
+        "rust compiler safety".to_string(),
+    ];
+    let scores = standard(&documents).score_document("rust compiler");
+    assert_eq!(scores.len(), 2);
+    assert!(scores.values().all(|score| *score == 0.0));
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_document_frequency_limits() {
+    let documents = [
+        "rust cargo crates".to_string(),
+        "rust python".to_string(),
+        "rust python pip".to_string(),
+        "rust go".to_string(),
+    ];
+    let mut tf_idf = tf_idf::TfIdf::with_options(
+        tf_idf::TfIdfParams::ProcessedDocuments(&documents),
+        tf_idf::TfIdfOptions {
+            min_df: tf_idf::DocumentFrequencyLimit::Count(2),
+            max_df: tf_idf::DocumentFrequencyLimit::Ratio(0.9),
+            ..Default::default()
+        },
+    );
+    assert_eq!(tf_idf.get_ranked_words(10), ["python"]);
+    assert!(tf_idf.get_idf("rust").is_none());
+    assert!(tf_idf.get_idf("cargo").is_none());
+    assert_eq!(tf_idf.document_frequency("rust"), 4);
+    assert_eq!(tf_idf.document_frequency("java"), 0);
+    assert_eq!(
+        tf_idf.corpus_stats(),
+        tf_idf::CorpusStats {
+            total_documents: 4,
+            vocabulary_size: 1,
+            distinct_terms: 6,
+            total_terms: 10,
+            average_document_length: 2.5,
+        }
+    );
+
+    // "rust" is now in 4 of the 5 documents, below the ratio
+    tf_idf.add_documents(&["cargo builds crates"]);
+    assert_eq!(tf_idf.get_idf_map().len(), 4);
+    assert!(tf_idf.get_score("cargo") > 0.0);
+    assert!(tf_idf.get_idf("rust").is_some());
+    assert_eq!(tf_idf.total_documents(), 5);
+    assert_eq!(tf_idf.vocabulary_size(), 4);
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_document_vectors() {
+    let documents = [
+        "rust cargo rust".to_string(),
+        "rust python".to_string(),
+        "python pip".to_string(),
+    ];
+    let tf_idf = tf_idf::TfIdf::new(tf_idf::TfIdfParams::ProcessedDocuments(&documents));
+    let vocabulary = tf_idf.get_vocabulary();
+    assert_eq!(vocabulary.len(), 4);
+    assert_eq!(vocabulary["cargo"], 0);
+
+    let vectors = tf_idf.get_document_vectors();
+    assert_eq!(vectors.len(), 3);
+    assert_eq!(
+        vectors[0].iter().map(|(id, _)| *id).collect::<Vec<usize>>(),
+        [vocabulary["cargo"], vocabulary["rust"]]
+    );
+    vectors.iter().for_each(|vector| {
+        let norm = vector
+            .iter()
+            .map(|(_, weight)| weight * weight)
+            .sum::<f32>();
+        assert!((norm - 1.0).abs() < 1e-5);
+    });
+    assert_eq!(tf_idf.get_document_vector(1), Some(&vectors[1]));
+    assert!(tf_idf.get_document_vector(3).is_none());
+
+    let (indptr, indices, data) = tf_idf.to_csr();
+    assert_eq!(indptr, [0, 2, 4, 6]);
+    assert_eq!(indices[..2], [vocabulary["cargo"], vocabulary["rust"]]);
+    assert_eq!(
+        data[2..4],
+        vectors[1]
+            .iter()
+            .map(|(_, weight)| *weight)
+            .collect::<Vec<f32>>()
+    );
+
+    assert!((tf_idf.cosine_similarity(0, 0) - 1.0).abs() < 1e-5);
+    assert!(tf_idf.cosine_similarity(1, 0) > 0.0);
+    assert_eq!(tf_idf.cosine_similarity(0, 2), 0.0);
+    assert_eq!(tf_idf.cosine_similarity(0, 3), 0.0);
+    let most_similar = tf_idf.most_similar(1, 2);
+    assert_eq!(most_similar.len(), 2);
+    assert!(most_similar[0].1 >= most_similar[1].1);
+    assert_eq!(
+        tf_idf
+            .most_similar(0, 5)
+            .iter()
+            .map(|(document, _)| *document)
+            .collect::<Vec<usize>>(),
+        [1, 2]
+    );
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_fixed_vocabulary() {
+    let documents = [
+        "rust cargo rust".to_string(),
+        "rust python".to_string(),
+        "python pip".to_string(),
+    ];
+    let vocabulary = [("rust", 7), ("python", 2), ("java", 0)]
+        .into_iter()
+        .map(|(word, id)| (word.to_string(), id))
+        .collect::<std::collections::HashMap<String, usize>>();
+    let tf_idf = tf_idf::TfIdf::with_vocabulary(
+        tf_idf::TfIdfParams::ProcessedDocuments(&documents),
+        tf_idf::TfIdfOptions::default(),
+        vocabulary.clone(),
+    );
+    assert_eq!(tf_idf.get_vocabulary(), &vocabulary);
+    assert_eq!(tf_idf.get_feature_count(), 8);
+    assert!(tf_idf.get_idf("cargo").is_none());
+    assert_eq!(tf_idf.get_score("pip"), 0.0);
+    assert_eq!(tf_idf.get_document_vector(0).unwrap()[0].0, 7);
+
+    // the ids are shared with a model fitted on other documents
+    let serving = tf_idf::TfIdf::with_vocabulary(
+        tf_idf::TfIdfParams::ProcessedDocuments(&["java python".to_string()]),
+        tf_idf::TfIdfOptions::default(),
+        tf_idf.get_vocabulary().clone(),
+    );
+    assert_eq!(
+        serving
+            .get_document_vector(0)
+            .unwrap()
+            .iter()
+            .map(|(id, _)| *id)
+            .collect::<Vec<usize>>(),
+        [0, 2]
+    );
+}
+
+#[cfg(all(feature = "tf_idf", feature = "ndarray"))]
+#[test]
+fn test_tf_idf_to_array2() {
+    let documents = [
+        "rust cargo rust".to_string(),
+        "rust python".to_string(),
+        "python pip".to_string(),
+    ];
+    let tf_idf = tf_idf::TfIdf::new(tf_idf::TfIdfParams::ProcessedDocuments(&documents));
+    let matrix = tf_idf.to_array2();
+    assert_eq!(matrix.shape(), [3, 4]);
+    let vocabulary = tf_idf.get_vocabulary();
+    assert_eq!(matrix[[2, vocabulary["cargo"]]], 0.0);
+    tf_idf
+        .get_document_vector(0)
+        .unwrap()
+        .iter()
+        .for_each(|(id, weight)| assert_eq!(matrix[[0, *id]], *weight));
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_hashing() {
+    let documents = [
+        "rust cargo rust".to_string(),
+        "rust python".to_string(),
+        "python pip".to_string(),
+    ];
+    let options = tf_idf::TfIdfOptions {
+        hashing_buckets: Some(16),
+        ..Default::default()
+    };
+    let tf_idf =
+        tf_idf::TfIdf::with_options(tf_idf::TfIdfParams::ProcessedDocuments(&documents), options);
+    let exact = tf_idf::TfIdf::new(tf_idf::TfIdfParams::ProcessedDocuments(&documents));
+    assert!(tf_idf.get_vocabulary().is_empty());
+    assert!(tf_idf
+        .get_ranked_words(4)
+        .iter()
+        .all(|bucket| bucket.parse::<usize>().unwrap() < 16));
+    assert!(tf_idf.get_idf("rust").is_some());
+    assert!(tf_idf.get_idf("rust").unwrap() <= exact.get_idf("rust").unwrap());
+    assert!(tf_idf.get_score("cargo") > 0.0);
+
+    tf_idf.get_document_vectors().iter().for_each(|vector| {
+        assert!(!vector.is_empty());
+        assert!(vector.iter().all(|(bucket, _)| *bucket < 16));
+    });
+    assert_eq!(tf_idf.score_query("pip")[0].0, 2);
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_score_query() {
+    let documents = [
+        "Rust is a systems programming language.".to_string(),
+        "Python is a scripting language.".to_string(),
+        "Cargo builds Rust crates.".to_string(),
+    ];
+    let tf_idf = tf_idf::TfIdf::new(tf_idf::TfIdfParams::UnprocessedDocuments(
+        &documents,
+        &get_stop_words(),
+        None,
+    ));
+    let ranked = tf_idf.score_query("Rust crates");
+    assert_eq!(
+        ranked
+            .iter()
+            .map(|(document, _)| *document)
+            .collect::<Vec<usize>>(),
+        [2, 0]
+    );
+    assert!(ranked[0].1 > ranked[1].1);
+    assert!(tf_idf.score_query("the COBOL").is_empty());
+
+    assert_eq!(
+        tf_idf
+            .top_keywords_for(1, 2)
+            .into_iter()
+            .map(|(word, _)| word)
+            .collect::<Vec<String>>(),
+        ["python", "scripting"]
+    );
+    assert!(tf_idf.top_keywords_for(3, 2).is_empty());
+    let all_keywords = tf_idf.top_keywords_for_all(1);
+    assert_eq!(all_keywords.len(), 3);
+    assert_eq!(all_keywords[1], tf_idf.top_keywords_for(1, 1));
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_background_idf() {
+    let background = tf_idf::BackgroundIdf::from_reader(
+        "# word weight\nrust 2.5\nprogramming 0.5\n\nsystems programming 3.0\n".as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(background.get_weight("rust"), 2.5);
+    assert_eq!(background.get_weight("systems programming"), 3.0);
+    // unknown words are the rarest
+    assert_eq!(background.get_weight("cargo"), 3.0);
+    assert!(tf_idf::BackgroundIdf::from_reader("rust two".as_bytes()).is_err());
+
+    let documents = ["Rust programming, programming and more programming.".to_string()];
+    let tf_idf = tf_idf::TfIdf::with_background_idf(
+        tf_idf::TfIdfParams::UnprocessedDocuments(&documents, &get_stop_words(), None),
+        tf_idf::TfIdfOptions::default(),
+        background,
+    );
+    assert_eq!(tf_idf.get_idf("rust"), Some(2.5));
+    // the frequent but common word ranks below the rare one
+    assert_eq!(tf_idf.get_ranked_words(2), ["rust", "programming"]);
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_stream_word_options() {
+    let stop_words = get_stop_words();
+    let corpus = "State-of-the-art #RustLang tools in 2024 👍\nWe don't ship aaaaaaaaaaaaaaaaaaaa blobs.\nOld tools in 1999.\n";
+    let options = tf_idf::TfIdfOptions {
+        min_word_length: 3,
+        max_word_length: Some(16),
+        number_policy: tokenizer::NumberPolicy::Placeholder,
+        emoji_policy: tokenizer::EmojiPolicy::Name,
+        hyphen_policy: tokenizer::HyphenPolicy::Join,
+        expand_contractions: true,
+        entity_policies: tokenizer::EntityPolicies {
+            hashtag: tokenizer::EntityPolicy::Normalized,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let documents = corpus
+        .lines()
+        .map(|line| line.to_string())
+        .collect::<Vec<String>>();
+    let fitted = tf_idf::TfIdf::with_options(
+        tf_idf::TfIdfParams::UnprocessedDocuments(&documents, &stop_words, None),
+        options,
+    );
+    let streamed =
+        tf_idf::TfIdf::from_reader(corpus.as_bytes(), &stop_words, None, options).unwrap();
+
+    assert_eq!(streamed.get_vocabulary(), fitted.get_vocabulary());
+    assert_eq!(streamed.get_idf_map(), fitted.get_idf_map());
+    assert_eq!(
+        streamed.document_frequency(tokenizer::NUMBER_PLACEHOLDER),
+        2
+    );
+    assert_eq!(streamed.document_frequency("state-of-the-art"), 1);
+    assert_eq!(streamed.document_frequency("rustlang"), 1);
+    assert_eq!(streamed.document_frequency("thumbs_up"), 1);
+    assert_eq!(streamed.document_frequency("aaaaaaaaaaaaaaaaaaaa"), 0);
+}
+
+#[cfg(all(feature = "unicode-normalization", feature = "tf_idf"))]
+#[test]
+fn test_tf_idf_stream_diacritic_folding() {
+    let stop_words = get_stop_words();
+    let corpus = "Café culture.\nThe cafe owners.\n";
+    let options = tf_idf::TfIdfOptions {
+        unicode_normalization: Some(tokenizer::UnicodeNormalization::Nfc),
+        fold_diacritics: true,
+        ..Default::default()
+    };
+    let streamed =
+        tf_idf::TfIdf::from_reader(corpus.as_bytes(), &stop_words, None, options).unwrap();
+
+    assert_eq!(streamed.document_frequency("cafe"), 2);
+    assert_eq!(streamed.document_frequency("café"), 0);
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_stream() {
+    let stop_words = get_stop_words();
+    let corpus = "Rust is a systems programming language.\nPython is a scripting language.\nCargo builds Rust crates.\n";
+    let documents = corpus
+        .lines()
+        .map(|line| line.to_string())
+        .collect::<Vec<String>>();
+    let fitted = tf_idf::TfIdf::new(tf_idf::TfIdfParams::UnprocessedDocuments(
+        &documents,
+        &stop_words,
+        None,
+    ));
+
+    let streamed = tf_idf::TfIdf::from_reader(
+        corpus.as_bytes(),
+        &stop_words,
+        None,
+        tf_idf::TfIdfOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(streamed.get_idf_map(), fitted.get_idf_map());
+    fitted
+        .get_word_scores_map()
+        .iter()
+        .for_each(|(word, score)| {
+            assert!((streamed.get_score(word) - score).abs() < 1e-6);
+        });
+    assert!(streamed.get_document_vectors().is_empty());
+
+    let batched = tf_idf::TfIdf::from_reader(
+        corpus.as_bytes(),
+        &stop_words,
+        None,
+        tf_idf::TfIdfOptions {
+            batch_size: 2,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(batched.total_documents(), 3);
+    assert_eq!(batched.get_idf_map(), fitted.get_idf_map());
+
+    let mut spilled = Vec::new();
+    let written = streamed
+        .write_document_vectors(corpus.as_bytes().lines(), &mut spilled)
+        .unwrap();
+    assert_eq!(written, 3);
+    let spilled_vectors = String::from_utf8(spilled)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|pair| {
+                    let (id, weight) = pair.split_once(':').unwrap();
+                    (id.parse::<usize>().unwrap(), weight.parse::<f32>().unwrap())
+                })
+                .collect::<Vec<(usize, f32)>>()
+        })
+        .collect::<Vec<Vec<(usize, f32)>>>();
+    spilled_vectors
+        .iter()
+        .zip(fitted.get_document_vectors())
+        .for_each(|(spilled, vector)| {
+            assert_eq!(spilled.len(), vector.len());
+            spilled.iter().zip(vector).for_each(|(a, b)| {
+                assert_eq!(a.0, b.0);
+                assert!((a.1 - b.1).abs() < 1e-6);
+            });
+        });
+}
+
+#[cfg(feature = "tf_idf")]
+#[test]
+fn test_tf_idf_add_documents() {
+    let stop_words = get_stop_words();
+    let documents = [
+        "Rust is a systems programming language.",
+        "Python is a scripting language.",
+        "Rust and Python developers write code.",
+        "Cargo builds Rust code.",
+    ];
+    let all_documents = documents
+        .iter()
+        .map(|document| document.to_string())
+        .collect::<Vec<String>>();
+    let fitted = tf_idf::TfIdf::new(tf_idf::TfIdfParams::UnprocessedDocuments(
+        &all_documents,
+        &stop_words,
+        None,
     ));
-    let words_result = tf_idf.get_ranked_words(100);
-    let expected_words = vec![
-        "rust",
-        "development",
-        "environment",
-        "work",
-        "programming",
-        "team",
-        "career",
-        "code",
-        "developer",
-        "dynamic",
-        "experience",
-        "familiarity",
-        "junior",
-        "learn",
-        "skills",
-        "strong",
-        "supportive",
-        "systems",
-        "to",
-        "well",
-        "ability",
-        "actively",
-        "actix",
-        "advancement",
-        "applications",
-        "apply",
-        "assist",
-        "bachelor",
-        "benefits",
-        "candidate",
-        "chance",
-        "clean",
-        "collaborate",
-        "collaborative",
-        "communication",
-        "competitive",
-        "computer",
-        "constructive",
-        "continuously",
-        "control",
-        "core",
-        "cutting",
-        "database",
-        "date",
-        "debugging",
-        "degree",
-        "description",
-        "design",
-        "desire",
-        "documented",
-        "ecosystem",
-        "edge",
-        "efficient",
-        "encourage",
-        "equivalent",
-        "excellent",
-        "features",
-        "feedback",
-        "field",
-        "foundation",
-        "frameworks",
-        "git",
-        "grow",
-        "growing",
-        "growth",
-        "haves",
-        "ideal",
-        "implement",
-        "javascript",
-        "job",
-        "join",
-        "kickstart",
-        "knowledge",
-        "language",
-        "languages",
-        "latest",
-        "like",
-        "looking",
-        "maintenance",
-        "mongodb",
-        "motivated",
-        "new",
-        "nice",
-        "offer",
-        "opportunity",
-        "oriented",
-        "package",
-        "participate",
-        "passion",
-        "passionate",
-        "postgresql",
-        "preferably",
-        "problem",
-        "proficiency",
-        "projects",
-        "provide",
-        "python",
-        "related",
-        "requirements",
-        "responsibilities",
-    ]
-    .iter()
-    .map(|x| x.to_string())
-    .collect::<HashSet<String>>();
-    assert!(is_percent_in_hashset(&words_result, &expected_words, 85.0));
+
+    let mut incremental = tf_idf::TfIdf::new(tf_idf::TfIdfParams::UnprocessedDocuments(
+        &all_documents[..2],
+        &stop_words,
+        None,
+    ));
+    assert!(incremental.get_idf("cargo").is_none());
+    assert_eq!(incremental.get_document_vectors().len(), 2);
+    incremental.add_documents(&documents[2..]);
+    assert_eq!(incremental.get_document_vectors().len(), 4);
+    assert!((incremental.cosine_similarity(0, 2) - fitted.cosine_similarity(0, 2)).abs() < 1e-6);
+
+    assert_eq!(incremental.get_idf_map().len(), fitted.get_idf_map().len());
+    fitted
+        .get_word_scores_map()
+        .iter()
+        .for_each(|(word, score)| {
+            assert!((incremental.get_score(word) - score).abs() < 1e-6);
+            assert!(
+                (incremental.get_idf(word).unwrap() - fitted.get_idf(word).unwrap()).abs() < 1e-6
+            );
+        });
 }
 
+#[cfg(feature = "co_occurrence")]
 #[test]
 fn test_co_occurrence() {
     let documents =
         tokenizer::Tokenizer::new(TEXT, &get_stop_words(), None).split_into_paragraphs();
-    let word_vec = vec![
+    let word_vec = [
         "rust",
         "development",
         "environment",
@@ -446,20 +2070,626 @@ fn test_co_occurrence() {
     .collect::<Vec<String>>();
     let co_occurrence = co_occurrence::CoOccurrence::new(&documents, &word_vec, 10);
     assert_eq!(
-        co_occurrence.get_matrix_row("rust").unwrap(),
-        [0.6666667, 0.6666667, 0.6666667, 0.0, 1.0, 0.6666667, 0.33333334, 0.0, 1.0, 0.6666667]
+        co_occurrence.get_matrix_row("rust").unwrap(),
+        [0.6666667, 0.6666667, 0.6666667, 0.0, 1.0, 0.6666667, 0.33333334, 0.0, 1.0, 0.6666667]
+    );
+    assert_eq!(
+        co_occurrence.get_matrix_row("development").unwrap(),
+        [0.6666667, 0.0, 0.33333334, 0.0, 0.0, 0.33333334, 0.33333334, 0.0, 0.0, 0.33333334]
+    );
+    assert_eq!(
+        co_occurrence.get_matrix_row("developer").unwrap(),
+        [1.0, 0.0, 0.0, 0.0, 0.33333334, 0.33333334, 0.0, 0.0, 0.0, 0.0]
+    );
+    assert_eq!(
+        co_occurrence.get_matrix_row("dynamic").unwrap(),
+        [0.6666667, 0.33333334, 0.6666667, 0.0, 0.33333334, 0.0, 0.33333334, 0.0, 0.0, 0.0]
+    );
+    assert!(!co_occurrence.is_sparse());
+
+    let sparse = co_occurrence::CoOccurrence::with_options(
+        &documents,
+        &word_vec,
+        10,
+        co_occurrence::CoOccurrenceOptions {
+            storage: co_occurrence::MatrixStorage::Sparse,
+            ..Default::default()
+        },
+    );
+    assert!(sparse.is_sparse());
+    assert_eq!(sparse.get_matrix(), co_occurrence.get_matrix());
+    assert_eq!(
+        sparse.get_relations("developer"),
+        co_occurrence.get_relations("developer")
+    );
+    assert_eq!(sparse.get_relation("rust", "code"), Some(0.0));
+
+    let top_relations = co_occurrence.get_top_relations("rust", 3, None).unwrap();
+    assert_eq!(
+        top_relations,
+        [
+            ("developer".to_string(), 1.0),
+            ("programming".to_string(), 1.0),
+            ("development".to_string(), 0.6666667)
+        ]
+    );
+    assert_eq!(
+        co_occurrence
+            .get_top_relations("rust", 10, Some(0.5))
+            .unwrap()
+            .len(),
+        7
+    );
+    assert!(co_occurrence.get_top_relations("java", 3, None).is_none());
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_weighting() {
+    let documents = [
+        "rust cargo rust python".to_string(),
+        "python pip".to_string(),
+    ];
+    let words = ["rust", "cargo", "python", "pip"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let weighted = |weighting| {
+        co_occurrence::CoOccurrence::with_options(
+            &documents,
+            &words,
+            1,
+            co_occurrence::CoOccurrenceOptions {
+                weighting,
+                ..Default::default()
+            },
+        )
+    };
+
+    let raw = weighted(co_occurrence::CoOccurrenceWeighting::Raw);
+    assert_eq!(raw.get_matrix_row("rust").unwrap(), [0.0, 2.0, 1.0, 0.0]);
+    assert_eq!(
+        weighted(co_occurrence::CoOccurrenceWeighting::MaxNormalized)
+            .get_relation("rust", "python"),
+        Some(0.5)
+    );
+    let row_normalized = weighted(co_occurrence::CoOccurrenceWeighting::RowNormalized);
+    assert_eq!(
+        row_normalized.get_matrix_row("rust").unwrap(),
+        [0.0, 2.0 / 3.0, 1.0 / 3.0, 0.0]
+    );
+    assert_eq!(row_normalized.get_relation("pip", "python"), Some(1.0));
+
+    // 8 co-occurrences, "cargo" and "pip" only appear next to "rust" and "python"
+    let pmi = weighted(co_occurrence::CoOccurrenceWeighting::Pmi);
+    let expected = ((2.0_f32 / 8.0) / (3.0 / 8.0 * 2.0 / 8.0)).ln();
+    assert!((pmi.get_relation("rust", "cargo").unwrap() - expected).abs() < 1e-6);
+    assert_eq!(pmi.get_relation("cargo", "pip"), Some(0.0));
+
+    let npmi = weighted(co_occurrence::CoOccurrenceWeighting::Npmi);
+    assert!(
+        (npmi.get_relation("rust", "cargo").unwrap() - expected / -(0.25_f32).ln()).abs() < 1e-6
+    );
+    assert!(npmi
+        .get_matrix()
+        .iter()
+        .flatten()
+        .all(|value| (-1.0..=1.0).contains(value)));
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_window_weighting() {
+    let documents = ["rust cargo rust python".to_string()];
+    let words = ["rust", "cargo", "python"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let weighted = |window_weighting| {
+        co_occurrence::CoOccurrence::with_options(
+            &documents,
+            &words,
+            2,
+            co_occurrence::CoOccurrenceOptions {
+                weighting: co_occurrence::CoOccurrenceWeighting::Raw,
+                window_weighting,
+                ..Default::default()
+            },
+        )
+    };
+
+    assert_eq!(
+        weighted(co_occurrence::WindowWeighting::Flat)
+            .get_matrix_row("rust")
+            .unwrap(),
+        [2.0, 2.0, 1.0]
+    );
+    assert_eq!(
+        weighted(co_occurrence::WindowWeighting::InverseDistance)
+            .get_matrix_row("rust")
+            .unwrap(),
+        [1.0, 2.0, 1.0]
+    );
+    let exponential = weighted(co_occurrence::WindowWeighting::Exponential(1.0));
+    assert_eq!(
+        exponential.get_relation("cargo", "python"),
+        Some((-1.0_f32).exp())
+    );
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_directed() {
+    let documents = ["rust cargo python".to_string()];
+    let words = ["rust", "cargo", "python"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let directed = co_occurrence::CoOccurrence::with_options(
+        &documents,
+        &words,
+        2,
+        co_occurrence::CoOccurrenceOptions {
+            weighting: co_occurrence::CoOccurrenceWeighting::Raw,
+            directed: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(directed.get_matrix_row("rust").unwrap(), [0.0, 1.0, 1.0]);
+    assert_eq!(directed.get_matrix_row("cargo").unwrap(), [0.0, 0.0, 1.0]);
+    assert_eq!(directed.get_relation("python", "rust"), Some(0.0));
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_add_documents() {
+    let documents = [
+        "rust cargo rust python".to_string(),
+        "python pip".to_string(),
+        "pip python rust".to_string(),
+    ];
+    let words = ["rust", "cargo", "python", "pip"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let options = co_occurrence::CoOccurrenceOptions {
+        weighting: co_occurrence::CoOccurrenceWeighting::Npmi,
+        ..Default::default()
+    };
+    let fitted = co_occurrence::CoOccurrence::with_options(&documents, &words, 2, options);
+
+    let mut incremental =
+        co_occurrence::CoOccurrence::with_options(&documents[..1], &words, 2, options);
+    assert_eq!(incremental.get_relation("python", "pip"), Some(0.0));
+    incremental.add_documents(&documents[1..]);
+    assert_eq!(incremental.get_matrix(), fitted.get_matrix());
+}
+
+#[cfg(all(feature = "co_occurrence", feature = "serde"))]
+#[test]
+fn test_co_occurrence_serde() {
+    let documents = [
+        "rust cargo rust python".to_string(),
+        "python pip".to_string(),
+    ];
+    let words = ["rust", "cargo", "python", "pip"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+
+    [
+        co_occurrence::MatrixStorage::Dense,
+        co_occurrence::MatrixStorage::Sparse,
+    ]
+    .into_iter()
+    .for_each(|storage| {
+        let co_occurrence = co_occurrence::CoOccurrence::with_options(
+            &documents,
+            &words,
+            2,
+            co_occurrence::CoOccurrenceOptions {
+                storage,
+                ..Default::default()
+            },
+        );
+        let serialized = serde_json::to_string(&co_occurrence).unwrap();
+        let mut loaded = serde_json::from_str::<co_occurrence::CoOccurrence>(&serialized).unwrap();
+        assert_eq!(loaded.get_matrix(), co_occurrence.get_matrix());
+        assert_eq!(loaded.get_labels(), co_occurrence.get_labels());
+        assert_eq!(loaded.is_sparse(), co_occurrence.is_sparse());
+
+        loaded.add_documents(&["pip python".to_string()]);
+        assert_eq!(loaded.get_relation("pip", "python"), Some(1.0));
+    });
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_phrases() {
+    let documents = [
+        "machine learning models need training data".to_string(),
+        "deep learning models".to_string(),
+        "learning data".to_string(),
+    ];
+    let words = [
+        "machine learning",
+        "deep learning",
+        "learning",
+        "models",
+        "data",
+    ]
+    .iter()
+    .map(|x| x.to_string())
+    .collect::<Vec<String>>();
+    let co_occurrence = co_occurrence::CoOccurrence::with_options(
+        &documents,
+        &words,
+        1,
+        co_occurrence::CoOccurrenceOptions {
+            weighting: co_occurrence::CoOccurrenceWeighting::Raw,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        co_occurrence.get_relation("machine learning", "models"),
+        Some(1.0)
+    );
+    assert_eq!(
+        co_occurrence.get_relation("deep learning", "models"),
+        Some(1.0)
+    );
+    assert_eq!(co_occurrence.get_relation("learning", "models"), Some(0.0));
+    assert_eq!(co_occurrence.get_relation("learning", "data"), Some(1.0));
+    assert_eq!(
+        co_occurrence.get_relation("machine learning", "data"),
+        Some(0.0)
+    );
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_tokenizer_phrases() {
+    let raw = co_occurrence::CoOccurrenceOptions {
+        weighting: co_occurrence::CoOccurrenceWeighting::Raw,
+        ..Default::default()
+    };
+    let documents = [
+        "Machine and learning models.".to_string(),
+        "The machine learning models.".to_string(),
+    ];
+    let words = ["machine learning", "machine", "learning", "models"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let stop_words = get_stop_words();
+    let tokenize = |text: &str| tokenizer::Tokenizer::new(text, &stop_words, None);
+
+    let co_occurrence = co_occurrence::CoOccurrence::with_options(&documents, &words, 1, raw);
+    assert_eq!(co_occurrence.get_relation("machine", "learning"), Some(0.0));
+
+    let mut tokenized =
+        co_occurrence::CoOccurrence::with_tokenizer(&documents, &words, 1, raw, tokenize);
+    // the stop words are dropped, but phrases are never matched across them
+    assert_eq!(tokenized.get_relation("machine", "learning"), Some(1.0));
+    assert_eq!(tokenized.get_relation("learning", "models"), Some(1.0));
+    assert_eq!(
+        tokenized.get_relation("machine learning", "models"),
+        Some(1.0)
+    );
+    assert_eq!(
+        tokenized.get_relation("machine learning", "machine"),
+        Some(0.0)
+    );
+
+    tokenized.add_documents_with_tokenizer(&documents[1..], tokenize);
+    assert_eq!(
+        tokenized.get_relation("machine learning", "models"),
+        Some(2.0)
+    );
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_context_scope() {
+    let documents =
+        ["Rust has cargo. Rust has crates, and crates have docs.\nPython has pip.".to_string()];
+    let words = ["Rust", "cargo", "crates", "docs", "Python", "pip"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let with_scope = |context_scope| {
+        co_occurrence::CoOccurrence::with_options(
+            &documents,
+            &words,
+            1,
+            co_occurrence::CoOccurrenceOptions {
+                weighting: co_occurrence::CoOccurrenceWeighting::Raw,
+                context_scope,
+                ..Default::default()
+            },
+        )
+    };
+
+    let sentence = with_scope(co_occurrence::ContextScope::Sentence);
+    assert_eq!(sentence.get_relation("Rust", "cargo"), Some(1.0));
+    assert_eq!(sentence.get_relation("Rust", "crates"), Some(1.0));
+    assert_eq!(sentence.get_relation("crates", "docs"), Some(1.0));
+    assert_eq!(sentence.get_relation("cargo", "crates"), Some(0.0));
+    assert_eq!(sentence.get_relation("crates", "crates"), Some(0.0));
+
+    let paragraph = with_scope(co_occurrence::ContextScope::Paragraph);
+    assert_eq!(paragraph.get_relation("Rust", "cargo"), Some(1.0));
+    assert_eq!(paragraph.get_relation("cargo", "docs"), Some(1.0));
+    assert_eq!(paragraph.get_relation("docs", "Python"), Some(0.0));
+    assert_eq!(paragraph.get_relation("Python", "pip"), Some(1.0));
+
+    let document = with_scope(co_occurrence::ContextScope::Document);
+    assert_eq!(document.get_relation("docs", "Python"), Some(1.0));
+    assert_eq!(document.get_relation("Rust", "pip"), Some(1.0));
+
+    let window = with_scope(co_occurrence::ContextScope::Window);
+    assert_eq!(window.get_relation("Rust", "crates"), Some(0.0));
+}
+
+#[cfg(all(feature = "co_occurrence", feature = "memmap2"))]
+#[test]
+fn test_co_occurrence_memory_map() {
+    let documents = [
+        "rust cargo crates rust cargo".to_string(),
+        "python pip python".to_string(),
+    ];
+    let words = ["rust", "cargo", "crates", "python", "pip"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let options = co_occurrence::CoOccurrenceOptions {
+        window_weighting: co_occurrence::WindowWeighting::InverseDistance,
+        ..Default::default()
+    };
+    let path = std::env::temp_dir().join("keyword_extraction_co_occurrence_memory_map.bin");
+    let mut mapped =
+        co_occurrence::CoOccurrence::with_memory_map(&documents[..1], &words, 2, options, &path)
+            .unwrap();
+    mapped.add_documents(&documents[1..]);
+    let in_memory = co_occurrence::CoOccurrence::with_options(&documents, &words, 2, options);
+
+    assert!(mapped.is_memory_mapped());
+    assert!(!in_memory.is_memory_mapped());
+    assert_eq!(
+        std::fs::metadata(&path).unwrap().len(),
+        (words.len() * words.len() * 4) as u64
+    );
+    assert_eq!(mapped.get_matrix(), in_memory.get_matrix());
+    assert_eq!(
+        mapped.get_top_relations("rust", 2, None),
+        in_memory.get_top_relations("rust", 2, None)
+    );
+    assert_eq!(mapped.clone().get_matrix(), in_memory.get_matrix());
+    assert!(!mapped.clone().is_memory_mapped());
+    #[cfg(feature = "serde")]
+    assert!(serde_json::to_string(&mapped).is_err());
+
+    drop(mapped);
+    let empty =
+        co_occurrence::CoOccurrence::with_memory_map(&[], &words, 2, options, &path).unwrap();
+    assert_eq!(empty.to_triplets(), []);
+
+    drop(empty);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_communities() {
+    let documents = [
+        "rust cargo crates rust cargo".to_string(),
+        "crates rust cargo".to_string(),
+        "python pip python".to_string(),
+        "pip python".to_string(),
+        "cargo python".to_string(),
+    ];
+    let words = ["rust", "cargo", "crates", "python", "pip", "java"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let co_occurrence = co_occurrence::CoOccurrence::new(&documents, &words, 1);
+
+    assert_eq!(
+        co_occurrence.communities(1.0),
+        [
+            vec!["rust", "cargo", "crates"],
+            vec!["python", "pip"],
+            vec!["java"]
+        ]
+    );
+    assert_eq!(co_occurrence.communities(100.0).len(), 6);
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_embeddings() {
+    let documents = [
+        "rust cargo crates rust cargo".to_string(),
+        "crates rust cargo".to_string(),
+        "python pip python".to_string(),
+        "pip python".to_string(),
+    ];
+    let words = ["rust", "cargo", "crates", "python", "pip"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let co_occurrence = co_occurrence::CoOccurrence::with_options(
+        &documents,
+        &words,
+        1,
+        co_occurrence::CoOccurrenceOptions {
+            weighting: co_occurrence::CoOccurrenceWeighting::Raw,
+            ..Default::default()
+        },
+    );
+    let dot = |embeddings: &co_occurrence::WordEmbeddings, word1: &str, word2: &str| {
+        embeddings
+            .get_vector(word1)
+            .unwrap()
+            .iter()
+            .zip(embeddings.get_vector(word2).unwrap())
+            .map(|(a, b)| a * b)
+            .sum::<f32>()
+    };
+
+    // the singular values are 4.8 and 4.2 for the "rust" words and 3.0 twice for the "python" ones
+    let embeddings = co_occurrence.get_embeddings(2);
+    assert_eq!(embeddings.get_dimensions(), 2);
+    assert_eq!(embeddings.get_vectors().len(), 5);
+    assert!(embeddings.get_vector("java").is_none());
+    assert!(dot(&embeddings, "pip", "pip") < 1e-3);
+    assert!(dot(&embeddings, "rust", "rust") > 1.0);
+
+    // with every dimension the vectors of "python" and "pip" are orthogonal of squared norm 3.0
+    let embeddings = co_occurrence.get_embeddings(10);
+    assert_eq!(embeddings.get_dimensions(), 5);
+    assert!((dot(&embeddings, "python", "python") - 3.0).abs() < 1e-3);
+    assert!(dot(&embeddings, "python", "pip").abs() < 1e-3);
+    assert!(embeddings.similarity("rust", "pip").unwrap().abs() < 1e-3);
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_export_graph() {
+    let documents = ["rust cargo rust python".to_string()];
+    let words = ["rust", "cargo", "python", "java"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let raw = co_occurrence::CoOccurrenceOptions {
+        weighting: co_occurrence::CoOccurrenceWeighting::Raw,
+        ..Default::default()
+    };
+    let co_occurrence = co_occurrence::CoOccurrence::with_options(&documents, &words, 1, raw);
+
+    let csv = co_occurrence.export_graph(co_occurrence::GraphFormat::EdgeListCsv, 0.0);
+    assert_eq!(csv, "source,target,weight\nrust,cargo,2\nrust,python,1\n");
+    let csv = co_occurrence.export_graph(co_occurrence::GraphFormat::EdgeListCsv, 2.0);
+    assert_eq!(csv, "source,target,weight\nrust,cargo,2\n");
+
+    let gexf = co_occurrence.export_graph(co_occurrence::GraphFormat::Gexf, 0.0);
+    assert!(gexf.contains("<graph defaultedgetype=\"undirected\">"));
+    assert!(gexf.contains(
+        "<node id=\"rust\" label=\"rust\"><attvalues><attvalue for=\"score\" value=\"3\"/>"
+    ));
+    assert!(gexf.contains("<node id=\"java\" label=\"java\">"));
+    assert!(gexf.contains("<edge id=\"1\" source=\"rust\" target=\"python\" weight=\"1\"/>"));
+    assert!(gexf.ends_with("</gexf>\n"));
+
+    let directed = co_occurrence::CoOccurrence::with_options(
+        &documents,
+        &words,
+        1,
+        co_occurrence::CoOccurrenceOptions {
+            directed: true,
+            ..raw
+        },
+    );
+    let graph_ml = directed.export_graph(co_occurrence::GraphFormat::GraphMl, 0.0);
+    assert!(graph_ml.contains("edgedefault=\"directed\""));
+    assert!(graph_ml
+        .contains("<edge source=\"cargo\" target=\"rust\"><data key=\"weight\">1</data></edge>"));
+    let dot = directed.export_graph(co_occurrence::GraphFormat::Dot, 0.0);
+    assert!(dot.contains("\"rust\" -> \"python\" [weight=1];"));
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_count_overflow() {
+    let words = ["rust", "cargo"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let documents = ["rust cargo ".repeat(40_000)];
+    let co_occurrence = co_occurrence::CoOccurrence::with_options(
+        &documents,
+        &words,
+        1,
+        co_occurrence::CoOccurrenceOptions {
+            weighting: co_occurrence::CoOccurrenceWeighting::Raw,
+            ..Default::default()
+        },
+    );
+    assert_eq!(co_occurrence.get_relation("rust", "cargo"), Some(79_999.0));
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_export() {
+    let documents = ["rust cargo".to_string(), "python pip".to_string()];
+    let words = ["rust", "cargo", "python", "pip"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let co_occurrence = co_occurrence::CoOccurrence::new(&documents, &words, 1);
+    assert_eq!(
+        co_occurrence.to_triplets(),
+        [(0, 1, 1.0), (1, 0, 1.0), (2, 3, 1.0), (3, 2, 1.0)]
     );
+
+    #[cfg(feature = "ndarray")]
+    {
+        let array = co_occurrence.to_array2();
+        assert_eq!(array.shape(), [4, 4]);
+        assert_eq!(
+            array
+                .rows()
+                .into_iter()
+                .map(|row| row.to_vec())
+                .collect::<Vec<Vec<f32>>>(),
+            co_occurrence.to_dense()
+        );
+    }
+    assert_eq!(&co_occurrence.to_dense(), co_occurrence.get_matrix());
     assert_eq!(
-        co_occurrence.get_matrix_row("development").unwrap(),
-        [0.6666667, 0.0, 0.33333334, 0.0, 0.0, 0.33333334, 0.33333334, 0.0, 0.0, 0.33333334]
+        co_occurrence.get_matrix_row_entries(&words[0]),
+        Some(vec![(1, 1.0)])
     );
+}
+
+#[cfg(feature = "co_occurrence")]
+#[test]
+fn test_co_occurrence_similarity() {
+    let documents = [
+        "rust cargo".to_string(),
+        "python cargo".to_string(),
+        "python pip".to_string(),
+        "java maven".to_string(),
+    ];
+    let words = ["rust", "python", "java", "cargo", "pip", "maven"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+    let co_occurrence = co_occurrence::CoOccurrence::new(&documents, &words, 1);
+
+    assert!((co_occurrence.similarity("rust", "rust").unwrap() - 1.0).abs() < 1e-6);
+    assert!(co_occurrence.similarity("rust", "python").unwrap() > 0.0);
+    assert_eq!(co_occurrence.similarity("rust", "java"), Some(0.0));
+    assert_eq!(co_occurrence.similarity("rust", "go"), None);
+
+    let most_similar = co_occurrence.most_similar("rust", 5).unwrap();
+    assert_eq!(most_similar.len(), 1);
+    assert_eq!(most_similar[0].0, "python");
+    assert_eq!(co_occurrence.most_similar("cargo", 1).unwrap()[0].0, "pip");
+
+    // "rust" and "python" never co-occur but both co-occur with "cargo"
+    assert_eq!(co_occurrence.get_relation("rust", "python"), Some(0.0));
     assert_eq!(
-        co_occurrence.get_matrix_row("developer").unwrap(),
-        [1.0, 0.0, 0.0, 0.0, 0.33333334, 0.33333334, 0.0, 0.0, 0.0, 0.0]
+        co_occurrence.get_second_order_relation("rust", "python"),
+        Some(1.0)
     );
+    let second_order = co_occurrence.get_second_order_matrix();
+    assert_eq!(second_order[0], [1.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+    assert_eq!(second_order[1][1], 2.0);
     assert_eq!(
-        co_occurrence.get_matrix_row("dynamic").unwrap(),
-        [0.6666667, 0.33333334, 0.6666667, 0.0, 0.33333334, 0.0, 0.33333334, 0.0, 0.0, 0.0]
+        co_occurrence.get_second_order_relation("java", "cargo"),
+        Some(second_order[2][3])
     );
 }
 
@@ -497,6 +2727,118 @@ fn test_rake() {
     }
 }
 
+#[test]
+fn test_rake_metric() {
+    let frequency_rake = rake::Rake::with_options(
+        rake::RakeParams::WithDefaults(TEXT, &get_stop_words()),
+        rake::RakeOptions {
+            metric: rake::RakeMetric::Frequency,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        frequency_rake.get_ranked_keyword(1),
+        vec!["rust".to_string()]
+    );
+    assert_eq!(frequency_rake.get_keyword_score("rust"), 8.0);
+
+    let degree_rake = rake::Rake::with_options(
+        rake::RakeParams::WithDefaults(TEXT, &get_stop_words()),
+        rake::RakeOptions {
+            metric: rake::RakeMetric::Degree,
+            ..Default::default()
+        },
+    );
+    let ratio_rake = rake::Rake::new(rake::RakeParams::WithDefaults(TEXT, &get_stop_words()));
+    assert!(degree_rake.get_keyword_score("rust") > ratio_rake.get_keyword_score("rust"));
+}
+
+#[test]
+fn test_rake_adjoining() {
+    let text = "The axis of evil is real. Fear the axis of evil. The axis and evil differ.";
+    let stop_words = get_stop_words();
+    let adjoined_phrases =
+        tokenizer::Tokenizer::new(text, &stop_words, None).split_into_adjoined_phrases(2);
+    assert_eq!(adjoined_phrases, vec!["axis of evil".to_string()]);
+
+    let rake_struct = rake::Rake::with_options(
+        rake::RakeParams::All(text, &stop_words, None, None),
+        rake::RakeOptions {
+            metric: rake::RakeMetric::Degree,
+            adjoining_frequency: Some(2),
+            ..Default::default()
+        },
+    );
+    assert!(rake_struct.get_phrase_score("axis of evil") > 0.0);
+    assert_eq!(rake_struct.get_phrase_score("axis and evil"), 0.0);
+}
+
+#[test]
+fn test_rake_phrase_bounds() {
+    let rake_struct = rake::Rake::with_options(
+        rake::RakeParams::WithDefaults(TEXT, &get_stop_words()),
+        rake::RakeOptions {
+            min_words: 2,
+            max_words: Some(4),
+            ..Default::default()
+        },
+    );
+    let phrases = rake_struct.get_phrase_scores_map();
+    assert!(!phrases.is_empty());
+    assert!(phrases.keys().all(|phrase| {
+        let words = phrase.split_whitespace().count();
+        (2..=4).contains(&words)
+    }));
+    assert_eq!(rake_struct.get_phrase_score("rust"), 0.0);
+    assert!(rake_struct.get_phrase_score("motivated junior rust developer") > 0.0);
+}
+
+#[test]
+fn test_rake_stop_phrases() {
+    let text = "Rust tooling from my point of view cargo. Rust tooling no matter what ship crates.";
+    let stop_words = get_stop_words();
+    let stop_phrases = ["point of view".to_string(), "no matter what".to_string()];
+    let phrases = tokenizer::Tokenizer::new(text, &stop_words, None).split_into_phrases(None);
+    assert!(phrases.iter().any(|phrase| phrase.contains("view")));
+
+    let phrases = tokenizer::Tokenizer::new(text, &stop_words, None)
+        .with_stop_phrases(&stop_phrases)
+        .split_into_phrases(None);
+    assert!(phrases.contains(&"rust tooling".to_string()));
+    assert!(phrases.contains(&"ship crates".to_string()));
+    assert!(!phrases.iter().any(|phrase| phrase.contains("view")));
+    assert!(!phrases.iter().any(|phrase| phrase.contains("matter")));
+
+    let rake_struct = rake::Rake::with_options(
+        rake::RakeParams::WithDefaults(text, &stop_words),
+        rake::RakeOptions {
+            stop_phrases: Some(&stop_phrases),
+            ..Default::default()
+        },
+    );
+    assert_eq!(rake_struct.get_keyword_score("view"), 0.0);
+    assert!(rake_struct.get_phrase_score("rust tooling") > 0.0);
+}
+
+#[test]
+fn test_rake_offsets() {
+    let text = "Rust's tooling is great. I like RUST TOOLING, really.";
+    let rake_struct = rake::Rake::new(rake::RakeParams::WithDefaults(text, &get_stop_words()));
+    let offsets = rake_struct.get_phrase_offsets("rust tooling");
+    assert_eq!(offsets, [(0, 14), (32, 44)]);
+    let highlighted = offsets
+        .iter()
+        .map(|(start, end)| {
+            text.chars()
+                .skip(*start)
+                .take(end - start)
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>();
+    assert_eq!(highlighted, ["Rust's tooling", "RUST TOOLING"]);
+    assert!(rake_struct.get_phrase_offsets("not a phrase").is_empty());
+}
+
 #[test]
 fn test_text_rank() {
     let expected_words = [
@@ -544,3 +2886,587 @@ fn test_text_rank() {
         assert!(phrase.split_whitespace().count() <= 3);
     }
 }
+
+#[test]
+fn test_text_rank_bias() {
+    let bias = [("python", 1.0), ("javascript", 1.0)]
+        .iter()
+        .map(|(w, b)| (w.to_string(), *b))
+        .collect::<std::collections::HashMap<String, f32>>();
+    let stop_words = get_stop_words();
+    let text_rank =
+        text_rank::TextRank::new(text_rank::TextRankParams::WithDefaults(TEXT, &stop_words));
+    let biased_text_rank = text_rank::TextRank::with_options(
+        text_rank::TextRankParams::WithDefaults(TEXT, &stop_words),
+        text_rank::TextRankOptions {
+            bias: Some(&bias),
+            ..Default::default()
+        },
+    );
+    assert!(biased_text_rank.get_word_score("python") > text_rank.get_word_score("python"));
+    assert!(biased_text_rank.get_word_score("javascript") > text_rank.get_word_score("javascript"));
+    assert!(biased_text_rank.get_word_score("rust") < text_rank.get_word_score("rust"));
+}
+
+#[test]
+fn test_text_rank_edge_weighting() {
+    let stop_words = get_stop_words();
+    let text_rank = text_rank::TextRank::with_options(
+        text_rank::TextRankParams::All(TEXT, &stop_words, None, 4, 0.85, 0.00005, None),
+        text_rank::TextRankOptions {
+            edge_weighting: text_rank::EdgeWeighting::InverseDistance,
+            ..Default::default()
+        },
+    );
+    let expected_words = ["rust", "environment", "development", "team", "programming"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<HashSet<String>>();
+    assert!(is_percent_in_hashset(
+        &text_rank.get_ranked_words(3),
+        &expected_words,
+        60.0
+    ));
+
+    let decayed_text_rank = text_rank::TextRank::with_options(
+        text_rank::TextRankParams::All(TEXT, &stop_words, None, 4, 0.85, 0.00005, None),
+        text_rank::TextRankOptions {
+            edge_weighting: text_rank::EdgeWeighting::Custom(|distance| {
+                0.5_f32.powi(distance as i32)
+            }),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        decayed_text_rank.get_ranked_words(1),
+        vec!["rust".to_string()]
+    );
+}
+
+#[test]
+fn test_text_rank_export_graph() {
+    let text_rank = text_rank::TextRank::new(text_rank::TextRankParams::WithDefaults(
+        "Rust developers love Rust tooling.",
+        &get_stop_words(),
+    ));
+    let dot = text_rank.export_graph(text_rank::GraphFormat::Dot);
+    assert!(dot.starts_with("graph \"text_rank\" {"));
+    assert!(dot.contains("\"developers\" -- \"rust\" [weight=2];"));
+    assert!(dot.contains("\"rust\" [score="));
+
+    let graph_ml = text_rank.export_graph(text_rank::GraphFormat::GraphMl);
+    assert!(graph_ml.contains("<node id=\"tooling\">"));
+    assert!(graph_ml
+        .contains("<edge source=\"love\" target=\"tooling\"><data key=\"weight\">1</data></edge>"));
+    assert!(graph_ml.ends_with("</graphml>\n"));
+}
+
+#[test]
+fn test_text_rank_graph_access() {
+    let text_rank = text_rank::TextRank::new(text_rank::TextRankParams::WithDefaults(
+        "Rust developers love Rust tooling.",
+        &get_stop_words(),
+    ));
+    assert_eq!(text_rank.get_graph().len(), 4);
+    assert_eq!(text_rank.get_neighbors("rust").unwrap().len(), 3);
+    assert!(text_rank.get_neighbors("python").is_none());
+    assert_eq!(text_rank.get_edge_weight("rust", "developers"), Some(2.0));
+    assert_eq!(
+        text_rank.get_edge_weight("developers", "tooling"),
+        Some(0.0)
+    );
+    assert_eq!(text_rank.get_edge_weight("rust", "python"), None);
+}
+
+#[test]
+fn test_text_rank_convergence_report() {
+    let text_rank = text_rank::TextRank::new(text_rank::TextRankParams::WithDefaults(
+        TEXT,
+        &get_stop_words(),
+    ));
+    let report = text_rank.get_convergence_report();
+    assert!(report.converged);
+    assert!(report.iterations > 1);
+    assert!(report.final_delta < 0.00005);
+}
+
+#[test]
+fn test_text_rank_unique_phrases() {
+    let text_rank = text_rank::TextRank::new(text_rank::TextRankParams::WithDefaults(
+        "Neural networks are popular and the neural network is useful",
+        &get_stop_words(),
+    ));
+    let phrases = text_rank.get_ranked_phrases(10);
+    assert!(phrases.contains(&"neural networks".to_string()));
+    assert!(phrases.contains(&"neural network".to_string()));
+
+    let unique_phrases =
+        text_rank.get_ranked_unique_phrase_scores(10, text_rank::PhraseNormalization::SStemmer);
+    assert_eq!(unique_phrases.len(), phrases.len() - 1);
+    assert_eq!(
+        unique_phrases
+            .iter()
+            .filter(|(p, _)| p.starts_with("neural network"))
+            .count(),
+        1
+    );
+
+    let custom_phrases = text_rank.get_ranked_unique_phrases(
+        10,
+        text_rank::PhraseNormalization::Custom(|phrase| phrase.trim_end_matches('s').to_string()),
+    );
+    assert_eq!(custom_phrases.len(), phrases.len() - 1);
+}
+
+#[test]
+fn test_text_rank_sentences() {
+    let text_rank = text_rank::TextRank::new(text_rank::TextRankParams::WithDefaults(
+        "Rust is a systems programming language. \
+        Rust programming is fast and safe. \
+        The weather is nice today. \
+        Systems written in Rust are safe.",
+        &get_stop_words(),
+    ));
+    let sentences = text_rank.rank_sentences(4);
+    assert_eq!(sentences.len(), 4);
+    assert_eq!(sentences[3].0, "The weather is nice today.");
+    assert!(sentences[0].0.contains("Rust"));
+    assert!(sentences[0].1 > sentences[3].1);
+}
+
+#[cfg(feature = "position_rank")]
+#[test]
+fn test_position_rank() {
+    let stop_words = get_stop_words();
+    let position_rank = position_rank::PositionRank::new(
+        position_rank::PositionRankParams::WithDefaults(TEXT, &stop_words),
+    );
+    let text_rank =
+        text_rank::TextRank::new(text_rank::TextRankParams::WithDefaults(TEXT, &stop_words));
+    let ranked_words = position_rank.get_ranked_words(10);
+    assert_eq!(ranked_words[0], "rust");
+    assert!(ranked_words.contains(&"junior".to_string()));
+    assert!(position_rank.get_word_score("title") > text_rank.get_word_score("title"));
+    assert!(position_rank.get_word_score("encourage") < text_rank.get_word_score("encourage"));
+
+    let limited_position_rank = position_rank::PositionRank::new(
+        position_rank::PositionRankParams::WithDefaultsAndPhraseLength(TEXT, &stop_words, Some(3)),
+    );
+    for phrase in limited_position_rank.get_ranked_phrases(10) {
+        assert!(phrase.split_whitespace().count() <= 3);
+    }
+}
+
+#[cfg(feature = "single_rank")]
+#[test]
+fn test_single_rank() {
+    let stop_words = get_stop_words();
+    let single_rank = single_rank::SingleRank::new(single_rank::SingleRankParams::WithDefaults(
+        TEXT,
+        &stop_words,
+    ));
+    let expected_words = [
+        "rust",
+        "environment",
+        "development",
+        "team",
+        "programming",
+        "code",
+        "systems",
+        "skills",
+        "experience",
+        "familiarity",
+    ]
+    .iter()
+    .map(|x| x.to_string())
+    .collect::<HashSet<String>>();
+    assert!(is_percent_in_hashset(
+        &single_rank.get_ranked_words(5),
+        &expected_words,
+        80.0
+    ));
+
+    let phrase_scores = single_rank.get_ranked_phrase_scores(10);
+    for (phrase, score) in phrase_scores {
+        let words_score = phrase
+            .split_whitespace()
+            .map(|w| single_rank.get_word_score(w))
+            .sum::<f32>();
+        assert!((score - words_score).abs() < 0.0001);
+    }
+}
+
+#[cfg(feature = "topic_rank")]
+#[test]
+fn test_topic_rank() {
+    let topic_rank = topic_rank::TopicRank::new(topic_rank::TopicRankParams::WithDefaults(
+        TEXT,
+        &get_stop_words(),
+    ));
+    let ranked_phrases = topic_rank.get_ranked_phrases(10);
+    assert_eq!(ranked_phrases.len(), 10);
+    assert_eq!(ranked_phrases[0], "rust");
+    assert!(topic_rank
+        .get_topic("rust")
+        .unwrap()
+        .contains(&"rust development".to_string()));
+
+    let topics = topic_rank.get_topics_map();
+    let candidates = topics.values().flatten().collect::<Vec<&String>>();
+    let unique_candidates = candidates.iter().collect::<HashSet<&&String>>();
+    assert_eq!(candidates.len(), unique_candidates.len());
+    assert!(topics
+        .iter()
+        .all(|(phrase, topic)| topic.first() == Some(phrase)));
+}
+
+#[cfg(feature = "multipartite_rank")]
+#[test]
+fn test_multipartite_rank() {
+    let stop_words = get_stop_words();
+    let multipartite_rank = multipartite_rank::MultipartiteRank::new(
+        multipartite_rank::MultipartiteRankParams::WithDefaults(TEXT, &stop_words),
+    );
+    let ranked_phrases = multipartite_rank.get_ranked_phrases(10);
+    assert_eq!(ranked_phrases.len(), 10);
+    assert_eq!(ranked_phrases[0], "rust");
+
+    let unboosted_multipartite_rank =
+        multipartite_rank::MultipartiteRank::new(multipartite_rank::MultipartiteRankParams::All(
+            TEXT,
+            &stop_words,
+            None,
+            0.74,
+            0.0,
+            0.85,
+            0.00005,
+            None,
+        ));
+    let first_phrase = "title junior rust developer job description";
+    assert!(
+        multipartite_rank.get_phrase_score(first_phrase)
+            > unboosted_multipartite_rank.get_phrase_score(first_phrase)
+    );
+}
+
+#[test]
+fn test_topical_text_rank() {
+    let stop_words = get_stop_words();
+    let word_topics = [
+        ("python", vec![0.9, 0.1]),
+        ("javascript", vec![0.9, 0.1]),
+        ("rust", vec![0.1, 0.9]),
+    ]
+    .iter()
+    .map(|(w, p)| (w.to_string(), p.to_vec()))
+    .collect::<std::collections::HashMap<String, Vec<f32>>>();
+    let languages_topic =
+        text_rank::TopicalTextRank::new(text_rank::TopicalTextRankParams::WithDefaults(
+            TEXT,
+            &stop_words,
+            &word_topics,
+            Some(&[1.0, 0.0]),
+        ));
+    let rust_topic =
+        text_rank::TopicalTextRank::new(text_rank::TopicalTextRankParams::WithDefaults(
+            TEXT,
+            &stop_words,
+            &word_topics,
+            Some(&[0.0, 1.0]),
+        ));
+    assert!(languages_topic.get_word_score("python") > rust_topic.get_word_score("python"));
+    assert!(languages_topic.get_word_score("rust") < rust_topic.get_word_score("rust"));
+
+    let mixed_topics = text_rank::TopicalTextRank::new(
+        text_rank::TopicalTextRankParams::WithDefaults(TEXT, &stop_words, &word_topics, None),
+    );
+    let python_score = mixed_topics.get_word_score("python");
+    assert!(python_score < languages_topic.get_word_score("python"));
+    assert!(python_score > rust_topic.get_word_score("python"));
+}
+
+#[cfg(feature = "embed_rank")]
+struct CharacterEmbedder;
+
+#[cfg(feature = "embed_rank")]
+impl embed_rank::Embedder for CharacterEmbedder {
+    fn embed(&self, text: &str) -> Vec<f32> {
+        text.to_lowercase()
+            .chars()
+            .filter(|c| c.is_ascii_lowercase())
+            .fold(vec![0.0; 26], |mut embedding, c| {
+                embedding[(c as u8 - b'a') as usize] += 1.0;
+                embedding
+            })
+    }
+}
+
+#[cfg(feature = "embed_rank")]
+#[test]
+fn test_embed_rank() {
+    let stop_words = get_stop_words();
+    let embed_rank = embed_rank::EmbedRank::new(embed_rank::EmbedRankParams::WithDefaults(
+        TEXT,
+        &stop_words,
+        &CharacterEmbedder,
+    ));
+    let ranked_phrases = embed_rank.get_ranked_phrase_scores(5);
+    assert_eq!(ranked_phrases.len(), 5);
+    assert!(ranked_phrases
+        .iter()
+        .all(|(_, score)| *score > 0.0 && *score <= 1.0));
+    assert_eq!(
+        embed_rank.get_phrase_embedding("rust").map(|e| e.len()),
+        Some(26)
+    );
+
+    let diverse_phrases = embed_rank.get_diverse_phrases(5, 0.0);
+    assert_eq!(diverse_phrases[0], ranked_phrases[0].0);
+    let diverse_phrases = embed_rank.get_diverse_phrases(5, 0.7);
+    assert_eq!(diverse_phrases.len(), 5);
+    assert_eq!(
+        diverse_phrases
+            .iter()
+            .collect::<std::collections::HashSet<_>>()
+            .len(),
+        5
+    );
+}
+
+#[cfg(feature = "kp_miner")]
+#[test]
+fn test_kp_miner() {
+    let stop_words = get_stop_words();
+    // no candidate is seen at least three times in such a short text
+    let kp_miner = kp_miner::KpMiner::new(kp_miner::KpMinerParams::WithDefaults(TEXT, &stop_words));
+    assert!(kp_miner.get_phrase_scores_map().is_empty());
+
+    let kp_miner = kp_miner::KpMiner::new(kp_miner::KpMinerParams::All(
+        TEXT,
+        &stop_words,
+        None,
+        None,
+        2,
+        400,
+        3.0,
+        2.3,
+        None,
+    ));
+    assert_eq!(kp_miner.get_ranked_phrases(10), vec!["rust".to_string()]);
+
+    let documents = vec![
+        "Rust is a systems programming language.".to_string(),
+        "Rust developers love the Rust compiler.".to_string(),
+    ];
+    let kp_miner = kp_miner::KpMiner::new(kp_miner::KpMinerParams::All(
+        TEXT,
+        &stop_words,
+        None,
+        Some(&documents),
+        1,
+        400,
+        3.0,
+        2.3,
+        None,
+    ));
+    assert!(kp_miner.get_phrase_score("rust") < kp_miner.get_phrase_score("python javascript"));
+}
+
+#[cfg(feature = "lex_rank")]
+#[test]
+fn test_lex_rank() {
+    let text = "Rust is a systems programming language. \
+        Rust programming is fast and memory safe. \
+        Cooking pasta requires boiling water. \
+        The Rust language has great programming tools.";
+    let lex_rank = lex_rank::LexRank::new(lex_rank::LexRankParams::WithDefaultsAndThreshold(
+        text,
+        &get_stop_words(),
+        0.0,
+    ));
+    let ranked_sentences = lex_rank.get_ranked_sentences(4);
+    assert_eq!(ranked_sentences.len(), 4);
+    assert_eq!(ranked_sentences[3], "Cooking pasta requires boiling water.");
+    assert!(
+        lex_rank.get_sentence_score("Rust is a systems programming language.")
+            > lex_rank.get_sentence_score("Cooking pasta requires boiling water.")
+    );
+}
+
+#[cfg(feature = "sg_rank")]
+#[test]
+fn test_sg_rank() {
+    let stop_words = get_stop_words();
+    let sg_rank = sg_rank::SgRank::new(sg_rank::SgRankParams::All(
+        TEXT,
+        &stop_words,
+        None,
+        1,
+        20,
+        1500,
+        3000,
+        0.85,
+        0.00005,
+        None,
+    ));
+    assert_eq!(sg_rank.get_phrase_scores_map().len(), 20);
+    assert_eq!(
+        sg_rank.get_ranked_phrases(1),
+        vec!["title junior rust developer job description".to_string()]
+    );
+
+    // only "rust" is seen at least twice as a candidate
+    let sg_rank = sg_rank::SgRank::new(sg_rank::SgRankParams::WithDefaults(TEXT, &stop_words));
+    assert_eq!(sg_rank.get_ranked_phrases(10), vec!["rust".to_string()]);
+}
+
+#[cfg(feature = "k_core")]
+#[test]
+fn test_k_core() {
+    let stop_words = get_stop_words();
+    let text = "Rust compiler language, rust compiler language and rust compiler. Cooking pasta.";
+    let k_core = k_core::KCore::new(k_core::KCoreParams::All(text, &stop_words, None, 1, false));
+    assert_eq!(k_core.get_main_core(), vec!["compiler", "language", "rust"]);
+    assert_eq!(k_core.get_core_number("rust"), 2.0);
+    assert_eq!(k_core.get_core_number("pasta"), 1.0);
+
+    let k_core = k_core::KCore::new(k_core::KCoreParams::All(text, &stop_words, None, 1, true));
+    assert!(k_core.get_core_number("rust") > k_core.get_core_number("pasta"));
+
+    let k_core = k_core::KCore::new(k_core::KCoreParams::WithDefaults(TEXT, &stop_words));
+    assert!(k_core.get_main_core().contains(&"rust".to_string()));
+}
+
+#[cfg(feature = "yake")]
+#[test]
+fn test_yake() {
+    let stop_words = get_stop_words();
+    let yake = yake::Yake::new(yake::YakeParams::WithDefaults(TEXT, &stop_words));
+    assert_eq!(
+        yake.get_ranked_keywords(5),
+        [
+            "junior rust developer",
+            "junior rust",
+            "rust developer",
+            "motivated junior rust",
+            "rust"
+        ]
+    );
+
+    let deduplicated_yake = yake::Yake::new(yake::YakeParams::All(
+        TEXT,
+        &stop_words,
+        None,
+        0.5,
+        3,
+        1,
+        yake::YakeFeatureWeights::default(),
+    ));
+    assert_eq!(deduplicated_yake.get_keyword_score("junior rust"), 0.0);
+    assert!(deduplicated_yake.get_keyword_score("junior rust developer") > 0.0);
+    assert!(deduplicated_yake.get_keyword_scores_map().len() < yake.get_keyword_scores_map().len());
+
+    let unigram_yake =
+        yake::Yake::new(yake::YakeParams::WithDefaultsAndNgram(TEXT, &stop_words, 1));
+    assert!(unigram_yake
+        .get_keyword_scores_map()
+        .keys()
+        .all(|keyword| !keyword.contains(' ')));
+    assert_eq!(unigram_yake.get_ranked_keywords(1), ["rust"]);
+
+    let explanation = yake.explain("junior rust developer").unwrap();
+    assert_eq!(
+        explanation
+            .iter()
+            .map(|(term, _)| term.as_str())
+            .collect::<Vec<&str>>(),
+        ["junior", "rust", "developer"]
+    );
+    assert!(explanation
+        .iter()
+        .all(|(_, features)| features.score > 0.0 && features.relatedness >= 1.0));
+    let (_, rust_features) = &explanation[1];
+    assert!(rust_features.frequency > explanation[0].1.frequency);
+    assert!(yake.explain("not a keyword").is_none());
+
+    let (keyword, offsets) = &yake.get_ranked_keyword_offsets(1)[0];
+    assert_eq!(keyword, "junior rust developer");
+    assert_eq!(offsets.len(), 2);
+    assert!(offsets.iter().all(|(start, end)| {
+        TEXT.chars()
+            .skip(*start)
+            .take(end - start)
+            .collect::<String>()
+            == "Junior Rust Developer"
+    }));
+
+    let caseless_yake = yake::Yake::new(yake::YakeParams::WithDefaultsAndFeatureWeights(
+        TEXT,
+        &stop_words,
+        yake::YakeFeatureWeights {
+            casing: 0.0,
+            ..Default::default()
+        },
+    ));
+    let default_yake = yake::Yake::new(yake::YakeParams::WithDefaults(TEXT, &stop_words));
+    let windowed_yake = yake::Yake::new(yake::YakeParams::WithDefaultsAndWindowSize(
+        TEXT,
+        &stop_words,
+        2,
+    ));
+    let relatedness = |yake: &yake::Yake| yake.explain("rust").unwrap()[0].1.relatedness;
+    assert!(relatedness(&windowed_yake) > relatedness(&default_yake));
+    // ignoring the capitalisation of "Rust" makes it look less like a keyword
+    assert!(
+        caseless_yake.get_keyword_score("rust") < default_yake.get_keyword_score("rust"),
+        "casing weight was not applied"
+    );
+}
+
+#[cfg(feature = "bm25")]
+#[test]
+fn test_bm25() {
+    let stop_words = get_stop_words();
+    let documents = [
+        "Rust is a systems programming language focused on safety.".to_string(),
+        "Python is a popular scripting language.".to_string(),
+        "The Rust compiler checks memory safety, Rust code is fast.".to_string(),
+    ];
+    let bm25 = bm25::Bm25::new(bm25::Bm25Params::WithDefaults(&documents, &stop_words));
+    assert!(bm25.get_term_score(0, "rust") > 0.0);
+    assert_eq!(bm25.get_term_score(1, "rust"), 0.0);
+    // term frequency saturates but still increases the score
+    assert!(bm25.get_term_score(2, "rust") > bm25.get_term_score(0, "rust"));
+    // terms shared with other documents have a lower idf
+    assert_eq!(
+        bm25.get_ranked_terms(1, 3),
+        ["popular", "python", "scripting"]
+    );
+
+    let ranked = bm25.score_query("Rust memory safety");
+    assert_eq!(
+        ranked
+            .iter()
+            .map(|(document, _)| *document)
+            .collect::<Vec<usize>>(),
+        [2, 0]
+    );
+    assert!(bm25.score_query("cobol").is_empty());
+
+    let bm25_plus = bm25::Bm25::new(bm25::Bm25Params::WithDefaultsAndDelta(
+        &documents,
+        &stop_words,
+        1.0,
+    ));
+    assert!(bm25_plus.get_term_score(0, "rust") > bm25.get_term_score(0, "rust"));
+    // documents without any query term score 0 and are not returned
+    assert_eq!(bm25_plus.get_term_score(1, "rust"), 0.0);
+    assert_eq!(
+        bm25_plus
+            .score_query("rust")
+            .iter()
+            .map(|(document, _)| *document)
+            .collect::<Vec<usize>>(),
+        [2, 0]
+    );
+}
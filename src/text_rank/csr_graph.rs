@@ -0,0 +1,96 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::text_rank_logic::Graph;
+
+/// Compressed sparse row adjacency of a word graph, where every node is mapped once to an index.
+/// Every row holds the incoming edges of a node, i.e. `graph[node][neighbor]` is the weight of
+/// the edge from `neighbor` to `node`, which for undirected graphs is the same in both directions.
+/// The stored weights are already divided by the outgoing weight sum of the neighbour, so a
+/// PageRank iteration is a single pass over contiguous arrays.
+pub struct CsrGraph {
+    row_offsets: Vec<usize>,
+    columns: Vec<usize>,
+    weights: Vec<f32>,
+}
+
+impl CsrGraph {
+    pub fn new(graph: &Graph, nodes: &[&String], node_indexes: &HashMap<String, usize>) -> Self {
+        let mut outgoing_weight_sums = vec![0.0_f32; nodes.len()];
+        graph.values().for_each(|edges| {
+            edges.iter().for_each(|(neighbor, weight)| {
+                outgoing_weight_sums[node_indexes[neighbor]] += weight;
+            })
+        });
+        let edges_count = graph.values().map(|edges| edges.len()).sum::<usize>();
+        let mut row_offsets = Vec::with_capacity(nodes.len() + 1);
+        let mut columns = Vec::with_capacity(edges_count);
+        let mut weights = Vec::with_capacity(edges_count);
+        row_offsets.push(0);
+
+        nodes.iter().for_each(|node| {
+            graph[node.as_str()].iter().for_each(|(neighbor, weight)| {
+                let neighbor_index = node_indexes[neighbor];
+                columns.push(neighbor_index);
+                weights.push(weight / outgoing_weight_sums[neighbor_index]);
+            });
+            row_offsets.push(columns.len());
+        });
+
+        Self {
+            row_offsets,
+            columns,
+            weights,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.row_offsets.len() - 1
+    }
+
+    fn score_row(&self, row: usize, prev_scores: &[f32], teleport: &[f32], damping: f32) -> f32 {
+        let (start, end) = (self.row_offsets[row], self.row_offsets[row + 1]);
+        let new_score = self.columns[start..end]
+            .iter()
+            .zip(self.weights[start..end].iter())
+            .map(|(column, weight)| weight * prev_scores[*column])
+            .sum::<f32>();
+
+        (1.0 - damping) * teleport[row] + damping * new_score
+    }
+
+    /// Runs one PageRank iteration over every row.
+    pub fn get_scores(&self, prev_scores: &[f32], teleport: &[f32], damping: f32) -> Vec<f32> {
+        #[cfg(feature = "parallel")]
+        {
+            (0..self.len())
+                .into_par_iter()
+                .map(|row| self.score_row(row, prev_scores, teleport, damping))
+                .collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            (0..self.len())
+                .map(|row| self.score_row(row, prev_scores, teleport, damping))
+                .collect()
+        }
+    }
+}
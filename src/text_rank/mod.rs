@@ -15,38 +15,87 @@
 
 use std::collections::HashMap;
 
+use unicode_segmentation::UnicodeSegmentation;
+
+mod csr_graph;
 mod text_rank_logic;
 pub mod text_rank_params;
-use text_rank_logic::TextRankLogic;
-pub use text_rank_params::TextRankParams;
+mod topical_text_rank;
+pub use text_rank_logic::ConvergenceReport;
+pub(crate) use text_rank_logic::Graph;
+pub(crate) use text_rank_logic::TextRankLogic;
+pub use text_rank_params::{EdgeWeighting, TextRankOptions, TextRankParams, TopicalTextRankParams};
+pub use topical_text_rank::TopicalTextRank;
 
+pub use crate::common::{GraphFormat, PhraseNormalization};
 use crate::{
-    common::{get_ranked_scores, get_ranked_strings},
-    tokenizer::Tokenizer,
+    common::{deduplicate_ranked_map, export_graph, get_ranked_scores, get_ranked_strings},
+    tokenizer::{SurfaceForms, Tokenize, Tokenizer},
 };
 
 pub struct TextRank {
+    graph: Graph,
     word_rank: HashMap<String, f32>,
     phrase_rank: HashMap<String, f32>,
+    convergence_report: ConvergenceReport,
+    sentences: Vec<(String, String)>,
+    surface_forms: SurfaceForms,
+    damping: f32,
+    tol: f32,
 }
 
 impl TextRank {
     /// Create a new TextRank instance.
     pub fn new(params: TextRankParams) -> Self {
-        let (text, stop_words, punctuation, window_size, damping, tol, phrase_length) =
-            params.get_params();
+        Self::with_options(params, TextRankOptions::default())
+    }
+
+    /// Create a new TextRank instance with the given ranking options.
+    pub fn with_options(params: TextRankParams, options: TextRankOptions) -> Self {
+        let (text, stop_words, punctuation, ..) = params.get_params();
         let tokenizer = Tokenizer::new(text, stop_words, punctuation);
-        let (word_rank, phrase_rank) = TextRankLogic::build_text_rank(
-            tokenizer.sync_split_into_words(),
-            tokenizer.sync_split_into_phrases(phrase_length),
+        Self::with_tokenizer_and_options(params, &tokenizer, options)
+    }
+
+    /// Create a new TextRank instance splitting the text with a custom tokenizer, the stop words and
+    /// punctuation of the parameters are left to it. The ranked sentences are matched with the ones of
+    /// the tokenizer in order.
+    pub fn with_tokenizer<T: Tokenize>(params: TextRankParams, tokenizer: &T) -> Self {
+        Self::with_tokenizer_and_options(params, tokenizer, TextRankOptions::default())
+    }
+
+    /// Create a new TextRank instance splitting the text with a custom tokenizer, with the given
+    /// ranking options, see `TextRank::with_tokenizer`.
+    pub fn with_tokenizer_and_options<T: Tokenize>(
+        params: TextRankParams,
+        tokenizer: &T,
+        options: TextRankOptions,
+    ) -> Self {
+        let (text, _, _, window_size, damping, tol, phrase_length) = params.get_params();
+        let (graph, word_rank, phrase_rank, convergence_report) = TextRankLogic::build_text_rank(
+            tokenizer.words(),
+            tokenizer.phrases(phrase_length),
             window_size,
             damping,
             tol,
+            options.bias,
+            options.edge_weighting,
         );
+        let sentences = text
+            .unicode_sentences()
+            .map(|s| s.trim().to_string())
+            .zip(tokenizer.sentences())
+            .collect::<Vec<(String, String)>>();
 
         Self {
+            graph,
             word_rank,
             phrase_rank,
+            convergence_report,
+            sentences,
+            surface_forms: tokenizer.surface_forms(),
+            damping,
+            tol,
         }
     }
 
@@ -80,6 +129,47 @@ impl TextRank {
         get_ranked_scores(&self.phrase_rank, n)
     }
 
+    /// Get the top n phrases with the highest score and their score, written in the most frequent
+    /// form of their words in the text, e.g. "NASA" instead of "nasa".
+    pub fn get_ranked_surface_phrase_scores(&self, n: usize) -> Vec<(String, f32)> {
+        self.surface_forms
+            .apply(get_ranked_scores(&self.phrase_rank, n))
+    }
+
+    /// Gets the most frequent form of every word in the text.
+    pub fn get_surface_forms(&self) -> &SurfaceForms {
+        &self.surface_forms
+    }
+
+    /// Gets the top n phrases with the highest score, merging phrases with the same normalized form
+    /// and keeping the highest scoring one.
+    pub fn get_ranked_unique_phrases(
+        &self,
+        n: usize,
+        normalization: PhraseNormalization,
+    ) -> Vec<String> {
+        get_ranked_strings(&deduplicate_ranked_map(&self.phrase_rank, normalization), n)
+    }
+
+    /// Gets the top n phrases with the highest score and their score, merging phrases with the same
+    /// normalized form and keeping the highest scoring one.
+    pub fn get_ranked_unique_phrase_scores(
+        &self,
+        n: usize,
+        normalization: PhraseNormalization,
+    ) -> Vec<(String, f32)> {
+        get_ranked_scores(&deduplicate_ranked_map(&self.phrase_rank, normalization), n)
+    }
+
+    /// Ranks the sentences of the text by running PageRank over a sentence similarity graph, and gets
+    /// the top n sentences, as written in the original text, with their score.
+    pub fn rank_sentences(&self, n: usize) -> Vec<(String, f32)> {
+        get_ranked_scores(
+            &TextRankLogic::build_sentence_rank(&self.sentences, self.damping, self.tol),
+            n,
+        )
+    }
+
     /// Gets the word scores map.
     pub fn get_word_scores_map(&self) -> &HashMap<String, f32> {
         &self.word_rank
@@ -89,4 +179,54 @@ impl TextRank {
     pub fn get_phrase_scores_map(&self) -> &HashMap<String, f32> {
         &self.phrase_rank
     }
+
+    /// Gets the report of how the PageRank power iteration converged.
+    pub fn get_convergence_report(&self) -> &ConvergenceReport {
+        &self.convergence_report
+    }
+
+    /// Gets the word graph, mapping each word to its neighbours and the weight of the edge between them.
+    pub fn get_graph(&self) -> &HashMap<String, HashMap<String, f32>> {
+        &self.graph
+    }
+
+    /// Gets the neighbours of a word and the weight of the edges to them.
+    pub fn get_neighbors(&self, word: &str) -> Option<&HashMap<String, f32>> {
+        self.graph.get(word)
+    }
+
+    /// Gets the weight of the edge between two words, 0.0 if both are in the graph but never co-occur.
+    pub fn get_edge_weight(&self, word1: &str, word2: &str) -> Option<f32> {
+        if !self.graph.contains_key(word2) {
+            return None;
+        }
+
+        self.graph
+            .get(word1)
+            .map(|edges| *edges.get(word2).unwrap_or(&0.0))
+    }
+
+    /// Exports the word graph, with its edge weights and the final word scores, to the given format.
+    pub fn export_graph(&self, format: GraphFormat) -> String {
+        let mut nodes = self
+            .word_rank
+            .iter()
+            .map(|(word, score)| (word.to_string(), *score))
+            .collect::<Vec<(String, f32)>>();
+        nodes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut edges = self
+            .graph
+            .iter()
+            .flat_map(|(word1, edges)| {
+                edges
+                    .iter()
+                    .filter(move |(word2, _)| word1 < *word2)
+                    .map(move |(word2, weight)| (word1.to_string(), word2.to_string(), *weight))
+            })
+            .collect::<Vec<(String, String, f32)>>();
+        edges.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+        export_graph("text_rank", &nodes, &edges, format, false)
+    }
 }
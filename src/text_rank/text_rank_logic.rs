@@ -13,11 +13,29 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+use super::{csr_graph::CsrGraph, text_rank_params::EdgeWeighting};
+
+pub type Graph = HashMap<String, HashMap<String, f32>>;
+
+/// The maximum number of power iterations before giving up on convergence.
+const MAX_ITERATIONS: usize = 1_000;
+
+/// Summary of how the PageRank power iteration behaved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConvergenceReport {
+    /// The number of iterations that were run.
+    pub iterations: usize,
+    /// The largest score difference between the last two iterations.
+    pub final_delta: f32,
+    /// Whether the final delta fell below the tolerance before reaching the iteration limit.
+    pub converged: bool,
+}
+
 pub struct TextRankLogic;
 
 fn score_phrase(phrase: &str, word_rank: &HashMap<String, f32>) -> (String, f32) {
@@ -30,23 +48,25 @@ fn score_phrase(phrase: &str, word_rank: &HashMap<String, f32>) -> (String, f32)
     (phrase.to_string(), score / words.len() as f32)
 }
 
-fn score_word(
-    edges: &HashMap<String, f32>,
-    node_indexes: &HashMap<String, usize>,
-    outgoing_weight_sums: &HashMap<String, f32>,
-    prev_scores: &[f32],
-    damping: f32,
-) -> f32 {
-    let new_score = edges
-        .iter()
-        .map(|(neighbor, weight)| {
-            let neighbor_index = node_indexes[neighbor];
-            let neighbor_outgoing_sum = outgoing_weight_sums[neighbor];
-            weight / neighbor_outgoing_sum * prev_scores[neighbor_index]
-        })
+#[cfg(any(feature = "position_rank", feature = "single_rank"))]
+fn sum_phrase_score(phrase: &str, word_rank: &HashMap<String, f32>) -> (String, f32) {
+    let score = phrase
+        .split_whitespace()
+        .filter_map(|word| word_rank.get(word))
         .sum::<f32>();
 
-    (1.0 - damping) + damping * new_score
+    (phrase.to_string(), score)
+}
+
+fn sentence_similarity(sentence1: &HashSet<&str>, sentence2: &HashSet<&str>) -> f32 {
+    let overlap = sentence1.intersection(sentence2).count() as f32;
+    let normalization = (sentence1.len() as f32).ln() + (sentence2.len() as f32).ln();
+
+    if normalization > 0.0 {
+        overlap / normalization
+    } else {
+        overlap
+    }
 }
 
 fn get_node_indexes(nodes: &[&String]) -> HashMap<String, usize> {
@@ -69,53 +89,45 @@ fn get_node_indexes(nodes: &[&String]) -> HashMap<String, usize> {
     }
 }
 
-fn get_scores(
-    graph: &HashMap<String, HashMap<String, f32>>,
+fn get_teleport_vector(
+    nodes: &[&String],
     node_indexes: &HashMap<String, usize>,
-    outgoing_weight_sums: &HashMap<String, f32>,
-    prev_scores: &[f32],
-    damping: f32,
+    bias: Option<&HashMap<String, f32>>,
 ) -> Vec<f32> {
-    #[cfg(feature = "parallel")]
-    {
-        graph
-            .par_iter()
-            .map(|(_, edges)| {
-                score_word(
-                    edges,
-                    &node_indexes,
-                    &outgoing_weight_sums,
-                    &prev_scores,
-                    damping,
-                )
-            })
-            .collect()
-    }
+    let n = nodes.len();
+    let mut teleport = vec![1.0_f32; n];
+    let bias = match bias {
+        Some(b) => b
+            .iter()
+            .filter(|(_, weight)| **weight > 0.0)
+            .map(|(word, weight)| (word.to_lowercase(), *weight))
+            .collect::<HashMap<String, f32>>(),
+        None => return teleport,
+    };
+    let total = nodes
+        .iter()
+        .filter_map(|node| bias.get(node.as_str()))
+        .sum::<f32>();
 
-    #[cfg(not(feature = "parallel"))]
-    {
-        graph
-            .values()
-            .map(|edges| {
-                score_word(
-                    edges,
-                    &node_indexes,
-                    &outgoing_weight_sums,
-                    &prev_scores,
-                    damping,
-                )
-            })
-            .collect()
+    if total <= 0.0 {
+        return teleport;
     }
+
+    nodes.iter().for_each(|node| {
+        let weight = bias.get(node.as_str()).unwrap_or(&0.0);
+        teleport[node_indexes[node.as_str()]] = weight / total * n as f32;
+    });
+    teleport
 }
 
-fn check_tolorance(scores: &[f32], prev_scores: &[f32], tol: f32) -> bool {
+fn get_max_delta(scores: &[f32], prev_scores: &[f32]) -> f32 {
     #[cfg(feature = "parallel")]
     {
-        scores.par_iter().enumerate().all(|(i, score)| {
-            let prev_score = prev_scores[i];
-            (score - prev_score).abs() < tol
-        })
+        scores
+            .par_iter()
+            .zip(prev_scores.par_iter())
+            .map(|(score, prev_score)| (score - prev_score).abs())
+            .reduce(|| 0.0_f32, f32::max)
     }
 
     #[cfg(not(feature = "parallel"))]
@@ -123,7 +135,8 @@ fn check_tolorance(scores: &[f32], prev_scores: &[f32], tol: f32) -> bool {
         scores
             .iter()
             .zip(prev_scores.iter())
-            .all(|(score, prev_score)| (score - prev_score).abs() < tol)
+            .map(|(score, prev_score)| (score - prev_score).abs())
+            .fold(0.0_f32, f32::max)
     }
 }
 
@@ -134,26 +147,132 @@ impl TextRankLogic {
         window_size: usize,
         damping: f32,
         tol: f32,
+        bias: Option<&HashMap<String, f32>>,
+        edge_weighting: EdgeWeighting,
+    ) -> (
+        Graph,
+        HashMap<String, f32>,
+        HashMap<String, f32>,
+        ConvergenceReport,
+    ) {
+        let graph = Self::create_graph(words, window_size, edge_weighting);
+        let (word_rank, report) = Self::rank_graph(&graph, damping, tol, bias);
+        let phrase_rank = Self::rank_phrases(phrases, &word_rank);
+        (graph, word_rank, phrase_rank, report)
+    }
+
+    /// Runs one PageRank per topic, biased by the probability of every word in the topic, and
+    /// combines the scores weighted by the topic weights (uniform when not given).
+    pub fn build_topical_text_rank(
+        words: Vec<String>,
+        phrases: Vec<String>,
+        window_size: usize,
+        damping: f32,
+        tol: f32,
+        word_topics: &HashMap<String, Vec<f32>>,
+        topic_weights: Option<&[f32]>,
     ) -> (HashMap<String, f32>, HashMap<String, f32>) {
-        let word_rank =
-            Self::create_word_rank(Self::create_graph(words, window_size), damping, tol);
+        let graph = Self::create_graph(words, window_size, EdgeWeighting::Uniform);
+        let topics_len = word_topics.values().map(|p| p.len()).max().unwrap_or(0);
+        let topic_weights = match topic_weights {
+            Some(weights) => {
+                let total = weights.iter().take(topics_len).sum::<f32>();
+                (0..topics_len)
+                    .map(|t| weights.get(t).map_or(0.0, |w| w / total))
+                    .collect::<Vec<f32>>()
+            }
+            None => vec![1.0 / topics_len as f32; topics_len],
+        };
+        let mut word_rank = graph
+            .keys()
+            .map(|word| (word.to_string(), 0.0_f32))
+            .collect::<HashMap<String, f32>>();
+
+        topic_weights
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| **weight > 0.0)
+            .for_each(|(t, weight)| {
+                let bias = word_topics
+                    .iter()
+                    .filter_map(|(word, p)| p.get(t).map(|p| (word.to_string(), *p)))
+                    .collect::<HashMap<String, f32>>();
+                let (topic_rank, _) = Self::rank_graph(&graph, damping, tol, Some(&bias));
+                topic_rank.into_iter().for_each(|(word, score)| {
+                    if let Some(total) = word_rank.get_mut(&word) {
+                        *total += weight * score;
+                    }
+                });
+            });
+
+        if topics_len == 0 {
+            word_rank = Self::rank_graph(&graph, damping, tol, None).0;
+        }
+
         let phrase_rank = Self::rank_phrases(phrases, &word_rank);
         (word_rank, phrase_rank)
     }
 
-    fn add_edge(graph: &mut HashMap<String, HashMap<String, f32>>, word1: &str, word2: &str) {
+    pub fn build_sentence_rank(
+        sentences: &[(String, String)],
+        damping: f32,
+        tol: f32,
+    ) -> HashMap<String, f32> {
+        let (word_rank, _) =
+            Self::rank_graph(&Self::create_sentence_graph(sentences), damping, tol, None);
+        word_rank
+    }
+
+    fn create_sentence_graph(sentences: &[(String, String)]) -> Graph {
+        let sentence_words = sentences
+            .iter()
+            .filter(|(_, processed)| !processed.is_empty())
+            .map(|(sentence, processed)| {
+                (
+                    sentence,
+                    processed.split_whitespace().collect::<HashSet<&str>>(),
+                )
+            })
+            .collect::<Vec<(&String, HashSet<&str>)>>();
+        let mut graph = sentence_words
+            .iter()
+            .map(|(sentence, _)| (sentence.to_string(), HashMap::new()))
+            .collect::<Graph>();
+
+        sentence_words
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (sentence1, words1))| {
+                sentence_words[i + 1..]
+                    .iter()
+                    .filter(move |(sentence2, _)| sentence1 != sentence2)
+                    .map(move |(sentence2, words2)| {
+                        (sentence1, sentence2, sentence_similarity(words1, words2))
+                    })
+            })
+            .filter(|(_, _, weight)| *weight > 0.0)
+            .for_each(|(sentence1, sentence2, weight)| {
+                Self::add_edge(&mut graph, sentence1, sentence2, weight);
+                Self::add_edge(&mut graph, sentence2, sentence1, weight);
+            });
+
+        graph
+    }
+
+    fn add_edge(graph: &mut Graph, word1: &str, word2: &str, weight: f32) {
         graph
             .entry(word1.to_string())
-            .or_insert_with(HashMap::new)
+            .or_default()
             .entry(word2.to_string())
-            .and_modify(|e| *e += 1.0)
-            .or_insert(1.0);
+            .and_modify(|e| *e += weight)
+            .or_insert(weight);
     }
 
     fn create_graph(
         words: Vec<String>,
         window_size: usize,
-    ) -> HashMap<String, HashMap<String, f32>> {
+        edge_weighting: EdgeWeighting,
+    ) -> Graph {
         let mut graph = HashMap::new();
 
         words
@@ -163,87 +282,96 @@ impl TextRankLogic {
                 words[i + 1..]
                     .iter()
                     .take(window_size)
-                    .filter(|word2| word1.as_str() != word2.as_str())
-                    .map(move |word2| (word1, word2))
+                    .enumerate()
+                    .filter(|(_, word2)| word1.as_str() != word2.as_str())
+                    .map(move |(j, word2)| (word1, word2, edge_weighting.get_weight(j + 1)))
             })
-            .for_each(|(word1, word2)| {
-                Self::add_edge(&mut graph, word1, word2);
-                Self::add_edge(&mut graph, word2, word1);
+            .filter(|(_, _, weight)| *weight > 0.0)
+            .for_each(|(word1, word2, weight)| {
+                Self::add_edge(&mut graph, word1, word2, weight);
+                Self::add_edge(&mut graph, word2, word1, weight);
             });
 
         graph
     }
 
-    fn get_outgoing_weight_sum(
-        graph: &HashMap<String, HashMap<String, f32>>,
-    ) -> HashMap<String, f32> {
-        #[cfg(feature = "parallel")]
-        {
-            graph
-                .par_iter()
-                .map(|(node, edges)| {
-                    let outgoing_weight_sum = edges.values().sum();
-                    (node.to_string(), outgoing_weight_sum)
-                })
-                .collect()
-        }
-
-        #[cfg(not(feature = "parallel"))]
-        {
-            graph
-                .iter()
-                .map(|(node, edges)| {
-                    let outgoing_weight_sum = edges.values().sum();
-                    (node.to_string(), outgoing_weight_sum)
-                })
-                .collect()
-        }
-    }
-
-    fn create_word_rank(
-        graph: HashMap<String, HashMap<String, f32>>,
+    pub fn rank_graph(
+        graph: &Graph,
         damping: f32,
         tol: f32,
-    ) -> HashMap<String, f32> {
+        bias: Option<&HashMap<String, f32>>,
+    ) -> (HashMap<String, f32>, ConvergenceReport) {
         let nodes = graph.keys().collect::<Vec<&String>>();
         let n = nodes.len();
         let node_indexes = get_node_indexes(&nodes);
+        let teleport = get_teleport_vector(&nodes, &node_indexes, bias);
         let mut scores = vec![1.0_f32; n];
-        let outgoing_weight_sums = Self::get_outgoing_weight_sum(&graph);
+        let csr_graph = CsrGraph::new(graph, &nodes, &node_indexes);
+        let mut report = ConvergenceReport {
+            iterations: 0,
+            final_delta: 0.0,
+            converged: false,
+        };
 
-        loop {
+        while report.iterations < MAX_ITERATIONS {
             let prev_scores = scores.to_owned();
-            scores = get_scores(
-                &graph,
-                &node_indexes,
-                &outgoing_weight_sums,
-                &prev_scores,
-                damping,
-            );
-
-            if check_tolorance(&scores, &prev_scores, tol) {
+            scores = csr_graph.get_scores(&prev_scores, &teleport, damping);
+            report.iterations += 1;
+            report.final_delta = get_max_delta(&scores, &prev_scores);
+
+            if report.final_delta < tol {
+                report.converged = true;
                 break;
             }
         }
 
         #[cfg(feature = "parallel")]
         {
-            nodes
+            (
+                nodes
+                    .par_iter()
+                    .map(|&node| (node.to_string(), scores[node_indexes[node]]))
+                    .collect::<HashMap<String, f32>>(),
+                report,
+            )
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            (
+                nodes
+                    .iter()
+                    .map(|&node| (node.to_string(), scores[node_indexes[node]]))
+                    .collect::<HashMap<String, f32>>(),
+                report,
+            )
+        }
+    }
+
+    fn rank_phrases(
+        phrases: Vec<String>,
+        word_scores: &HashMap<String, f32>,
+    ) -> HashMap<String, f32> {
+        #[cfg(feature = "parallel")]
+        {
+            phrases
                 .par_iter()
-                .map(|&node| (node.to_string(), scores[node_indexes[node]]))
+                .map(|phrase| score_phrase(phrase, word_scores))
                 .collect::<HashMap<String, f32>>()
         }
 
         #[cfg(not(feature = "parallel"))]
         {
-            nodes
+            phrases
                 .iter()
-                .map(|&node| (node.to_string(), scores[node_indexes[node]]))
+                .map(|phrase| score_phrase(phrase, word_scores))
                 .collect::<HashMap<String, f32>>()
         }
     }
 
-    fn rank_phrases(
+    /// Scores every phrase with the sum, instead of the average, of its word scores.
+    #[cfg(any(feature = "position_rank", feature = "single_rank"))]
+    pub fn rank_phrases_by_sum(
         phrases: Vec<String>,
         word_scores: &HashMap<String, f32>,
     ) -> HashMap<String, f32> {
@@ -251,7 +379,7 @@ impl TextRankLogic {
         {
             phrases
                 .par_iter()
-                .map(|phrase| score_phrase(phrase, word_scores))
+                .map(|phrase| sum_phrase_score(phrase, word_scores))
                 .collect::<HashMap<String, f32>>()
         }
 
@@ -259,7 +387,7 @@ impl TextRankLogic {
         {
             phrases
                 .iter()
-                .map(|phrase| score_phrase(phrase, word_scores))
+                .map(|phrase| sum_phrase_score(phrase, word_scores))
                 .collect::<HashMap<String, f32>>()
         }
     }
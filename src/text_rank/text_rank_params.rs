@@ -13,10 +13,48 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
+
 use crate::common::{PhraseLength, Punctuation, Stopwords, Text, WindowSize};
 
 type DampingFactor = f32;
 type Tolerance = f32;
+type WordTopics<'a> = &'a HashMap<String, Vec<f32>>;
+type TopicWeights<'a> = Option<&'a [f32]>;
+
+/// The weight given to an edge between two words that co-occur inside the window.
+#[derive(Clone, Copy, Default)]
+pub enum EdgeWeighting {
+    /// Every co-occurrence adds 1.0 to the edge weight.
+    #[default]
+    Uniform,
+    /// Every co-occurrence adds 1/distance to the edge weight, where distance is the number of words between both plus one.
+    InverseDistance,
+    /// Every co-occurrence adds the result of the given decay function applied to the distance.
+    Custom(fn(usize) -> f32),
+}
+
+impl EdgeWeighting {
+    /// Returns the weight of a co-occurrence at the given distance.
+    pub fn get_weight(&self, distance: usize) -> f32 {
+        match self {
+            EdgeWeighting::Uniform => 1.0,
+            EdgeWeighting::InverseDistance => 1.0 / distance as f32,
+            EdgeWeighting::Custom(decay) => decay(distance),
+        }
+    }
+}
+
+/// The options of the TextRank algorithm on top of its parameters, see `TextRank::with_options`.
+#[derive(Clone, Copy, Default)]
+pub struct TextRankOptions<'a> {
+    /// Optional map of seed words to weights, used as the teleport distribution of the PageRank
+    /// instead of the uniform one, defaults to none.
+    pub bias: Option<&'a HashMap<String, f32>>,
+    /// How co-occurrences inside the window are weighted in the graph, defaults to
+    /// `EdgeWeighting::Uniform`.
+    pub edge_weighting: EdgeWeighting,
+}
 
 /// The parameters to be used in the TextRank algorithm.
 pub enum TextRankParams<'a> {
@@ -65,9 +103,9 @@ impl<'a> TextRankParams<'a> {
     pub fn get_params(
         &self,
     ) -> (
-        Text,
-        Stopwords,
-        Punctuation,
+        Text<'_>,
+        Stopwords<'_>,
+        Punctuation<'_>,
         WindowSize,
         DampingFactor,
         Tolerance,
@@ -100,3 +138,93 @@ impl<'a> TextRankParams<'a> {
         }
     }
 }
+
+/// The parameters to be used in the Topical TextRank (Topical PageRank) algorithm.
+pub enum TopicalTextRankParams<'a> {
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `word_topics` - Map of words to their probability in every topic, e.g. from an LDA model.
+    /// 4. `topic_weights` - Optional weight of every topic in the text, used to combine the topic scores.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `window_size` - The size of the window to be used in the graph, defaults to 2.
+    /// * `damping_factor` - The damping factor to be used in the graph, defaults to 0.85.
+    /// * `tolerance` - The minimum difference between iterations to stop the algorithm, defaults to 0.00005.
+    /// * `topic_weights` - Defaults to the same weight for every topic when not given.
+    WithDefaults(Text<'a>, Stopwords<'a>, WordTopics<'a>, TopicWeights<'a>),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `punctuation` - Optional list of punctuation symbols.
+    /// 4. `window_size` - The size of the window to be used in the graph.
+    /// 5. `damping_factor` - The damping factor to be used in the graph.
+    /// 6. `tolerance` - The minimum difference between iterations to stop the algorithm.
+    /// 7. `phrase_length` - Optional maximum length of the phrases to be ranked.
+    /// 8. `word_topics` - Map of words to their probability in every topic, e.g. from an LDA model.
+    /// 9. `topic_weights` - Optional weight of every topic in the text, used to combine the topic scores.
+    All(
+        Text<'a>,
+        Stopwords<'a>,
+        Punctuation<'a>,
+        WindowSize,
+        DampingFactor,
+        Tolerance,
+        PhraseLength,
+        WordTopics<'a>,
+        TopicWeights<'a>,
+    ),
+}
+
+impl<'a> TopicalTextRankParams<'a> {
+    /// Returns the params to be used in the Topical TextRank algorithm.
+    pub fn get_params(
+        &self,
+    ) -> (
+        Text<'_>,
+        Stopwords<'_>,
+        Punctuation<'_>,
+        WindowSize,
+        DampingFactor,
+        Tolerance,
+        PhraseLength,
+        WordTopics<'_>,
+        TopicWeights<'_>,
+    ) {
+        match self {
+            TopicalTextRankParams::WithDefaults(text, stop_words, word_topics, topic_weights) => (
+                text,
+                stop_words,
+                None,
+                2,
+                0.85,
+                0.00005,
+                None,
+                word_topics,
+                *topic_weights,
+            ),
+            TopicalTextRankParams::All(
+                text,
+                stop_words,
+                punctuation,
+                window_size,
+                damping_factor,
+                min_diff,
+                phrase_length,
+                word_topics,
+                topic_weights,
+            ) => (
+                text,
+                stop_words,
+                *punctuation,
+                *window_size,
+                *damping_factor,
+                *min_diff,
+                *phrase_length,
+                word_topics,
+                *topic_weights,
+            ),
+        }
+    }
+}
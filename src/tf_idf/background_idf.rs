@@ -0,0 +1,89 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Precomputed IDF weights of a background corpus (e.g. Wikipedia), to extract keywords of a single
+/// document with TF-IDF. Words missing from the background corpus get the highest weight, as the
+/// rarest ones.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BackgroundIdf {
+    weights: HashMap<String, f32>,
+    default_weight: f32,
+}
+
+impl BackgroundIdf {
+    /// Creates the background IDF from its weights.
+    pub fn new(weights: HashMap<String, f32>) -> Self {
+        let default_weight = weights.values().fold(0.0_f32, |max, w| max.max(*w));
+
+        Self {
+            weights,
+            default_weight,
+        }
+    }
+
+    /// Reads the weights from lines of a word (or phrase) followed by its weight, separated by
+    /// whitespace. Empty lines and lines starting with `#` are ignored.
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut weights = HashMap::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (word, weight) = line
+                .rsplit_once(char::is_whitespace)
+                .and_then(|(word, weight)| {
+                    weight
+                        .parse::<f32>()
+                        .ok()
+                        .map(|weight| (word.trim().to_lowercase(), weight))
+                })
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid background IDF line: {}", line),
+                    )
+                })?;
+            weights.insert(word, weight);
+        }
+
+        Ok(Self::new(weights))
+    }
+
+    /// Reads the weights from a file, see `BackgroundIdf::from_reader` for the format.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Gets the weight of a word, the highest weight if it is not in the background corpus.
+    pub fn get_weight(&self, word: &str) -> f32 {
+        *self.weights.get(word).unwrap_or(&self.default_weight)
+    }
+}
@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashSet;
+use std::{collections::HashSet, io};
 
 use regex::Regex;
 use unicode_segmentation::UnicodeSegmentation;
@@ -21,46 +21,73 @@ use unicode_segmentation::UnicodeSegmentation;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use crate::common::{get_special_char_regex, process_word, PUNCTUATION};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-pub struct DocumentProcessor<'a> {
-    documents: &'a [String],
-    stopwords: HashSet<String>,
+use crate::{
+    common::{
+        get_special_char_regex, process_word, Punctuation, StopwordSet, Stopwords, WordNormalizer,
+        PUNCTUATION,
+    },
+    tokenizer::Tokenizer,
+};
+
+use super::TermUnit;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DocumentProcessor {
+    stopwords: StopwordSet,
     punctuation: HashSet<String>,
+    ngram_range: (usize, usize),
+    term_unit: TermUnit,
+    hashing_buckets: Option<usize>,
+    normalizer: WordNormalizer,
 }
 
-impl<'a> DocumentProcessor<'a> {
-    pub fn new(
-        documents: &'a [String],
-        stopwords: &'a [String],
-        punctuation: &'a Option<&'a [String]>,
-    ) -> Self {
+/// The 64-bit FNV-1a hash, stable across processes unlike the standard library hasher.
+fn fnv1a_hash(term: &str) -> u64 {
+    term.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+impl DocumentProcessor {
+    pub fn new(stopwords: Stopwords, punctuation: Punctuation) -> Self {
+        Self::from_stopword_set(&StopwordSet::new(stopwords), punctuation)
+    }
+
+    /// Creates a processor sharing a set of stopwords built once.
+    pub fn from_stopword_set(stopwords: &StopwordSet, punctuation: Punctuation) -> Self {
         Self {
-            documents,
-            stopwords: stopwords
-                .iter()
-                .map(|s| s.to_owned())
-                .collect::<HashSet<String>>(),
-            punctuation: punctuation
-                .unwrap_or(
-                    &PUNCTUATION
-                        .iter()
-                        .map(|s| s.to_string())
-                        .collect::<Vec<String>>(),
-                )
-                .iter()
-                .map(|s| s.to_string())
-                .collect::<HashSet<String>>(),
+            ngram_range: (1, 1),
+            term_unit: TermUnit::Words,
+            hashing_buckets: None,
+            normalizer: WordNormalizer::default(),
+            stopwords: stopwords.clone(),
+            punctuation: match punctuation {
+                Some(punctuation) => punctuation.iter().map(|s| s.to_string()).collect(),
+                None => PUNCTUATION.iter().map(|s| s.to_string()).collect(),
+            },
         }
     }
 
     fn process_document(&self, document: &str, special_char_regex: &Regex) -> String {
-        document
+        self.normalizer
+            .normalize_text(document)
             .unicode_sentences()
             .map(|s| {
-                s.split_word_bounds()
+                self.normalizer
+                    .split_words(s)
+                    .into_iter()
                     .filter_map(|w| {
-                        process_word(w, special_char_regex, &self.stopwords, &self.punctuation)
+                        process_word(
+                            w,
+                            special_char_regex,
+                            &self.stopwords,
+                            &self.punctuation,
+                            &self.normalizer,
+                        )
                     })
                     .collect::<Vec<String>>()
                     .join(" ")
@@ -69,23 +96,220 @@ impl<'a> DocumentProcessor<'a> {
             .join(" ")
     }
 
-    pub fn process_documents(&self) -> Vec<String> {
+    /// Sets the minimum and maximum number of words of the terms, n-grams never span stop words or
+    /// punctuation.
+    pub fn with_ngram_range(mut self, (min, max): (usize, usize)) -> Self {
+        let min = min.max(1);
+        self.ngram_range = (min, max.max(min));
+        self
+    }
+
+    /// Sets whether the terms are words (and their n-grams) or the phrases of the tokenizer.
+    pub fn with_term_unit(mut self, term_unit: TermUnit) -> Self {
+        self.term_unit = term_unit;
+        self
+    }
+
+    /// Sets how the words are normalized after being lowercased.
+    pub fn with_normalizer(mut self, normalizer: WordNormalizer) -> Self {
+        if normalizer.is_text_normalized() {
+            self.stopwords.normalize(&normalizer);
+        }
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Gets a tokenizer of a text with the same stop words, punctuation and normalization.
+    pub fn get_tokenizer(&self, text: &str) -> Tokenizer {
+        Tokenizer::from_stopword_set(
+            text,
+            &self.stopwords,
+            Some(&self.punctuation.iter().cloned().collect::<Vec<String>>()),
+        )
+        .with_normalizer(self.normalizer.clone())
+    }
+
+    pub fn get_term_unit(&self) -> TermUnit {
+        self.term_unit
+    }
+
+    fn split_document_into_terms(&self, document: &str, special_char_regex: &Regex) -> Vec<String> {
+        if self.term_unit == TermUnit::Phrases {
+            return self
+                .get_tokenizer(document)
+                .sync_split_into_phrases(None)
+                .into_iter()
+                .map(|phrase| self.get_term_key(&phrase))
+                .collect();
+        }
+
+        self.normalizer
+            .normalize_text(document)
+            .unicode_sentences()
+            .flat_map(|s| {
+                self.normalizer
+                    .split_words(s)
+                    .into_iter()
+                    .filter(|w| !w.trim().is_empty())
+                    .fold(vec![Vec::<String>::new()], |mut runs, w| {
+                        match process_word(
+                            w,
+                            special_char_regex,
+                            &self.stopwords,
+                            &self.punctuation,
+                            &self.normalizer,
+                        ) {
+                            Some(word) => runs.last_mut().unwrap().push(word),
+                            None => runs.push(Vec::new()),
+                        }
+                        runs
+                    })
+            })
+            .flat_map(|run| self.generate_ngrams(&run))
+            .collect()
+    }
+
+    fn generate_ngrams(&self, words: &[String]) -> Vec<String> {
+        let (min, max) = self.ngram_range;
+
+        (min..=max.min(words.len()))
+            .flat_map(|n| {
+                words
+                    .windows(n)
+                    .map(|ngram| self.get_term_key(&ngram.join(" ")))
+            })
+            .collect()
+    }
+
+    /// Sets the number of buckets the terms are hashed into, instead of keeping every term.
+    pub fn with_hashing_buckets(mut self, buckets: Option<usize>) -> Self {
+        self.hashing_buckets = buckets.map(|buckets| buckets.max(1));
+        self
+    }
+
+    /// Gets the key a term is counted under, its bucket number when hashing.
+    pub fn get_term_key(&self, term: &str) -> String {
+        match self.hashing_buckets {
+            Some(buckets) => (fnv1a_hash(term) % buckets as u64).to_string(),
+            None => term.to_string(),
+        }
+    }
+
+    /// Whether the terms are hashed into buckets.
+    pub fn is_hashing(&self) -> bool {
+        self.hashing_buckets.is_some()
+    }
+
+    /// Splits an unprocessed document into its terms.
+    pub fn split_into_terms(&self, document: &str) -> Vec<String> {
+        self.split_document_into_terms(document, get_special_char_regex())
+    }
+
+    /// Splits already processed documents, whose words are separated by whitespace, into their terms.
+    pub fn split_processed_documents_into_terms(&self, documents: &[String]) -> Vec<Vec<String>> {
+        documents
+            .iter()
+            .map(|document| {
+                self.generate_ngrams(
+                    &document
+                        .split_whitespace()
+                        .map(|word| word.to_string())
+                        .collect::<Vec<String>>(),
+                )
+            })
+            .collect()
+    }
+
+    /// Turns already tokenized documents into their terms, without processing the tokens.
+    pub fn split_tokenized_documents_into_terms(
+        &self,
+        documents: &[Vec<&str>],
+    ) -> Vec<Vec<String>> {
+        documents
+            .iter()
+            .map(|tokens| {
+                self.generate_ngrams(
+                    &tokens
+                        .iter()
+                        .map(|token| token.to_string())
+                        .collect::<Vec<String>>(),
+                )
+            })
+            .collect()
+    }
+
+    /// Splits unprocessed documents into their terms.
+    pub fn split_documents_into_terms(&self, documents: &[String]) -> Vec<Vec<String>> {
         let special_char_regex = get_special_char_regex();
 
         #[cfg(feature = "parallel")]
         {
-            self.documents
+            documents
                 .par_iter()
-                .map(|doc| self.process_document(doc, &special_char_regex))
+                .map(|doc| self.split_document_into_terms(doc, special_char_regex))
+                .collect::<Vec<Vec<String>>>()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            documents
+                .iter()
+                .map(|doc| self.split_document_into_terms(doc, special_char_regex))
+                .collect::<Vec<Vec<String>>>()
+        }
+    }
+
+    pub fn process_documents(&self, documents: &[String]) -> Vec<String> {
+        let special_char_regex = get_special_char_regex();
+
+        #[cfg(feature = "parallel")]
+        {
+            documents
+                .par_iter()
+                .map(|doc| self.process_document(doc, special_char_regex))
                 .collect::<Vec<String>>()
         }
 
         #[cfg(not(feature = "parallel"))]
         {
-            self.documents
+            documents
                 .iter()
-                .map(|doc| self.process_document(doc, &special_char_regex))
+                .map(|doc| self.process_document(doc, special_char_regex))
                 .collect::<Vec<String>>()
         }
     }
+
+    /// Processes a stream of documents in batches of `batch_size`, in parallel within a batch with the
+    /// `parallel` feature, handing every processed batch to `consume` so only one batch is held in
+    /// memory. `process` is how a batch is processed, e.g. `DocumentProcessor::process_documents`.
+    pub fn process_documents_in_batches<I, T, P, C>(
+        &self,
+        documents: I,
+        batch_size: usize,
+        process: P,
+        mut consume: C,
+    ) -> io::Result<()>
+    where
+        I: IntoIterator<Item = io::Result<String>>,
+        P: Fn(&Self, &[String]) -> Vec<T>,
+        C: FnMut(Vec<T>),
+    {
+        let batch_size = batch_size.max(1);
+        let mut batch = Vec::with_capacity(batch_size);
+
+        for document in documents {
+            batch.push(document?);
+
+            if batch.len() == batch_size {
+                consume(process(self, &batch));
+                batch.clear();
+            }
+        }
+
+        if !batch.is_empty() {
+            consume(process(self, &batch));
+        }
+
+        Ok(())
+    }
 }
@@ -13,42 +13,522 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+    sync::OnceLock,
+};
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ndarray")]
+use ndarray::Array2;
+
+mod background_idf;
 mod document_processor;
 mod tf_idf_logic;
 pub mod tf_idf_params;
+pub use background_idf::BackgroundIdf;
+pub(crate) use document_processor::DocumentProcessor;
 use tf_idf_logic::TfIdfLogic;
-pub use tf_idf_params::{TextSplit, TfIdfParams};
+pub use tf_idf_params::{
+    DocumentFrequencyLimit, IdfWeighting, TermUnit, TextSplit, TfIdfOptions, TfIdfParams,
+    TfWeighting,
+};
 
-use crate::common::{get_ranked_scores, get_ranked_strings};
+use crate::{
+    common::{
+        get_ranked_scores, get_ranked_strings, sparse_cosine_similarity, Punctuation, Stopwords,
+    },
+    tokenizer::Tokenize,
+};
 
-pub struct TfIdf(HashMap<String, f32>);
+/// A sparse vector of `(word_id, weight)` pairs sorted by word id.
+pub type SparseVector = Vec<(usize, f32)>;
+
+/// The term-document matrix in compressed sparse row format, as the `(indptr, indices, data)` triple
+/// with a row per document and a column per word id.
+pub type CsrMatrix = (Vec<usize>, Vec<usize>, Vec<f32>);
+
+/// A summary of the corpus a TF-IDF model was fitted on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CorpusStats {
+    /// The number of analyzed documents.
+    pub total_documents: usize,
+    /// The number of terms kept after the document frequency limits.
+    pub vocabulary_size: usize,
+    /// The number of distinct terms seen, including the pruned ones.
+    pub distinct_terms: usize,
+    /// The number of term occurrences in every document.
+    pub total_terms: usize,
+    /// The mean number of term occurrences per document.
+    pub average_document_length: f32,
+}
+
+/// A fitted TF-IDF model, with the `serde` feature it can be saved and loaded to score new documents
+/// without refitting. Only the vocabulary and corpus counts are saved, so the per-document methods of
+/// a loaded model have no documents to work with.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TfIdf {
+    word_scores: HashMap<String, f32>,
+    idf: HashMap<String, f32>,
+    vocabulary: HashMap<String, usize>,
+    word_counts: HashMap<String, f32>,
+    document_frequencies: HashMap<String, f32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    document_word_counts: Vec<HashMap<String, f32>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    document_vectors: OnceLock<Vec<SparseVector>>,
+    total_documents: usize,
+    document_processor: DocumentProcessor,
+    options: TfIdfOptions,
+    background_idf: Option<BackgroundIdf>,
+    fixed_vocabulary: bool,
+}
 
 impl TfIdf {
     /// Creates a new TfIdf struct with the given parameters.
     pub fn new(params: TfIdfParams) -> Self {
-        let documents = params.get_documents();
-        Self(TfIdfLogic::build_tfidf(&documents))
+        Self::with_options(params, TfIdfOptions::default())
+    }
+
+    /// Creates a new TfIdf struct with the given parameters and fitting options.
+    pub fn with_options(params: TfIdfParams, options: TfIdfOptions) -> Self {
+        Self::fit_documents(params, options, None, None)
+    }
+
+    /// Creates a new TfIdf struct whose IDF weights come from a background corpus instead of the
+    /// analyzed documents, so the keywords of a single document can be extracted.
+    pub fn with_background_idf(
+        params: TfIdfParams,
+        options: TfIdfOptions,
+        background_idf: BackgroundIdf,
+    ) -> Self {
+        Self::fit_documents(params, options, Some(background_idf), None)
+    }
+
+    /// Creates a new TfIdf struct restricted to a fixed vocabulary, e.g. the one of a model fitted
+    /// before, so the word ids of the document vectors are shared between models. Words outside the
+    /// vocabulary are ignored.
+    pub fn with_vocabulary(
+        params: TfIdfParams,
+        options: TfIdfOptions,
+        vocabulary: HashMap<String, usize>,
+    ) -> Self {
+        Self::fit_documents(params, options, None, Some(vocabulary))
+    }
+
+    /// Creates a new TfIdf struct from documents split by a custom tokenizer each, using its words, or
+    /// its phrases with `TermUnit::Phrases`, as the tokens of `TfIdfParams::TokenizedDocuments`.
+    pub fn with_tokenizers<T: Tokenize>(tokenizers: &[T], options: TfIdfOptions) -> Self {
+        let documents = tokenizers
+            .iter()
+            .map(|tokenizer| match options.term_unit {
+                TermUnit::Words => tokenizer.words(),
+                TermUnit::Phrases => tokenizer.phrases(None),
+            })
+            .collect::<Vec<Vec<String>>>();
+        let tokens = documents
+            .iter()
+            .map(|document| document.iter().map(|token| token.as_str()).collect())
+            .collect::<Vec<Vec<&str>>>();
+
+        Self::with_options(TfIdfParams::TokenizedDocuments(&tokens), options)
+    }
+
+    fn fit_documents(
+        params: TfIdfParams,
+        options: TfIdfOptions,
+        background_idf: Option<BackgroundIdf>,
+        vocabulary: Option<HashMap<String, usize>>,
+    ) -> Self {
+        let document_processor = params.get_document_processor(&options);
+        let documents = params.get_document_terms(&document_processor);
+        let (word_counts, document_frequencies) = TfIdfLogic::count_words(&documents);
+        let fixed_vocabulary = vocabulary.is_some();
+
+        let mut tf_idf = Self {
+            word_scores: HashMap::new(),
+            idf: HashMap::new(),
+            vocabulary: vocabulary.unwrap_or_default(),
+            word_counts,
+            document_frequencies,
+            document_word_counts: TfIdfLogic::count_document_words(&documents),
+            document_vectors: OnceLock::new(),
+            total_documents: documents.len(),
+            document_processor,
+            options,
+            background_idf,
+            fixed_vocabulary,
+        };
+        tf_idf.fit();
+        tf_idf
+    }
+
+    /// Fits the model on a stream of unprocessed documents too large to be held in memory, processed in
+    /// batches of `TfIdfOptions::batch_size`. Only the corpus counts are kept, so the per-document
+    /// methods have no documents to work with. The document vectors can be written out in a second
+    /// pass with `TfIdf::write_document_vectors`.
+    pub fn from_stream<I>(
+        documents: I,
+        stop_words: Stopwords,
+        punctuation: Punctuation,
+        options: TfIdfOptions,
+    ) -> io::Result<Self>
+    where
+        I: IntoIterator<Item = io::Result<String>>,
+    {
+        let document_processor = options.get_document_processor(stop_words, punctuation);
+        let mut tf_idf = Self {
+            word_scores: HashMap::new(),
+            idf: HashMap::new(),
+            vocabulary: HashMap::new(),
+            word_counts: HashMap::new(),
+            document_frequencies: HashMap::new(),
+            document_word_counts: Vec::new(),
+            document_vectors: OnceLock::new(),
+            total_documents: 0,
+            document_processor,
+            options,
+            background_idf: None,
+            fixed_vocabulary: false,
+        };
+
+        tf_idf.document_processor.process_documents_in_batches(
+            documents,
+            options.batch_size,
+            DocumentProcessor::split_documents_into_terms,
+            |terms| {
+                let (word_counts, document_frequencies) = TfIdfLogic::count_words(&terms);
+                TfIdfLogic::merge_counts(&mut tf_idf.word_counts, word_counts);
+                TfIdfLogic::merge_counts(&mut tf_idf.document_frequencies, document_frequencies);
+                tf_idf.total_documents += terms.len();
+            },
+        )?;
+
+        tf_idf.fit();
+        Ok(tf_idf)
+    }
+
+    /// Fits the model on a reader with one unprocessed document per line, see `TfIdf::from_stream`.
+    pub fn from_reader<R: BufRead>(
+        reader: R,
+        stop_words: Stopwords,
+        punctuation: Punctuation,
+        options: TfIdfOptions,
+    ) -> io::Result<Self> {
+        Self::from_stream(reader.lines(), stop_words, punctuation, options)
+    }
+
+    /// Writes the TF-IDF vector of every streamed document to the writer, e.g. a file to spill them to
+    /// disk, as one line of space separated `word_id:weight` pairs per document. Returns the number of
+    /// documents written.
+    pub fn write_document_vectors<I, W>(&self, documents: I, mut writer: W) -> io::Result<usize>
+    where
+        I: IntoIterator<Item = io::Result<String>>,
+        W: Write,
+    {
+        let mut written = 0_usize;
+
+        for document in documents {
+            let vector = self.to_sparse_vector(self.score_document(&document?));
+            let line = vector
+                .iter()
+                .map(|(id, weight)| format!("{}:{}", id, weight))
+                .collect::<Vec<String>>()
+                .join(" ");
+            writeln!(writer, "{}", line)?;
+            written += 1;
+        }
+
+        writer.flush()?;
+        Ok(written)
+    }
+
+    fn fit(&mut self) {
+        let (word_scores, idf) = TfIdfLogic::build_tfidf(
+            &self.word_counts,
+            &self.document_frequencies,
+            self.total_documents,
+            &self.options,
+            self.background_idf.as_ref(),
+            self.fixed_vocabulary.then_some(&self.vocabulary),
+        );
+        if !self.document_processor.is_hashing() && !self.fixed_vocabulary {
+            self.vocabulary = TfIdfLogic::build_vocabulary(&idf);
+        }
+        self.word_scores = word_scores;
+        self.idf = idf;
+        self.document_vectors = OnceLock::new();
+    }
+
+    fn build_document_vectors(&self) -> Vec<SparseVector> {
+        let vectorize = |word_counts: &HashMap<String, f32>| {
+            self.to_sparse_vector(TfIdfLogic::score_document_words(
+                word_counts,
+                &self.idf,
+                &self.options,
+            ))
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            self.document_word_counts
+                .par_iter()
+                .map(vectorize)
+                .collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.document_word_counts.iter().map(vectorize).collect()
+        }
+    }
+
+    /// Adds new documents to the model, merging their counts into the corpus ones to update the word
+    /// scores and IDF weights, without processing the documents already analyzed again. The document
+    /// vectors are rebuilt with the new weights the next time they are needed. Terms pruned by the
+    /// document frequency limits can enter the vocabulary once they reach them.
+    pub fn add_documents(&mut self, documents: &[&str]) {
+        let documents = self.document_processor.split_documents_into_terms(
+            &documents
+                .iter()
+                .map(|document| document.to_string())
+                .collect::<Vec<String>>(),
+        );
+        let (word_counts, document_frequencies) = TfIdfLogic::count_words(&documents);
+        TfIdfLogic::merge_counts(&mut self.word_counts, word_counts);
+        TfIdfLogic::merge_counts(&mut self.document_frequencies, document_frequencies);
+        self.document_word_counts
+            .extend(TfIdfLogic::count_document_words(&documents));
+        self.total_documents += documents.len();
+        self.fit();
+    }
+
+    /// Scores the words of a new document with the fitted IDF weights, words outside the vocabulary
+    /// are ignored.
+    pub fn score_document(&self, document: &str) -> HashMap<String, f32> {
+        TfIdfLogic::score_document(
+            self.document_processor.split_into_terms(document),
+            &self.idf,
+            &self.options,
+        )
+    }
+
+    /// Gets the TF-IDF vector of an analyzed document, with the ids of the vocabulary.
+    pub fn get_document_vector(&self, document: usize) -> Option<&SparseVector> {
+        self.get_document_vectors().get(document)
+    }
+
+    /// Gets the TF-IDF vectors of every analyzed document, with the ids of the vocabulary, built on
+    /// the first call after the model is fitted.
+    pub fn get_document_vectors(&self) -> &[SparseVector] {
+        self.document_vectors
+            .get_or_init(|| self.build_document_vectors())
+    }
+
+    /// Gets the term-document matrix of the analyzed documents in compressed sparse row format.
+    pub fn to_csr(&self) -> CsrMatrix {
+        let mut indptr = vec![0_usize];
+        let mut indices = Vec::new();
+        let mut data = Vec::new();
+
+        self.get_document_vectors().iter().for_each(|vector| {
+            vector.iter().for_each(|(id, weight)| {
+                indices.push(*id);
+                data.push(*weight);
+            });
+            indptr.push(indices.len());
+        });
+
+        (indptr, indices, data)
+    }
+
+    /// Gets the dense term-document matrix of the analyzed documents, with a row per document and a
+    /// column per word id.
+    #[cfg(feature = "ndarray")]
+    pub fn to_array2(&self) -> Array2<f32> {
+        let document_vectors = self.get_document_vectors();
+        let mut matrix = Array2::zeros((document_vectors.len(), self.get_feature_count()));
+
+        document_vectors
+            .iter()
+            .enumerate()
+            .for_each(|(document, vector)| {
+                vector
+                    .iter()
+                    .for_each(|(id, weight)| matrix[[document, *id]] = *weight)
+            });
+
+        matrix
+    }
+
+    /// Gets the number of columns of the document vectors, the number of buckets in hashing mode.
+    pub fn get_feature_count(&self) -> usize {
+        match self.options.hashing_buckets {
+            Some(buckets) => buckets.max(1),
+            None => self.vocabulary.values().max().map_or(0, |id| id + 1),
+        }
+    }
+
+    /// Gets the top n words of an analyzed document with the highest score and their score.
+    pub fn top_keywords_for(&self, document: usize, n: usize) -> Vec<(String, f32)> {
+        self.document_word_counts
+            .get(document)
+            .map_or_else(Vec::new, |word_counts| {
+                get_ranked_scores(
+                    &TfIdfLogic::score_document_words(word_counts, &self.idf, &self.options),
+                    n,
+                )
+            })
+    }
+
+    /// Gets the top n words of every analyzed document with the highest score and their score.
+    pub fn top_keywords_for_all(&self, n: usize) -> Vec<Vec<(String, f32)>> {
+        #[cfg(feature = "parallel")]
+        {
+            (0..self.document_word_counts.len())
+                .into_par_iter()
+                .map(|document| self.top_keywords_for(document, n))
+                .collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            (0..self.document_word_counts.len())
+                .map(|document| self.top_keywords_for(document, n))
+                .collect()
+        }
+    }
+
+    /// Gets the cosine similarity of two analyzed documents, 0.0 if one of them does not exist.
+    pub fn cosine_similarity(&self, document_a: usize, document_b: usize) -> f32 {
+        match (
+            self.get_document_vector(document_a),
+            self.get_document_vector(document_b),
+        ) {
+            (Some(a), Some(b)) => sparse_cosine_similarity(a, b),
+            _ => 0.0,
+        }
+    }
+
+    /// Gets the top n analyzed documents most similar to a document and their cosine similarity.
+    pub fn most_similar(&self, document: usize, n: usize) -> Vec<(usize, f32)> {
+        match self.get_document_vector(document) {
+            Some(vector) => {
+                let mut similarities = self.rank_documents(vector);
+                similarities.retain(|(other, _)| *other != document);
+                similarities.truncate(n);
+                similarities
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Scores every analyzed document against a query processed like the documents, returning the
+    /// indexes and cosine similarities of the matching documents from the most to the least relevant.
+    pub fn score_query(&self, query: &str) -> Vec<(usize, f32)> {
+        let vector = self.to_sparse_vector(self.score_document(query));
+        let mut similarities = self.rank_documents(&vector);
+        similarities.retain(|(_, similarity)| *similarity > 0.0);
+        similarities
+    }
+
+    fn to_sparse_vector(&self, scores: HashMap<String, f32>) -> SparseVector {
+        let vocabulary = (!self.document_processor.is_hashing()).then_some(&self.vocabulary);
+        TfIdfLogic::to_sparse_vector(scores, vocabulary)
+    }
+
+    fn rank_documents(&self, vector: &[(usize, f32)]) -> Vec<(usize, f32)> {
+        let mut similarities = self
+            .get_document_vectors()
+            .iter()
+            .enumerate()
+            .map(|(document, document_vector)| {
+                (document, sparse_cosine_similarity(vector, document_vector))
+            })
+            .collect::<Vec<(usize, f32)>>();
+        similarities.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        similarities
+    }
+
+    /// Gets the number of analyzed documents containing a term, including the pruned ones.
+    pub fn document_frequency(&self, term: &str) -> usize {
+        self.document_frequencies
+            .get(&self.document_processor.get_term_key(term))
+            .map_or(0, |frequency| *frequency as usize)
+    }
+
+    /// Gets the number of terms kept after the document frequency limits.
+    pub fn vocabulary_size(&self) -> usize {
+        self.idf.len()
+    }
+
+    /// Gets the number of analyzed documents.
+    pub fn total_documents(&self) -> usize {
+        self.total_documents
+    }
+
+    /// Gets a summary of the corpus the model was fitted on.
+    pub fn corpus_stats(&self) -> CorpusStats {
+        let total_terms = self.word_counts.values().sum::<f32>() as usize;
+
+        CorpusStats {
+            total_documents: self.total_documents,
+            vocabulary_size: self.vocabulary_size(),
+            distinct_terms: self.document_frequencies.len(),
+            total_terms,
+            average_document_length: if self.total_documents == 0 {
+                0.0
+            } else {
+                total_terms as f32 / self.total_documents as f32
+            },
+        }
+    }
+
+    /// Gets the vocabulary, mapping every word to its id in the document vectors. It stays empty in
+    /// hashing mode, where the ids are the bucket numbers.
+    pub fn get_vocabulary(&self) -> &HashMap<String, usize> {
+        &self.vocabulary
+    }
+
+    /// Gets the IDF weight of a word in the vocabulary, or of its bucket in hashing mode.
+    pub fn get_idf(&self, word: &str) -> Option<f32> {
+        self.idf
+            .get(&self.document_processor.get_term_key(word))
+            .copied()
+    }
+
+    /// Gets the vocabulary and its IDF weights.
+    pub fn get_idf_map(&self) -> &HashMap<String, f32> {
+        &self.idf
     }
 
-    /// Gets the score of a given word.
+    /// Gets the score of a given word, or of its bucket in hashing mode.
     pub fn get_score(&self, word: &str) -> f32 {
-        *self.0.get(word).unwrap_or(&0.0)
+        *self
+            .word_scores
+            .get(&self.document_processor.get_term_key(word))
+            .unwrap_or(&0.0)
     }
 
     /// Gets the top n words with the highest score.
     pub fn get_ranked_words(&self, n: usize) -> Vec<String> {
-        get_ranked_strings(&self.0, n)
+        get_ranked_strings(&self.word_scores, n)
     }
 
     /// Gets the top n words with the highest score.
     pub fn get_ranked_word_scores(&self, n: usize) -> Vec<(String, f32)> {
-        get_ranked_scores(&self.0, n)
+        get_ranked_scores(&self.word_scores, n)
     }
 
     /// Gets the word scores map.
     pub fn get_word_scores_map(&self) -> &HashMap<String, f32> {
-        &self.0
+        &self.word_scores
     }
 }
@@ -18,20 +18,177 @@ use std::collections::{HashMap, HashSet};
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+use super::{BackgroundIdf, DocumentFrequencyLimit, IdfWeighting, TfIdfOptions, TfWeighting};
+
 pub struct TfIdfLogic;
 
 impl TfIdfLogic {
-    pub fn build_tfidf(documents: &[String]) -> HashMap<String, f32> {
-        Self::l2_normalize(Self::calculate_tf_idf(
-            Self::calculate_tf(Self::generate_word_hashmap(documents)),
-            Self::calculate_idf(
-                documents.len() as f32,
-                Self::generate_unique_word_hashmap(documents),
+    /// Counts the occurrences of every word and the number of documents it appears in.
+    pub fn count_words(documents: &[Vec<String>]) -> (HashMap<String, f32>, HashMap<String, f32>) {
+        let to_owned = |map: HashMap<&str, f32>| {
+            map.into_iter()
+                .map(|(word, count)| (word.to_string(), count))
+                .collect::<HashMap<String, f32>>()
+        };
+
+        (
+            to_owned(Self::generate_word_hashmap(documents)),
+            to_owned(Self::generate_unique_word_hashmap(documents)),
+        )
+    }
+
+    /// Adds the counts of new documents to the existing ones.
+    pub fn merge_counts(counts: &mut HashMap<String, f32>, new_counts: HashMap<String, f32>) {
+        new_counts
+            .into_iter()
+            .for_each(|(word, count)| *counts.entry(word).or_insert(0.0) += count);
+    }
+
+    pub fn build_tfidf(
+        word_counts: &HashMap<String, f32>,
+        document_frequencies: &HashMap<String, f32>,
+        total_documents: usize,
+        options: &TfIdfOptions,
+        background_idf: Option<&BackgroundIdf>,
+        vocabulary: Option<&HashMap<String, usize>>,
+    ) -> (HashMap<String, f32>, HashMap<String, f32>) {
+        let min_df = Self::get_document_frequency_limit(options.min_df, total_documents);
+        let max_df = Self::get_document_frequency_limit(options.max_df, total_documents);
+        let document_frequencies = Self::borrow_words(document_frequencies)
+            .into_iter()
+            .filter(|(word, count)| {
+                *count >= min_df
+                    && *count <= max_df
+                    && vocabulary.map_or(true, |vocabulary| vocabulary.contains_key(*word))
+            })
+            .collect::<HashMap<&str, f32>>();
+        let word_counts = Self::borrow_words(word_counts)
+            .into_iter()
+            .filter(|(word, _)| document_frequencies.contains_key(word))
+            .collect::<HashMap<&str, f32>>();
+        let idf = match background_idf {
+            Some(background_idf) => document_frequencies
+                .into_keys()
+                .map(|word| (word, background_idf.get_weight(word)))
+                .collect::<HashMap<&str, f32>>(),
+            None => Self::calculate_idf(
+                total_documents as f32,
+                document_frequencies,
+                options.idf_weighting,
+            ),
+        };
+        let idf_weights = idf
+            .iter()
+            .map(|(word, weight)| (word.to_string(), *weight))
+            .collect::<HashMap<String, f32>>();
+
+        (
+            Self::l2_normalize(
+                Self::calculate_tf_idf(
+                    Self::calculate_tf(word_counts, options.get_tf_weighting()),
+                    idf,
+                ),
+                options.l2_normalization,
             ),
-        ))
+            idf_weights,
+        )
+    }
+
+    fn get_document_frequency_limit(limit: DocumentFrequencyLimit, total_documents: usize) -> f32 {
+        match limit {
+            DocumentFrequencyLimit::Count(count) => count as f32,
+            DocumentFrequencyLimit::Ratio(ratio) => ratio * total_documents as f32,
+        }
+    }
+
+    fn borrow_words(map: &HashMap<String, f32>) -> HashMap<&str, f32> {
+        map.iter()
+            .map(|(word, count)| (word.as_str(), *count))
+            .collect::<HashMap<&str, f32>>()
+    }
+
+    pub fn score_document(
+        document: Vec<String>,
+        idf: &HashMap<String, f32>,
+        options: &TfIdfOptions,
+    ) -> HashMap<String, f32> {
+        Self::score_word_counts(Self::generate_word_hashmap(&[document]), idf, options)
     }
 
-    fn generate_word_hashmap(documents: &[String]) -> HashMap<&str, f32> {
+    /// Counts the occurrences of every word in each document.
+    pub fn count_document_words(documents: &[Vec<String>]) -> Vec<HashMap<String, f32>> {
+        let count = |document: &Vec<String>| {
+            document
+                .iter()
+                .fold(HashMap::<String, f32>::new(), |mut acc, word| {
+                    *acc.entry(word.to_string()).or_insert(0.0) += 1.0;
+                    acc
+                })
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            documents.par_iter().map(count).collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            documents.iter().map(count).collect()
+        }
+    }
+
+    /// Scores the counted words of a document, words outside the vocabulary are ignored.
+    pub fn score_document_words(
+        word_counts: &HashMap<String, f32>,
+        idf: &HashMap<String, f32>,
+        options: &TfIdfOptions,
+    ) -> HashMap<String, f32> {
+        Self::score_word_counts(Self::borrow_words(word_counts), idf, options)
+    }
+
+    /// Gives every word of the vocabulary an id, in alphabetical order.
+    pub fn build_vocabulary(idf: &HashMap<String, f32>) -> HashMap<String, usize> {
+        let mut words = idf.keys().collect::<Vec<&String>>();
+        words.sort();
+        words
+            .into_iter()
+            .enumerate()
+            .map(|(id, word)| (word.to_string(), id))
+            .collect()
+    }
+
+    /// Turns word scores into a sparse vector sorted by word id, the words are bucket numbers
+    /// without a vocabulary.
+    pub fn to_sparse_vector(
+        scores: HashMap<String, f32>,
+        vocabulary: Option<&HashMap<String, usize>>,
+    ) -> Vec<(usize, f32)> {
+        let mut vector = scores
+            .into_iter()
+            .filter_map(|(word, score)| match vocabulary {
+                Some(vocabulary) => vocabulary.get(&word).map(|id| (*id, score)),
+                None => word.parse::<usize>().ok().map(|bucket| (bucket, score)),
+            })
+            .collect::<Vec<(usize, f32)>>();
+        vector.sort_by_key(|(id, _)| *id);
+        vector
+    }
+
+    fn score_word_counts(
+        word_counts: HashMap<&str, f32>,
+        idf: &HashMap<String, f32>,
+        options: &TfIdfOptions,
+    ) -> HashMap<String, f32> {
+        Self::l2_normalize(
+            Self::calculate_tf(word_counts, options.get_tf_weighting())
+                .into_iter()
+                .filter_map(|(word, tf)| idf.get(word).map(|weight| (word, tf * weight)))
+                .collect::<HashMap<&str, f32>>(),
+            options.l2_normalization,
+        )
+    }
+
+    fn generate_word_hashmap(documents: &[Vec<String>]) -> HashMap<&str, f32> {
         #[cfg(feature = "parallel")]
         {
             Self::parallel_word_hashmap(documents)
@@ -44,10 +201,10 @@ impl TfIdfLogic {
     }
 
     #[cfg(not(feature = "parallel"))]
-    fn basic_word_hashmap(documents: &[String]) -> HashMap<&str, f32> {
+    fn basic_word_hashmap(documents: &[Vec<String>]) -> HashMap<&str, f32> {
         documents
             .iter()
-            .flat_map(|document| document.split_whitespace())
+            .flat_map(|document| document.iter().map(String::as_str))
             .fold(HashMap::new(), |mut acc, word| {
                 let count = acc.entry(word).or_insert(0.0);
                 *count += 1.0;
@@ -56,30 +213,24 @@ impl TfIdfLogic {
     }
 
     #[cfg(feature = "parallel")]
-    fn parallel_word_hashmap(documents: &[String]) -> HashMap<&str, f32> {
+    fn parallel_word_hashmap(documents: &[Vec<String>]) -> HashMap<&str, f32> {
         documents
             .par_iter()
-            .fold(
-                || HashMap::new(),
-                |mut acc, document| {
-                    document
-                        .split_whitespace()
-                        .for_each(|word| *acc.entry(word).or_insert(0.0) += 1.0);
-                    acc
-                },
-            )
-            .reduce(
-                || HashMap::new(),
-                |mut acc, hmap| {
-                    for (word, count) in hmap {
-                        *acc.entry(word).or_insert(0.0) += count;
-                    }
-                    acc
-                },
-            )
+            .fold(HashMap::new, |mut acc, document| {
+                document
+                    .iter()
+                    .for_each(|word| *acc.entry(word.as_str()).or_insert(0.0) += 1.0);
+                acc
+            })
+            .reduce(HashMap::new, |mut acc, hmap| {
+                for (word, count) in hmap {
+                    *acc.entry(word).or_insert(0.0) += count;
+                }
+                acc
+            })
     }
 
-    fn generate_unique_word_hashmap(documents: &[String]) -> HashMap<&str, f32> {
+    fn generate_unique_word_hashmap(documents: &[Vec<String>]) -> HashMap<&str, f32> {
         #[cfg(feature = "parallel")]
         {
             Self::parallel_unique_word_hashmap(documents)
@@ -92,10 +243,15 @@ impl TfIdfLogic {
     }
 
     #[cfg(not(feature = "parallel"))]
-    fn basic_unique_word_hashmap(documents: &[String]) -> HashMap<&str, f32> {
+    fn basic_unique_word_hashmap(documents: &[Vec<String>]) -> HashMap<&str, f32> {
         documents
             .iter()
-            .map(|document| document.split_whitespace().collect::<HashSet<&str>>())
+            .map(|document| {
+                document
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<HashSet<&str>>()
+            })
             .flat_map(|unique_words| unique_words.into_iter())
             .fold(HashMap::new(), |mut acc, word| {
                 let count = acc.entry(word).or_insert(0.0);
@@ -105,70 +261,96 @@ impl TfIdfLogic {
     }
 
     #[cfg(feature = "parallel")]
-    fn parallel_unique_word_hashmap(documents: &[String]) -> HashMap<&str, f32> {
+    fn parallel_unique_word_hashmap(documents: &[Vec<String>]) -> HashMap<&str, f32> {
         documents
             .par_iter()
-            .map(|document| document.split_whitespace().collect::<HashSet<&str>>())
-            .fold(
-                || HashMap::new(),
-                |mut acc, unique_words| {
-                    unique_words
-                        .into_iter()
-                        .for_each(|word| *acc.entry(word).or_insert(0.0) += 1.0);
-                    acc
-                },
-            )
-            .reduce(
-                || HashMap::new(),
-                |mut acc, hmap| {
-                    for (word, count) in hmap {
-                        *acc.entry(word).or_insert(0.0) += count;
-                    }
-                    acc
-                },
-            )
+            .map(|document| {
+                document
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<HashSet<&str>>()
+            })
+            .fold(HashMap::new, |mut acc, unique_words| {
+                unique_words
+                    .into_iter()
+                    .for_each(|word| *acc.entry(word).or_insert(0.0) += 1.0);
+                acc
+            })
+            .reduce(HashMap::new, |mut acc, hmap| {
+                for (word, count) in hmap {
+                    *acc.entry(word).or_insert(0.0) += count;
+                }
+                acc
+            })
     }
 
-    fn calculate_tf(tf: HashMap<&str, f32>) -> HashMap<&str, f32> {
+    fn calculate_tf(tf: HashMap<&str, f32>, weighting: TfWeighting) -> HashMap<&str, f32> {
         #[cfg(feature = "parallel")]
         {
-            Self::parallel_tf(tf)
+            Self::parallel_tf(tf, weighting)
         }
 
         #[cfg(not(feature = "parallel"))]
         {
-            Self::basic_tf(tf)
+            Self::basic_tf(tf, weighting)
+        }
+    }
+
+    fn weight_tf(count: f32, total_words: f32, max_count: f32, weighting: TfWeighting) -> f32 {
+        match weighting {
+            TfWeighting::Raw => count / total_words,
+            TfWeighting::Log => 1.0 + count.ln(),
+            TfWeighting::Augmented => 0.5 + 0.5 * count / max_count,
+            TfWeighting::Boolean => 1.0,
         }
     }
 
     #[cfg(not(feature = "parallel"))]
-    fn basic_tf(tf: HashMap<&str, f32>) -> HashMap<&str, f32> {
+    fn basic_tf(tf: HashMap<&str, f32>, weighting: TfWeighting) -> HashMap<&str, f32> {
         let total_words = tf.values().sum::<f32>();
+        let max_count = tf.values().fold(0.0_f32, |max, count| max.max(*count));
         tf.iter()
-            .map(|(word, count)| (*word, count / total_words))
+            .map(|(word, count)| {
+                (
+                    *word,
+                    Self::weight_tf(*count, total_words, max_count, weighting),
+                )
+            })
             .collect::<HashMap<&str, f32>>()
     }
 
     #[cfg(feature = "parallel")]
-    fn parallel_tf(tf: HashMap<&str, f32>) -> HashMap<&str, f32> {
+    fn parallel_tf(tf: HashMap<&str, f32>, weighting: TfWeighting) -> HashMap<&str, f32> {
         let total_words = tf.par_iter().map(|(_, v)| v).sum::<f32>();
+        let max_count = tf.par_iter().map(|(_, v)| *v).reduce(|| 0.0, f32::max);
         tf.par_iter()
-            .map(|(word, count)| (*word, count / total_words))
+            .map(|(word, count)| {
+                (
+                    *word,
+                    Self::weight_tf(*count, total_words, max_count, weighting),
+                )
+            })
             .collect::<HashMap<&str, f32>>()
     }
 
+    fn weight_idf(docs_len: f32, count: f32, weighting: IdfWeighting) -> f32 {
+        match weighting {
+            IdfWeighting::Standard => (docs_len / count).ln(),
+            IdfWeighting::Smooth => ((docs_len + 1.0_f32) / (count + 1.0_f32)).ln() + 1.0_f32,
+            IdfWeighting::Probabilistic => ((docs_len - count) / count).ln().max(0.0_f32),
+        }
+    }
+
     fn calculate_idf<'a>(
         docs_len: f32,
         word_hashmap: HashMap<&'a str, f32>,
+        weighting: IdfWeighting,
     ) -> HashMap<&'a str, f32> {
         #[cfg(feature = "parallel")]
         {
             word_hashmap
                 .par_iter()
-                .map(|(word, count)| {
-                    let documents_with_term = (docs_len + 1.0_f32) / (count + 1.0_f32);
-                    (*word, documents_with_term.ln() + 1.0_f32)
-                })
+                .map(|(word, count)| (*word, Self::weight_idf(docs_len, *count, weighting)))
                 .collect::<HashMap<&'a str, f32>>()
         }
 
@@ -176,10 +358,7 @@ impl TfIdfLogic {
         {
             word_hashmap
                 .iter()
-                .map(|(word, count)| {
-                    let documents_with_term = (docs_len + 1.0_f32) / (count + 1.0_f32);
-                    (*word, documents_with_term.ln() + 1.0_f32)
-                })
+                .map(|(word, count)| (*word, Self::weight_idf(docs_len, *count, weighting)))
                 .collect::<HashMap<&'a str, f32>>()
         }
     }
@@ -203,7 +382,14 @@ impl TfIdfLogic {
         }
     }
 
-    fn l2_normalize(tf_id: HashMap<&str, f32>) -> HashMap<String, f32> {
+    fn l2_normalize(tf_id: HashMap<&str, f32>, normalize: bool) -> HashMap<String, f32> {
+        if !normalize {
+            return tf_id
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<HashMap<String, f32>>();
+        }
+
         #[cfg(feature = "parallel")]
         {
             Self::parallel_l2_normalize(tf_id)
@@ -222,6 +408,14 @@ impl TfIdfLogic {
             .map(|value| value * value)
             .sum::<f32>()
             .sqrt();
+        // an all-zero vector, e.g. of terms found in every document, is returned as is
+        if l2_norm == 0.0_f32 {
+            return tf_id
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<HashMap<String, f32>>();
+        }
+
         tf_id
             .iter()
             .map(|(key, value)| (key.to_string(), value / l2_norm))
@@ -235,6 +429,13 @@ impl TfIdfLogic {
             .map(|(_, value)| value * value)
             .sum::<f32>()
             .sqrt();
+        // an all-zero vector, e.g. of terms found in every document, is returned as is
+        if l2_norm == 0.0_f32 {
+            return tf_id
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<HashMap<String, f32>>();
+        }
 
         tf_id
             .par_iter()
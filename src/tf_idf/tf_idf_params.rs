@@ -14,12 +14,26 @@
 // along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-    common::{Documents, Punctuation, Stopwords, Text},
-    tokenizer::Tokenizer,
+    common::{Documents, Punctuation, Stopwords, Text, WordBoundaryRules, WordNormalizer},
+    tokenizer::{
+        EmojiPolicy, EntityPolicies, HyphenPolicy, LongWordPolicy, NumberPolicy, ParagraphSplit,
+        Tokenizer,
+    },
 };
 
+#[cfg(feature = "stemming")]
+use crate::tokenizer::StemmingLanguage;
+#[cfg(feature = "unicode-normalization")]
+use crate::tokenizer::UnicodeNormalization;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
 use super::document_processor::DocumentProcessor;
 
+type TokenizedDocuments<'a> = &'a [Vec<&'a str>];
+
 /// The options to split the text into documents.
 pub enum TextSplit {
     Sentences,
@@ -27,6 +41,198 @@ pub enum TextSplit {
     Phrases,
 }
 
+/// The SMART term frequency variants.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TfWeighting {
+    /// The count of the term over the total number of terms.
+    #[default]
+    Raw,
+    /// `1 + ln(count)`, dampening frequent terms.
+    Log,
+    /// `0.5 + 0.5 * count / max_count`, preventing a bias towards longer documents.
+    Augmented,
+    /// 1.0 for every term present.
+    Boolean,
+}
+
+/// The SMART inverse document frequency variants.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IdfWeighting {
+    /// `ln(N / df)`.
+    Standard,
+    /// `ln((N + 1) / (df + 1)) + 1`, never zero for terms present in every document.
+    #[default]
+    Smooth,
+    /// `max(0, ln((N - df) / df))`.
+    Probabilistic,
+}
+
+/// What the terms of the vocabulary are.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TermUnit {
+    /// Single words, or their n-grams with the `ngram_range` option.
+    #[default]
+    Words,
+    /// The phrases of `Tokenizer::split_into_phrases`, i.e. the word sequences between stop words.
+    /// Processed documents have no stop words left, so their terms stay words.
+    Phrases,
+}
+
+/// A bound on the number of documents a term appears in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DocumentFrequencyLimit {
+    /// An absolute number of documents.
+    Count(usize),
+    /// A proportion of the documents, from 0.0 to 1.0.
+    Ratio(f32),
+}
+
+/// The options of the TF-IDF fitting.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TfIdfOptions {
+    /// The minimum and maximum number of words of the vocabulary terms, defaults to `(1, 1)`.
+    /// N-grams of unprocessed documents never span stop words or punctuation.
+    pub ngram_range: (usize, usize),
+    /// The term frequency variant, defaults to `TfWeighting::Raw`.
+    pub tf_weighting: TfWeighting,
+    /// The inverse document frequency variant, defaults to `IdfWeighting::Smooth`.
+    pub idf_weighting: IdfWeighting,
+    /// Terms appearing in fewer documents are dropped from the vocabulary, defaults to `Count(1)`.
+    pub min_df: DocumentFrequencyLimit,
+    /// Terms appearing in more documents are dropped from the vocabulary, defaults to `Ratio(1.0)`.
+    pub max_df: DocumentFrequencyLimit,
+    /// What the terms of the vocabulary are, defaults to `TermUnit::Words`.
+    pub term_unit: TermUnit,
+    /// The number of buckets the terms are hashed into, skipping the explicit vocabulary to keep the
+    /// memory constant. The words of the model become bucket numbers, defaults to none.
+    pub hashing_buckets: Option<usize>,
+    /// Replaces the term frequency with `1 + ln(count)` like scikit-learn, the same as
+    /// `TfWeighting::Log`, defaults to false.
+    pub sublinear_tf: bool,
+    /// Scales the scores of every document to unit length, defaults to true.
+    pub l2_normalization: bool,
+    /// The number of streamed documents processed at once, in parallel with the `parallel` feature,
+    /// bounding the memory of `TfIdf::from_stream`, defaults to 1024.
+    pub batch_size: usize,
+    /// The language of the Snowball stemmer applied to the words of unprocessed documents, defaults
+    /// to none.
+    #[cfg(feature = "stemming")]
+    pub stemming: Option<StemmingLanguage>,
+    /// The Unicode normalization form applied to unprocessed documents and stopwords, defaults to
+    /// none.
+    #[cfg(feature = "unicode-normalization")]
+    pub unicode_normalization: Option<UnicodeNormalization>,
+    /// Removes the accents of the words of unprocessed documents, defaults to false.
+    #[cfg(feature = "unicode-normalization")]
+    pub fold_diacritics: bool,
+    /// The minimum number of characters of the words of unprocessed documents, shorter words are
+    /// dropped like stop words, defaults to 0.
+    pub min_word_length: usize,
+    /// The maximum number of characters of the words of unprocessed documents, defaults to none.
+    pub max_word_length: Option<usize>,
+    /// How the words longer than `max_word_length` are handled, defaults to
+    /// `LongWordPolicy::Drop`.
+    pub long_word_policy: LongWordPolicy,
+    /// How the numbers of unprocessed documents are handled, defaults to `NumberPolicy::Keep`.
+    pub number_policy: NumberPolicy,
+    /// How the emoji and emoticons of unprocessed documents are handled, defaults to
+    /// `EmojiPolicy::Keep`.
+    pub emoji_policy: EmojiPolicy,
+    /// How the hyphenated words of unprocessed documents are split, defaults to
+    /// `HyphenPolicy::Split`.
+    pub hyphen_policy: HyphenPolicy,
+    /// Expands the English contractions of unprocessed documents, e.g. "don't" into "do not",
+    /// defaults to false.
+    pub expand_contractions: bool,
+    /// How the URLs, emails, hashtags and mentions of unprocessed documents are handled, all split
+    /// by default.
+    pub entity_policies: EntityPolicies,
+}
+
+impl Default for TfIdfOptions {
+    fn default() -> Self {
+        Self {
+            ngram_range: (1, 1),
+            tf_weighting: TfWeighting::default(),
+            idf_weighting: IdfWeighting::default(),
+            min_df: DocumentFrequencyLimit::Count(1),
+            max_df: DocumentFrequencyLimit::Ratio(1.0),
+            term_unit: TermUnit::default(),
+            hashing_buckets: None,
+            sublinear_tf: false,
+            l2_normalization: true,
+            batch_size: 1024,
+            #[cfg(feature = "stemming")]
+            stemming: None,
+            #[cfg(feature = "unicode-normalization")]
+            unicode_normalization: None,
+            #[cfg(feature = "unicode-normalization")]
+            fold_diacritics: false,
+            min_word_length: 0,
+            max_word_length: None,
+            long_word_policy: LongWordPolicy::default(),
+            number_policy: NumberPolicy::default(),
+            emoji_policy: EmojiPolicy::default(),
+            hyphen_policy: HyphenPolicy::default(),
+            expand_contractions: false,
+            entity_policies: EntityPolicies::default(),
+        }
+    }
+}
+
+impl TfIdfOptions {
+    pub(crate) fn get_word_normalizer(&self) -> WordNormalizer {
+        WordNormalizer {
+            custom: None,
+            filter: None,
+            #[cfg(feature = "stemming")]
+            stemming: self.stemming,
+            #[cfg(feature = "unicode-normalization")]
+            unicode_normalization: self.unicode_normalization,
+            #[cfg(feature = "unicode-normalization")]
+            fold_diacritics: self.fold_diacritics,
+            min_word_length: self.min_word_length,
+            max_word_length: self.max_word_length,
+            long_word_policy: self.long_word_policy,
+            number_policy: self.number_policy,
+            emoji_policy: self.emoji_policy,
+            hyphen_policy: self.hyphen_policy,
+            contractions: self
+                .expand_contractions
+                .then(|| WordNormalizer::get_contractions(&[])),
+            entity_policies: self.entity_policies,
+            word_boundary_rules: WordBoundaryRules::default(),
+            stopword_patterns: None,
+        }
+    }
+
+    /// Builds the processor of the documents with every term and word normalization option.
+    pub(crate) fn get_document_processor(
+        &self,
+        stop_words: Stopwords,
+        punctuation: Punctuation,
+    ) -> DocumentProcessor {
+        DocumentProcessor::new(stop_words, punctuation)
+            .with_ngram_range(self.ngram_range)
+            .with_term_unit(self.term_unit)
+            .with_hashing_buckets(self.hashing_buckets)
+            .with_normalizer(self.get_word_normalizer())
+    }
+
+    pub(crate) fn get_tf_weighting(&self) -> TfWeighting {
+        if self.sublinear_tf {
+            TfWeighting::Log
+        } else {
+            self.tf_weighting
+        }
+    }
+}
+
 /// The `TfIdfParams` enum represents the parameters for the TF-IDF (Term Frequency - Inverse Document Frequency) algorithm.
 /// The parameters to be used in the Tf-Idf algorithm.
 pub enum TfIdfParams<'a> {
@@ -52,24 +258,92 @@ pub enum TfIdfParams<'a> {
     /// * `punctuation`: Optional list of punctuation symbols.
     /// * `split`: The option to split the text into documents.
     TextBlock(Text<'a>, Stopwords<'a>, Punctuation<'a>, TextSplit),
+
+    /// Represents documents already split into tokens by another tokenizer, the tokens are used as
+    /// they are.
+    ///
+    /// ## Arguments
+    /// * `documents`: The tokens of every document to be analyzed.
+    TokenizedDocuments(TokenizedDocuments<'a>),
 }
 
 impl<'a> TfIdfParams<'a> {
     /// Returns the documents to be analyzed.
     pub fn get_documents(&self) -> Vec<String> {
         match self {
-            TfIdfParams::UnprocessedDocuments(documents, stopwords, punctuatuion) => {
-                DocumentProcessor::new(documents, stopwords, punctuatuion).process_documents()
-            }
+            TfIdfParams::UnprocessedDocuments(documents, _, _) => self
+                .get_document_processor(&TfIdfOptions::default())
+                .process_documents(documents),
             TfIdfParams::ProcessedDocuments(documents) => documents.to_vec(),
+            TfIdfParams::TokenizedDocuments(documents) => {
+                documents.iter().map(|tokens| tokens.join(" ")).collect()
+            }
             TfIdfParams::TextBlock(text, stop_words, punctuation, split) => {
-                let tokenizer = Tokenizer::new(text, stop_words, *punctuation);
+                Self::split_text(&Tokenizer::new(text, stop_words, *punctuation), split)
+            }
+        }
+    }
+
+    fn split_text(tokenizer: &Tokenizer, split: &TextSplit) -> Vec<String> {
+        match split {
+            TextSplit::Sentences => tokenizer.split_into_sentences(),
+            TextSplit::Paragraphs => tokenizer.split_into_paragraphs(),
+            TextSplit::Phrases => tokenizer.split_into_phrases(None),
+        }
+    }
+
+    /// Returns the terms of every document to be analyzed.
+    pub(crate) fn get_document_terms(&self, processor: &DocumentProcessor) -> Vec<Vec<String>> {
+        match self {
+            TfIdfParams::UnprocessedDocuments(documents, _, _) => {
+                processor.split_documents_into_terms(documents)
+            }
+            TfIdfParams::TokenizedDocuments(documents) => {
+                processor.split_tokenized_documents_into_terms(documents)
+            }
+            TfIdfParams::TextBlock(text, _, _, split)
+                if processor.get_term_unit() == TermUnit::Phrases =>
+            {
                 match split {
-                    TextSplit::Sentences => tokenizer.split_into_sentences(),
-                    TextSplit::Paragraphs => tokenizer.split_into_paragraphs(),
-                    TextSplit::Phrases => tokenizer.split_into_phrases(None),
+                    TextSplit::Sentences => processor.split_documents_into_terms(
+                        &text
+                            .unicode_sentences()
+                            .map(|sentence| sentence.to_string())
+                            .collect::<Vec<String>>(),
+                    ),
+                    TextSplit::Paragraphs => processor.split_documents_into_terms(
+                        &ParagraphSplit::Lines
+                            .split(text)
+                            .into_iter()
+                            .filter(|paragraph| !paragraph.trim().is_empty())
+                            .map(|paragraph| paragraph.to_string())
+                            .collect::<Vec<String>>(),
+                    ),
+                    TextSplit::Phrases => Self::split_text(&processor.get_tokenizer(text), split)
+                        .into_iter()
+                        .map(|phrase| vec![processor.get_term_key(&phrase)])
+                        .collect(),
                 }
             }
+            TfIdfParams::TextBlock(text, _, _, split) => processor
+                .split_processed_documents_into_terms(&Self::split_text(
+                    &processor.get_tokenizer(text),
+                    split,
+                )),
+            _ => processor.split_processed_documents_into_terms(&self.get_documents()),
+        }
+    }
+
+    /// Returns the processor used to turn new documents into the same terms as the analyzed ones.
+    pub(crate) fn get_document_processor(&self, options: &TfIdfOptions) -> DocumentProcessor {
+        match self {
+            TfIdfParams::UnprocessedDocuments(_, stop_words, punctuation)
+            | TfIdfParams::TextBlock(_, stop_words, punctuation, _) => {
+                options.get_document_processor(stop_words, *punctuation)
+            }
+            TfIdfParams::ProcessedDocuments(_) | TfIdfParams::TokenizedDocuments(_) => {
+                options.get_document_processor(&[], None)
+            }
         }
     }
 }
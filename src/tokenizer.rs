@@ -13,65 +13,246 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, Read},
+    sync::{Arc, OnceLock},
+};
 
-use regex::Regex;
+pub use regex::{Error as RegexError, Regex, RegexSet};
 use unicode_segmentation::UnicodeSegmentation;
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+#[cfg(feature = "stemming")]
+pub use rust_stemmers::Algorithm as StemmingLanguage;
+
+#[cfg(feature = "stop-words")]
+pub use crate::common::StopwordLanguage;
+#[cfg(feature = "unicode-normalization")]
+pub use crate::common::UnicodeNormalization;
+#[cfg(feature = "lang-detect")]
+pub use crate::common::{DetectedLanguage, Language};
+pub use crate::common::{
+    EmojiPolicy, EntityKind, EntityPolicies, EntityPolicy, HyphenPolicy, LongWordPolicy,
+    NumberPolicy, StopwordFormat, StopwordSet, WordBoundaryRules, WordPieceVocabulary,
+    CONTINUING_SUBWORD_PREFIX, CONTRACTIONS, EMOJI_NAMES, EMOJI_PLACEHOLDER, EMOTICONS,
+    NUMBER_PLACEHOLDER, UNKNOWN_SUBWORD,
+};
+
 use crate::common::{
-    get_special_char_regex, is_punctuation, process_word, PhraseLength, Punctuation, Stopwords,
-    Text, PUNCTUATION,
+    get_special_char_regex, is_number, is_punctuation, process_word, PhraseLength, Punctuation,
+    Stopwords, Text, WordNormalizer, PUNCTUATION,
 };
 
+/// Splits a text into the words, sentences and phrases the extractors work on, implemented by
+/// [`Tokenizer`] and by custom tokenizers, e.g. wrapping lindera or jieba for languages without spaces.
+/// The words of the sentences and phrases are separated by spaces, and every split keeps the order of
+/// the text.
+pub trait Tokenize {
+    /// The words of the text, without stopwords and punctuation.
+    fn words(&self) -> Vec<String>;
+    /// The sentences of the text, as their words.
+    fn sentences(&self) -> Vec<String>;
+    /// The phrases of the text, the sequences of words between stopwords and punctuation, split every
+    /// `length` words if given.
+    fn phrases(&self, length: PhraseLength) -> Vec<String>;
+    /// The most frequent form every word is written in, none by default so the words are returned
+    /// as they are.
+    fn surface_forms(&self) -> SurfaceForms {
+        SurfaceForms::default()
+    }
+}
+
+/// The most frequent original form of every normalized word, e.g. "NASA" for "nasa", to show ranked
+/// words and phrases as written in the text.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SurfaceForms {
+    forms: HashMap<String, String>,
+}
+
+impl SurfaceForms {
+    /// Gets the surface form of a word, or of every word of a phrase, words without one are kept.
+    pub fn get_surface_form(&self, term: &str) -> String {
+        term.split_whitespace()
+            .map(|word| self.forms.get(word).map_or(word, |form| form.as_str()))
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    /// Replaces the terms of a ranking with their surface forms.
+    pub fn apply(&self, ranked: Vec<(String, f32)>) -> Vec<(String, f32)> {
+        ranked
+            .into_iter()
+            .map(|(term, score)| (self.get_surface_form(&term), score))
+            .collect()
+    }
+}
+
+#[derive(Clone)]
 pub struct Tokenizer {
     text: String,
-    stopwords: HashSet<String>,
+    stopwords: StopwordSet,
     punctuation: HashSet<String>,
+    stop_phrases: HashMap<String, Vec<Vec<String>>>,
+    normalizer: WordNormalizer,
+    special_char_regex: Regex,
+    paragraph_split: ParagraphSplit,
+}
+
+/// How a text is split into paragraphs, with the Unix "\n", Windows "\r\n" and classic Mac OS
+/// "\r" line endings.
+#[derive(Clone, Debug, Default)]
+pub enum ParagraphSplit {
+    /// Every line is a paragraph.
+    #[default]
+    Lines,
+    /// The paragraphs are separated by blank lines, so hard-wrapped lines are joined.
+    BlankLines,
+    /// The paragraphs are separated by the matches of a regex.
+    Delimiters(Regex),
+}
+
+impl ParagraphSplit {
+    /// Separates the paragraphs by any of the given strings, e.g. `"\n---\n"` or the form feed,
+    /// whose "\n" match any line ending. Empty strings are ignored, and every line is a paragraph
+    /// when none is left. Fails if the delimiters are too large to be compiled into a regex.
+    pub fn delimiters(delimiters: &[&str]) -> Result<Self, RegexError> {
+        let pattern = delimiters
+            .iter()
+            .filter(|delimiter| !delimiter.is_empty())
+            .map(|delimiter| regex::escape(delimiter).replace('\n', LINE_ENDING_PATTERN))
+            .collect::<Vec<String>>()
+            .join("|");
+
+        if pattern.is_empty() {
+            return Ok(Self::Lines);
+        }
+
+        Ok(Self::Delimiters(Regex::new(&pattern)?))
+    }
+
+    /// Splits a text into its paragraphs.
+    pub fn split<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        match self {
+            Self::Lines => get_line_ending_regex().split(text).collect(),
+            Self::BlankLines => split_blank_lines(text),
+            Self::Delimiters(regex) => regex.split(text).collect(),
+        }
+    }
+}
+
+const LINE_ENDING_PATTERN: &str = r"(?:\r\n?|\n)";
+
+fn get_line_ending_regex() -> &'static Regex {
+    static LINE_ENDING_REGEX: OnceLock<Regex> = OnceLock::new();
+    LINE_ENDING_REGEX.get_or_init(|| Regex::new(LINE_ENDING_PATTERN).unwrap())
+}
+
+/// Splits a text into the runs of lines between its blank lines.
+fn split_blank_lines(text: &str) -> Vec<&str> {
+    let mut paragraphs = Vec::new();
+    let mut paragraph: Option<(usize, usize)> = None;
+    let mut start = 0;
+
+    get_line_ending_regex()
+        .find_iter(text)
+        .map(|line_ending| line_ending.range())
+        .chain(std::iter::once(text.len()..text.len()))
+        .for_each(|line_ending| {
+            if text[start..line_ending.start].trim().is_empty() {
+                paragraphs.extend(paragraph.take().map(|(first, last)| &text[first..last]));
+            } else {
+                let first = paragraph.map_or(start, |(first, _)| first);
+                paragraph = Some((first, line_ending.start));
+            }
+
+            start = line_ending.end;
+        });
+
+    paragraphs.extend(paragraph.map(|(first, last)| &text[first..last]));
+    paragraphs
 }
 
 #[cfg(feature = "parallel")]
-fn get_sentence_space_regex() -> Regex {
-    Regex::new(r"^([\.!?])[\n\t\r]").unwrap()
+fn get_sentence_space_regex() -> &'static Regex {
+    static SENTENCE_SPACE_REGEX: OnceLock<Regex> = OnceLock::new();
+    SENTENCE_SPACE_REGEX.get_or_init(|| Regex::new(r"^([\.!?])[\n\t\r]").unwrap())
+}
+
+/// A word, sentence or phrase with the `(start, end)` byte offsets, with an exclusive end, of its
+/// first and last token in the text.
+pub type WithOffsets = (String, (usize, usize));
+
+/// A word or phrase with the index of the sentence of its first token, among the sentences of
+/// `Tokenizer::split_into_sentences`.
+pub type WithSentence = (String, usize);
+
+/// The kind of a token of the text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// A token with letters, e.g. "rust", "c++" or "3d".
+    Word,
+    /// A number, e.g. "42", "3.14" or "2024-01-01".
+    Number,
+    /// A punctuation mark of the tokenizer's punctuation.
+    Punctuation,
+    /// Any other token, e.g. "€", "©" or an emoji.
+    Symbol,
 }
 
+fn count_word(mut counts: HashMap<String, usize>, word: String) -> HashMap<String, usize> {
+    *counts.entry(word).or_default() += 1;
+    counts
+}
+
+#[allow(clippy::too_many_arguments)]
 fn create_phrase(
-    mut phrases: Vec<String>,
-    mut phrase: String,
-    base_word: &str,
+    mut phrases: Vec<WithOffsets>,
+    (mut phrase, mut offsets): WithOffsets,
+    (start, base_word): (usize, &str),
+    in_stop_phrase: bool,
     special_char_regex: &Regex,
     punctuation: &HashSet<String>,
     stopwords: &HashSet<String>,
+    normalizer: &WordNormalizer,
     length: Option<usize>,
-) -> (Vec<String>, String) {
-    let word = special_char_regex
-        .replace_all(base_word.trim(), "")
-        .to_lowercase();
+) -> (Vec<WithOffsets>, WithOffsets) {
+    let word = normalizer.clean_word(base_word, special_char_regex);
 
     if !is_punctuation(&word, punctuation) {
-        if stopwords.contains(&word) {
-            if !phrase.is_empty() {
-                phrases.push(phrase);
-                phrase = String::new();
+        match normalizer.filter_word(word) {
+            Some(word)
+                if !in_stop_phrase
+                    && !stopwords.contains(&word)
+                    && !normalizer.is_dropped(&word) =>
+            {
+                if phrase.is_empty() {
+                    offsets.0 = start;
+                } else {
+                    phrase.push(' ');
+                }
+
+                phrase.push_str(&normalizer.normalize(word));
+                offsets.1 = start + base_word.len();
             }
-        } else {
-            if !phrase.is_empty() {
-                phrase.push(' ');
+            _ => {
+                if !phrase.is_empty() {
+                    phrases.push((phrase, offsets));
+                    phrase = String::new();
+                }
             }
-
-            phrase.push_str(&word);
         }
     }
     if let Some(length) = length {
         if phrase.split_whitespace().count() >= length {
-            phrases.push(phrase);
+            phrases.push((phrase, offsets));
             phrase = String::new();
         }
     }
 
-    (phrases, phrase)
+    (phrases, (phrase, offsets))
 }
 
 fn process_sentences(
@@ -79,10 +260,12 @@ fn process_sentences(
     special_char_regex: &Regex,
     punctuation: &HashSet<String>,
     stopwords: &HashSet<String>,
+    normalizer: &WordNormalizer,
 ) -> String {
-    sentence
-        .split_word_bounds()
-        .filter_map(|w| process_word(w, special_char_regex, stopwords, punctuation))
+    normalizer
+        .split_words(sentence)
+        .into_iter()
+        .filter_map(|w| process_word(w, special_char_regex, stopwords, punctuation, normalizer))
         .collect::<Vec<String>>()
         .join(" ")
 }
@@ -92,6 +275,7 @@ fn process_paragraphs(
     special_char_regex: &Regex,
     punctuation: &HashSet<String>,
     stopwords: &HashSet<String>,
+    normalizer: &WordNormalizer,
 ) -> Option<String> {
     if paragraph.trim().is_empty() {
         return None;
@@ -101,8 +285,12 @@ fn process_paragraphs(
         paragraph
             .unicode_sentences()
             .map(|s| {
-                s.split_word_bounds()
-                    .filter_map(|w| process_word(w, special_char_regex, stopwords, punctuation))
+                normalizer
+                    .split_words(s)
+                    .into_iter()
+                    .filter_map(|w| {
+                        process_word(w, special_char_regex, stopwords, punctuation, normalizer)
+                    })
                     .collect::<Vec<String>>()
                     .join(" ")
             })
@@ -114,12 +302,20 @@ fn process_paragraphs(
 impl Tokenizer {
     /// Create a new Tokenizer instance.
     pub fn new(text: Text, stopwords: Stopwords, punctuation: Punctuation) -> Self {
+        Self::from_stopword_set(text, &StopwordSet::new(stopwords), punctuation)
+    }
+
+    /// Create a new Tokenizer instance sharing a set of stopwords built once, instead of copying
+    /// them for every text. The set is only copied by the text normalization options if they change
+    /// its stopwords, see `TokenizerBuilder` to normalize them once for many texts.
+    pub fn from_stopword_set(
+        text: Text,
+        stopwords: &StopwordSet,
+        punctuation: Punctuation,
+    ) -> Self {
         Self {
             text: text.to_owned(),
-            stopwords: stopwords
-                .iter()
-                .map(|s| s.to_owned())
-                .collect::<HashSet<String>>(),
+            stopwords: stopwords.clone(),
             punctuation: punctuation
                 .unwrap_or(
                     &PUNCTUATION
@@ -130,30 +326,396 @@ impl Tokenizer {
                 .iter()
                 .map(|s| s.to_string())
                 .collect::<HashSet<String>>(),
+            stop_phrases: HashMap::new(),
+            normalizer: WordNormalizer::default(),
+            special_char_regex: get_special_char_regex().clone(),
+            paragraph_split: ParagraphSplit::default(),
+        }
+    }
+
+    /// Creates a tokenizer with the stopwords of a language from the `stop-words` crate.
+    #[cfg(feature = "stop-words")]
+    pub fn from_language(text: Text, language: StopwordLanguage, punctuation: Punctuation) -> Self {
+        Self::from_stopword_set(text, &StopwordSet::from_language(language), punctuation)
+    }
+
+    /// Creates a tokenizer with the stopwords of the language detected in the text, stemming its
+    /// words with the `stemming` feature, or without stopwords if it cannot be detected.
+    #[cfg(feature = "lang-detect")]
+    pub fn from_detected_language(text: Text, punctuation: Punctuation) -> Self {
+        let Some(detected) = DetectedLanguage::detect(text) else {
+            return Self::new(text, &[], punctuation);
+        };
+        let tokenizer = Self::from_stopword_set(text, &detected.stopwords, punctuation);
+
+        #[cfg(feature = "stemming")]
+        if let Some(language) = detected.stemming {
+            return tokenizer.with_stemming(language);
+        }
+
+        tokenizer
+    }
+
+    /// Sets the regex of the characters removed from every word before it is lowercased, by default
+    /// the possessives and the commas and periods around the word.
+    pub fn with_special_char_regex(mut self, special_char_regex: Regex) -> Self {
+        self.special_char_regex = special_char_regex;
+        self
+    }
+
+    /// Sets the patterns of the characters removed from every word, matched as alternatives of a
+    /// single regex, failing if any of them is not a valid regex.
+    pub fn with_special_char_patterns(self, patterns: &[&str]) -> Result<Self, RegexError> {
+        let pattern = patterns
+            .iter()
+            .map(|pattern| format!("(?:{pattern})"))
+            .collect::<Vec<String>>()
+            .join("|");
+
+        Ok(self.with_special_char_regex(Regex::new(&pattern)?))
+    }
+
+    /// Drops the words matching any of the regex patterns like stopwords, e.g. `^\d+(px|em)$` or
+    /// `^[a-f0-9]{32}$`, failing if any of them is not a valid regex. The words are matched once
+    /// cleaned and lowercased.
+    pub fn with_stopword_patterns(mut self, patterns: &[&str]) -> Result<Self, RegexError> {
+        self.normalizer.stopword_patterns = Some(RegexSet::new(patterns)?);
+        Ok(self)
+    }
+
+    /// Sets how the text is split into paragraphs, on every line by default.
+    pub fn with_paragraph_split(mut self, paragraph_split: ParagraphSplit) -> Self {
+        self.paragraph_split = paragraph_split;
+        self
+    }
+
+    /// Stems every word with the Snowball stemmer of the given language, so the forms of a word, e.g.
+    /// "connected", "connecting" and "connection", are counted as one.
+    #[cfg(feature = "stemming")]
+    pub fn with_stemming(mut self, language: StemmingLanguage) -> Self {
+        self.normalizer.stemming = Some(language);
+        self
+    }
+
+    /// Normalizes every lowercased word that is not a stopword with a custom function, e.g. a
+    /// lemmatizer, applied before stemming.
+    pub fn with_word_normalizer<F>(mut self, normalizer: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.normalizer.custom = Some(Arc::new(normalizer));
+        self
+    }
+
+    /// Drops the words with fewer characters, e.g. 3 to drop the 1 and 2 character fragments, which
+    /// break phrases like stopwords.
+    pub fn with_min_word_length(mut self, min_word_length: usize) -> Self {
+        self.normalizer.min_word_length = min_word_length;
+        self
+    }
+
+    /// Drops or truncates the words longer than `max_word_length` characters, e.g. base64 blobs
+    /// and minified code. The dropped words break phrases like stopwords.
+    pub fn with_max_word_length(mut self, max_word_length: usize, policy: LongWordPolicy) -> Self {
+        self.normalizer.max_word_length = Some(max_word_length);
+        self.normalizer.long_word_policy = policy;
+        self
+    }
+
+    /// Sets how the numbers, e.g. figures, years and IDs, are handled, kept by default.
+    pub fn with_number_policy(mut self, number_policy: NumberPolicy) -> Self {
+        self.normalizer.number_policy = number_policy;
+        self
+    }
+
+    /// Sets how the emoji and emoticons are handled, kept by default.
+    pub fn with_emoji_policy(mut self, emoji_policy: EmojiPolicy) -> Self {
+        self.normalizer.emoji_policy = emoji_policy;
+        self.normalize_text();
+        self
+    }
+
+    /// Sets how the hyphenated words, e.g. "state-of-the-art", are split, at the hyphens by default.
+    pub fn with_hyphen_policy(mut self, hyphen_policy: HyphenPolicy) -> Self {
+        self.normalizer.hyphen_policy = hyphen_policy;
+        self
+    }
+
+    /// Expands the contractions, e.g. "don't" into "do not" and "it's" into "it is", with the English
+    /// ones of `CONTRACTIONS` replaced or extended by the given ones. Possessives not in the table,
+    /// e.g. "John's", are still removed by the special-character regex.
+    pub fn with_contraction_expansion(mut self, overrides: &[(&str, &str)]) -> Self {
+        self.normalizer.contractions = Some(WordNormalizer::get_contractions(overrides));
+        self.normalize_text();
+        self
+    }
+
+    /// Sets how a kind of web or social media token, e.g. URLs or hashtags, is handled, split on its
+    /// word bounds by default.
+    pub fn with_entity_policy(mut self, kind: EntityKind, policy: EntityPolicy) -> Self {
+        self.normalizer.entity_policies.set(kind, policy);
+        self
+    }
+
+    /// Applies a Unicode normalization form to the text and stopwords, so the same words written
+    /// with different code points, e.g. composed and decomposed accents or full-width letters, are
+    /// merged.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn with_unicode_normalization(mut self, form: UnicodeNormalization) -> Self {
+        self.normalizer.unicode_normalization = Some(form);
+        self.normalize_text();
+        self
+    }
+
+    /// Adjusts the Unicode word bounds the text is split on, e.g. to split the French elisions or
+    /// keep "AT&T" as a single word.
+    pub fn with_word_boundary_rules(mut self, word_boundary_rules: WordBoundaryRules) -> Self {
+        self.normalizer.word_boundary_rules = word_boundary_rules;
+        self
+    }
+
+    /// Filters or transforms every cleaned, lowercased word before the stopwords are checked, e.g.
+    /// to drop gene IDs or mask personal data. The dropped words break phrases like stopwords.
+    pub fn with_token_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.normalizer.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Removes the accents of the text and stopwords by dropping the combining marks of their
+    /// canonical decomposition, e.g. "café" and "naïve" become "cafe" and "naive".
+    #[cfg(feature = "unicode-normalization")]
+    pub fn with_diacritic_folding(mut self) -> Self {
+        self.normalizer.fold_diacritics = true;
+        self.normalize_text();
+        self
+    }
+
+    /// Adds stopwords to the tokenizer, normalized like its text, without copying the stopwords
+    /// shared with other tokenizers.
+    pub fn add_stopwords(&mut self, stopwords: Stopwords) {
+        let stopwords = self.normalize_stopwords(stopwords);
+        self.stopwords.add_stopwords(&stopwords);
+    }
+
+    /// Removes stopwords from the tokenizer, without copying the stopwords shared with other
+    /// tokenizers.
+    pub fn remove_stopwords(&mut self, stopwords: Stopwords) {
+        let stopwords = self.normalize_stopwords(stopwords);
+        self.stopwords.remove_stopwords(&stopwords);
+    }
+
+    /// Adds stopwords to the tokenizer, see `Tokenizer::add_stopwords`.
+    pub fn with_extra_stopwords(mut self, stopwords: Stopwords) -> Self {
+        self.add_stopwords(stopwords);
+        self
+    }
+
+    fn normalize_stopwords(&self, stopwords: Stopwords) -> Vec<String> {
+        stopwords
+            .iter()
+            .map(|stopword| self.normalizer.normalize_text(stopword).into_owned())
+            .collect()
+    }
+
+    /// Sets how the words are normalized, shared with the processors of other documents whose
+    /// stopwords are already normalized.
+    pub(crate) fn with_normalizer(mut self, normalizer: WordNormalizer) -> Self {
+        self.normalizer = normalizer;
+        self.text = self.normalizer.normalize_text(&self.text).into_owned();
+        self
+    }
+
+    fn normalize_text(&mut self) {
+        if !self.normalizer.is_text_normalized() {
+            return;
+        }
+
+        self.text = self.normalizer.normalize_text(&self.text).into_owned();
+        self.stopwords.normalize(&self.normalizer);
+    }
+
+    /// Creates a builder of tokenizers with the default options, no stopwords and the default
+    /// punctuation.
+    pub fn builder() -> TokenizerBuilder {
+        TokenizerBuilder::default()
+    }
+
+    /// Creates a reader of the text of `reader` in chunks of at most 64 KiB, each ending at a
+    /// sentence bound, so very large files are tokenized without being loaded into a `String`.
+    pub fn from_reader<R: Read>(
+        reader: R,
+        stopwords: Stopwords,
+        punctuation: Punctuation,
+    ) -> TokenizerReader<R> {
+        Self::new("", stopwords, punctuation).into_reader(reader)
+    }
+
+    /// Creates a reader of the text of `reader` in chunks with the options of this tokenizer,
+    /// ignoring its text.
+    pub fn into_reader<R: Read>(self, reader: R) -> TokenizerReader<R> {
+        TokenizerReader {
+            reader,
+            tokenizer: self,
+            buffer: Vec::new(),
+            buffer_size: 64 * 1024,
+            is_finished: false,
+        }
+    }
+
+    /// Sets multi-word stop phrases, e.g. "for example" or "in order to", that break phrases like stopwords do.
+    pub fn with_stop_phrases(mut self, stop_phrases: &[String]) -> Self {
+        let special_char_regex = &self.special_char_regex;
+
+        stop_phrases.iter().for_each(|stop_phrase| {
+            let words = stop_phrase
+                .split_word_bounds()
+                .map(|w| special_char_regex.replace_all(w.trim(), "").to_lowercase())
+                .filter(|w| !is_punctuation(w, &self.punctuation))
+                .collect::<Vec<String>>();
+
+            if let Some(first) = words.first() {
+                self.stop_phrases
+                    .entry(first.to_string())
+                    .or_default()
+                    .push(words);
+            }
+        });
+        self.stop_phrases
+            .values_mut()
+            .for_each(|candidates| candidates.sort_by_key(|words| std::cmp::Reverse(words.len())));
+        self
+    }
+
+    /// Marks the tokens that belong to a stop phrase, matching the longest stop phrase first.
+    fn get_stop_phrase_mask(&self, tokens: &[&str], special_char_regex: &Regex) -> Vec<bool> {
+        let mut mask = vec![false; tokens.len()];
+
+        if self.stop_phrases.is_empty() {
+            return mask;
+        }
+
+        let words = tokens
+            .iter()
+            .enumerate()
+            .filter_map(|(i, w)| {
+                let word = self.normalizer.clean_word(w, special_char_regex);
+                if word.is_empty() {
+                    None
+                } else {
+                    Some((i, word))
+                }
+            })
+            .collect::<Vec<(usize, String)>>();
+        let mut i = 0;
+
+        while i < words.len() {
+            let matched = self.stop_phrases.get(&words[i].1).and_then(|candidates| {
+                candidates.iter().find(|stop_phrase| {
+                    stop_phrase.len() <= words.len() - i
+                        && stop_phrase
+                            .iter()
+                            .zip(&words[i..])
+                            .all(|(stop_word, (_, word))| stop_word == word)
+                })
+            });
+
+            match matched {
+                Some(stop_phrase) => {
+                    words[i..i + stop_phrase.len()]
+                        .iter()
+                        .for_each(|(token, _)| mask[*token] = true);
+                    i += stop_phrase.len();
+                }
+                None => i += 1,
+            }
+        }
+
+        mask
+    }
+
+    /// Gets the most frequent original form of every word, the first one seen on ties.
+    pub fn get_surface_forms(&self) -> SurfaceForms {
+        let special_char_regex = &self.special_char_regex;
+        let mut counts = HashMap::<String, Vec<(String, usize)>>::new();
+
+        self.normalizer
+            .split_words(&self.text)
+            .into_iter()
+            .for_each(|w| {
+                if let Some(word) = process_word(
+                    w,
+                    special_char_regex,
+                    &self.stopwords,
+                    &self.punctuation,
+                    &self.normalizer,
+                ) {
+                    let form = special_char_regex.replace_all(w.trim(), "").to_string();
+                    let forms = counts.entry(word).or_default();
+
+                    match forms.iter_mut().find(|(seen, _)| *seen == form) {
+                        Some((_, count)) => *count += 1,
+                        None => forms.push((form, 1)),
+                    }
+                }
+            });
+
+        SurfaceForms {
+            forms: counts
+                .into_iter()
+                .map(|(word, forms)| {
+                    let (form, _) =
+                        forms
+                            .into_iter()
+                            .fold((String::new(), 0), |best, (form, count)| {
+                                if count > best.1 {
+                                    (form, count)
+                                } else {
+                                    best
+                                }
+                            });
+                    (word, form)
+                })
+                .collect(),
         }
     }
 
     /// Split text into words by splitting on word bounds.
     pub fn split_into_words(&self) -> Vec<String> {
-        let special_char_regex = get_special_char_regex();
+        let special_char_regex = &self.special_char_regex;
 
         #[cfg(feature = "parallel")]
         {
-            self.text
-                .split_word_bounds()
-                .par_bridge()
+            self.normalizer
+                .split_words(&self.text)
+                .into_par_iter()
                 .filter_map(|w| {
-                    process_word(w, &special_char_regex, &self.stopwords, &self.punctuation)
+                    process_word(
+                        w,
+                        special_char_regex,
+                        &self.stopwords,
+                        &self.punctuation,
+                        &self.normalizer,
+                    )
                 })
                 .collect::<Vec<String>>()
         }
 
         #[cfg(not(feature = "parallel"))]
         {
-            self.text
-                .split_word_bounds()
+            self.normalizer
+                .split_words(&self.text)
+                .into_iter()
                 .filter_map(|w| {
-                    process_word(w, &special_char_regex, &self.stopwords, &self.punctuation)
+                    process_word(
+                        w,
+                        special_char_regex,
+                        &self.stopwords,
+                        &self.punctuation,
+                        &self.normalizer,
+                    )
                 })
                 .collect::<Vec<String>>()
         }
@@ -161,18 +723,133 @@ impl Tokenizer {
 
     /// Split text into words by splitting on word bounds (always synchronous even with parallel flag).
     pub fn sync_split_into_words(&self) -> Vec<String> {
-        let special_char_regex = get_special_char_regex();
-        self.text
-            .split_word_bounds()
+        let special_char_regex = &self.special_char_regex;
+        self.normalizer
+            .split_words(&self.text)
+            .into_iter()
             .filter_map(|w| {
-                process_word(w, &special_char_regex, &self.stopwords, &self.punctuation)
+                process_word(
+                    w,
+                    special_char_regex,
+                    &self.stopwords,
+                    &self.punctuation,
+                    &self.normalizer,
+                )
             })
             .collect::<Vec<String>>()
     }
 
+    /// Split text into the WordPiece subwords of its words, e.g. "keywords" into "key", "##word"
+    /// and "##s", aligned with the tokens of transformer-based systems (always synchronous even
+    /// with parallel flag).
+    pub fn split_into_subwords(&self, vocabulary: &WordPieceVocabulary) -> Vec<String> {
+        self.sync_split_into_words()
+            .into_iter()
+            .flat_map(|word| vocabulary.split_word(&word))
+            .collect()
+    }
+
+    /// Counts the occurrences of every word of `split_into_words` without collecting them.
+    pub fn word_frequencies(&self) -> HashMap<String, usize> {
+        #[cfg(feature = "parallel")]
+        {
+            let special_char_regex = &self.special_char_regex;
+            self.normalizer
+                .split_words(&self.text)
+                .into_par_iter()
+                .filter_map(|w| {
+                    process_word(
+                        w,
+                        special_char_regex,
+                        &self.stopwords,
+                        &self.punctuation,
+                        &self.normalizer,
+                    )
+                })
+                .fold(HashMap::new, count_word)
+                .reduce(HashMap::new, |mut counts, other| {
+                    other.into_iter().for_each(|(word, count)| {
+                        *counts.entry(word).or_default() += count;
+                    });
+                    counts
+                })
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.sync_word_frequencies()
+        }
+    }
+
+    /// Counts the occurrences of every word of `split_into_words` (always synchronous even with
+    /// parallel flag).
+    pub fn sync_word_frequencies(&self) -> HashMap<String, usize> {
+        let special_char_regex = &self.special_char_regex;
+        self.normalizer
+            .split_words(&self.text)
+            .into_iter()
+            .filter_map(|w| {
+                process_word(
+                    w,
+                    special_char_regex,
+                    &self.stopwords,
+                    &self.punctuation,
+                    &self.normalizer,
+                )
+            })
+            .fold(HashMap::new(), count_word)
+    }
+
+    /// Split text into words by splitting on word bounds, with the byte offsets of every word in the
+    /// text (always synchronous even with parallel flag).
+    pub fn split_into_words_with_offsets(&self) -> Vec<WithOffsets> {
+        let special_char_regex = &self.special_char_regex;
+        self.normalizer
+            .split_word_indices(&self.text)
+            .into_iter()
+            .filter_map(|(start, w)| {
+                process_word(
+                    w,
+                    special_char_regex,
+                    &self.stopwords,
+                    &self.punctuation,
+                    &self.normalizer,
+                )
+                .map(|word| (word, (start, start + w.len())))
+            })
+            .collect()
+    }
+
+    /// Split text into all its tokens, including the stopwords and punctuation, each with its kind
+    /// (always synchronous even with parallel flag). The words and numbers are cleaned and
+    /// lowercased, the other tokens are kept as they are.
+    pub fn classify_tokens(&self) -> Vec<(String, TokenKind)> {
+        let special_char_regex = &self.special_char_regex;
+        self.normalizer
+            .split_words(&self.text)
+            .into_iter()
+            .filter(|token| !token.trim().is_empty())
+            .map(|token| {
+                let word = self.normalizer.clean_word(token, special_char_regex);
+
+                if is_punctuation(&word, &self.punctuation)
+                    || is_punctuation(token, &self.punctuation)
+                {
+                    (token.to_string(), TokenKind::Punctuation)
+                } else if is_number(&word) {
+                    (word, TokenKind::Number)
+                } else if word.chars().any(char::is_alphanumeric) {
+                    (word, TokenKind::Word)
+                } else {
+                    (token.to_string(), TokenKind::Symbol)
+                }
+            })
+            .collect()
+    }
+
     /// Split text into unicode sentences by splitting on punctuation.
     pub fn split_into_sentences(&self) -> Vec<String> {
-        let special_char_regex = get_special_char_regex();
+        let special_char_regex = &self.special_char_regex;
 
         #[cfg(feature = "parallel")]
         {
@@ -180,7 +857,13 @@ impl Tokenizer {
                 .unicode_sentences()
                 .par_bridge()
                 .map(|s| {
-                    process_sentences(s, &special_char_regex, &self.punctuation, &self.stopwords)
+                    process_sentences(
+                        s,
+                        special_char_regex,
+                        &self.punctuation,
+                        &self.stopwords,
+                        &self.normalizer,
+                    )
                 })
                 .collect::<Vec<String>>()
         }
@@ -190,7 +873,13 @@ impl Tokenizer {
             self.text
                 .unicode_sentences()
                 .map(|s| {
-                    process_sentences(s, &special_char_regex, &self.punctuation, &self.stopwords)
+                    process_sentences(
+                        s,
+                        special_char_regex,
+                        &self.punctuation,
+                        &self.stopwords,
+                        &self.normalizer,
+                    )
                 })
                 .collect::<Vec<String>>()
         }
@@ -198,52 +887,121 @@ impl Tokenizer {
 
     /// Split text into unicode sentences (always synchronous even with parallel flag).
     pub fn sync_split_into_sentences(&self) -> Vec<String> {
-        let special_char_regex = get_special_char_regex();
+        let special_char_regex = &self.special_char_regex;
         self.text
             .unicode_sentences()
-            .map(|s| process_sentences(s, &special_char_regex, &self.punctuation, &self.stopwords))
+            .map(|s| {
+                process_sentences(
+                    s,
+                    special_char_regex,
+                    &self.punctuation,
+                    &self.stopwords,
+                    &self.normalizer,
+                )
+            })
             .collect::<Vec<String>>()
     }
 
     /// Split text into phrases by splitting on stopwords.
     pub fn split_into_phrases(&self, length: PhraseLength) -> Vec<String> {
-        let special_char_regex = get_special_char_regex();
+        let special_char_regex = &self.special_char_regex;
 
         #[cfg(feature = "parallel")]
         {
-            self.parallel_phrase_split(&special_char_regex, length)
+            self.parallel_phrase_split(special_char_regex, length)
         }
 
         #[cfg(not(feature = "parallel"))]
         {
-            self.basic_phrase_split(&special_char_regex, length)
+            self.basic_phrase_split(special_char_regex, length)
         }
     }
 
     /// Split text into words by splitting on word bounds (always synchronous even with parallel flag).
     pub fn sync_split_into_phrases(&self, length: Option<usize>) -> Vec<String> {
-        let special_char_regex = get_special_char_regex();
+        let special_char_regex = &self.special_char_regex;
 
-        self.basic_phrase_split(&special_char_regex, length)
+        self.basic_phrase_split(special_char_regex, length)
     }
 
     fn basic_phrase_split(&self, special_char_regex: &Regex, length: Option<usize>) -> Vec<String> {
-        let (mut phrases, last_phrase) = self.text.split_word_bounds().fold(
-            (Vec::<String>::new(), String::new()),
-            |(phrases, acc), w| {
+        self.split_phrases_with_offsets(&self.text, special_char_regex, length)
+            .into_iter()
+            .map(|(phrase, _)| phrase)
+            .collect()
+    }
+
+    /// Split text into phrases by splitting on stopwords, with the byte offsets of every phrase in
+    /// the text (always synchronous even with parallel flag). The offsets are of the text as it is
+    /// tokenized, after the emoticon replacement, contraction expansion and Unicode normalization, if
+    /// any.
+    pub fn split_into_phrases_with_offsets(&self, length: PhraseLength) -> Vec<WithOffsets> {
+        let special_char_regex = &self.special_char_regex;
+
+        self.split_phrases_with_offsets(&self.text, special_char_regex, length)
+    }
+
+    /// Split text into words by splitting on word bounds, with the index of the sentence of every
+    /// word (always synchronous even with parallel flag).
+    pub fn split_into_words_with_sentences(&self) -> Vec<WithSentence> {
+        self.add_sentence_indexes(self.split_into_words_with_offsets())
+    }
+
+    /// Split text into phrases by splitting on stopwords, with the index of the sentence of the
+    /// first word of every phrase, as phrases are not broken by sentence ends (always synchronous
+    /// even with parallel flag).
+    pub fn split_into_phrases_with_sentences(&self, length: PhraseLength) -> Vec<WithSentence> {
+        self.add_sentence_indexes(self.split_into_phrases_with_offsets(length))
+    }
+
+    fn add_sentence_indexes(&self, terms: Vec<WithOffsets>) -> Vec<WithSentence> {
+        let sentence_starts = self
+            .text
+            .split_sentence_bound_indices()
+            .filter(|(_, sentence)| sentence.chars().any(char::is_alphanumeric))
+            .map(|(start, _)| start)
+            .collect::<Vec<usize>>();
+
+        terms
+            .into_iter()
+            .map(|(term, (start, _))| {
+                let sentence = sentence_starts
+                    .partition_point(|sentence_start| *sentence_start <= start)
+                    .saturating_sub(1);
+                (term, sentence)
+            })
+            .collect()
+    }
+
+    fn split_phrases_with_offsets(
+        &self,
+        text: &str,
+        special_char_regex: &Regex,
+        length: Option<usize>,
+    ) -> Vec<WithOffsets> {
+        let tokens = self.normalizer.split_word_indices(text);
+        let mask = self.get_stop_phrase_mask(
+            &tokens.iter().map(|(_, w)| *w).collect::<Vec<&str>>(),
+            special_char_regex,
+        );
+        let (mut phrases, last_phrase) = tokens.into_iter().zip(mask).fold(
+            (Vec::<WithOffsets>::new(), (String::new(), (0, 0))),
+            |(phrases, acc), (token, in_stop_phrase)| {
                 create_phrase(
                     phrases,
                     acc,
-                    w,
+                    token,
+                    in_stop_phrase,
                     special_char_regex,
                     &self.punctuation,
                     &self.stopwords,
+                    &self.normalizer,
                     length,
                 )
             },
         );
 
-        if !last_phrase.is_empty() {
+        if !last_phrase.0.is_empty() {
             phrases.push(last_phrase);
         }
 
@@ -259,52 +1017,237 @@ impl Tokenizer {
         get_sentence_space_regex()
             .replace_all(&self.text, "¶")
             .par_split('¶')
-            .map(|s| {
-                let (mut phrases, last_phrase) = s.split_word_bounds().fold(
-                    (Vec::<String>::new(), String::new()),
-                    |(phrases, acc), w| {
-                        create_phrase(
-                            phrases,
-                            acc,
-                            w,
-                            &special_char_regex,
-                            &self.punctuation,
-                            &self.stopwords,
-                            length,
-                        )
-                    },
-                );
-
-                if !last_phrase.is_empty() {
-                    phrases.push(last_phrase);
+            .flat_map_iter(|s| {
+                self.split_phrases_with_offsets(s, special_char_regex, length)
+                    .into_iter()
+                    .map(|(phrase, _)| phrase)
+            })
+            .collect::<Vec<String>>()
+    }
+
+    /// Gets the phrases made of two stopword separated phrases joined with their interior stopwords,
+    /// e.g. "axis of evil", that are seen at least `min_frequency` times, in order of first appearance.
+    pub fn split_into_adjoined_phrases(&self, min_frequency: usize) -> Vec<String> {
+        let special_char_regex = &self.special_char_regex;
+        let mut adjoined = Vec::<(String, usize)>::new();
+        let mut adjoined_indexes = HashMap::<String, usize>::new();
+        let mut previous: Option<String> = None;
+        let mut interior = Vec::<String>::new();
+        let mut phrase = String::new();
+        let mut prefix: Option<String> = None;
+        let mut adjoinable = true;
+        let mut finish_phrase = |phrase: &mut String,
+                                 prefix: &mut Option<String>,
+                                 adjoinable: &mut bool|
+         -> Option<String> {
+            if let Some(prefix) = prefix.take() {
+                let candidate = format!("{} {}", prefix, phrase);
+
+                match adjoined_indexes.get(&candidate) {
+                    Some(i) => adjoined[*i].1 += 1,
+                    None => {
+                        adjoined_indexes.insert(candidate.to_string(), adjoined.len());
+                        adjoined.push((candidate, 1));
+                    }
+                }
+            }
+
+            let finished = std::mem::take(phrase);
+            let left = if *adjoinable { Some(finished) } else { None };
+            *adjoinable = true;
+            left
+        };
+
+        let tokens = self.normalizer.split_words(&self.text);
+        let mask = self.get_stop_phrase_mask(&tokens, special_char_regex);
+
+        for (w, in_stop_phrase) in tokens.into_iter().zip(mask) {
+            let word = self.normalizer.clean_word(w, special_char_regex);
+
+            if word.is_empty() {
+                continue;
+            }
+
+            let (word, in_stop_phrase) =
+                if in_stop_phrase || is_punctuation(&word, &self.punctuation) {
+                    (word, in_stop_phrase)
+                } else {
+                    match self.normalizer.filter_word(word) {
+                        Some(word) => (word, false),
+                        // the filtered out words break the phrases like stop phrases
+                        None => (String::new(), true),
+                    }
+                };
+
+            if in_stop_phrase {
+                if !phrase.is_empty() {
+                    finish_phrase(&mut phrase, &mut prefix, &mut adjoinable);
+                }
+
+                previous = None;
+                interior.clear();
+            } else if is_punctuation(&word, &self.punctuation) {
+                if phrase.is_empty() {
+                    previous = None;
+                    interior.clear();
+                } else {
+                    adjoinable = false;
+                }
+            } else if self.stopwords.contains(&word) || self.normalizer.is_dropped(&word) {
+                if !phrase.is_empty() {
+                    previous = finish_phrase(&mut phrase, &mut prefix, &mut adjoinable);
+                    interior.clear();
+                }
+
+                if previous.is_some() {
+                    interior.push(word);
+                }
+            } else {
+                if phrase.is_empty() {
+                    if let (Some(left), false) = (&previous, interior.is_empty()) {
+                        prefix = Some(format!("{} {}", left, interior.join(" ")));
+                    }
+
+                    previous = None;
+                    interior.clear();
+                } else {
+                    phrase.push(' ');
                 }
 
-                phrases
+                phrase.push_str(&self.normalizer.normalize(word));
+            }
+        }
+
+        if !phrase.is_empty() {
+            finish_phrase(&mut phrase, &mut prefix, &mut adjoinable);
+        }
+
+        adjoined
+            .into_iter()
+            .filter(|(_, frequency)| *frequency >= min_frequency)
+            .map(|(candidate, _)| candidate)
+            .collect()
+    }
+
+    /// Splits a sentence into its runs of words between stopwords, stop phrases and punctuation.
+    fn get_word_runs(&self, sentence: &str, special_char_regex: &Regex) -> Vec<Vec<String>> {
+        let tokens = self
+            .normalizer
+            .split_words(sentence)
+            .into_iter()
+            .filter(|w| !w.trim().is_empty())
+            .collect::<Vec<&str>>();
+        let mask = self.get_stop_phrase_mask(&tokens, special_char_regex);
+
+        tokens.into_iter().zip(mask).fold(
+            vec![Vec::<String>::new()],
+            |mut runs, (w, in_stop_phrase)| {
+                let word = if in_stop_phrase {
+                    None
+                } else {
+                    process_word(
+                        w,
+                        special_char_regex,
+                        &self.stopwords,
+                        &self.punctuation,
+                        &self.normalizer,
+                    )
+                };
+
+                match word {
+                    Some(word) => runs.last_mut().unwrap().push(word),
+                    None if runs.last().is_some_and(|run| !run.is_empty()) => runs.push(Vec::new()),
+                    None => {}
+                }
+                runs
+            },
+        )
+    }
+
+    /// Gets the contiguous n-grams of `min` to `max` words of a sentence, which never span stopwords,
+    /// stop phrases or punctuation.
+    fn get_sentence_ngrams(
+        &self,
+        sentence: &str,
+        (min, max): (usize, usize),
+        special_char_regex: &Regex,
+    ) -> Vec<String> {
+        let runs = self.get_word_runs(sentence, special_char_regex);
+        let longest = runs.iter().map(|run| run.len()).max().unwrap_or(0);
+
+        (min.max(1)..=max.min(longest))
+            .flat_map(|n| {
+                runs.iter()
+                    .flat_map(move |run| run.windows(n).map(|ngram| ngram.join(" ")))
             })
-            .flatten()
+            .collect()
+    }
+
+    /// Split text into the contiguous n-grams of `min` to `max` words of every sentence, by sentence
+    /// and then by size in order of appearance, which never span sentences, stopwords, stop phrases or punctuation.
+    pub fn split_into_ngrams(&self, ngram_range: (usize, usize)) -> Vec<String> {
+        #[cfg(feature = "parallel")]
+        {
+            let special_char_regex = &self.special_char_regex;
+            self.text
+                .unicode_sentences()
+                .collect::<Vec<&str>>()
+                .par_iter()
+                .flat_map(|s| self.get_sentence_ngrams(s, ngram_range, special_char_regex))
+                .collect::<Vec<String>>()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.sync_split_into_ngrams(ngram_range)
+        }
+    }
+
+    /// Split text into the n-grams of every sentence (always synchronous even with parallel flag).
+    pub fn sync_split_into_ngrams(&self, ngram_range: (usize, usize)) -> Vec<String> {
+        let special_char_regex = &self.special_char_regex;
+
+        self.text
+            .unicode_sentences()
+            .flat_map(|s| self.get_sentence_ngrams(s, ngram_range, special_char_regex))
             .collect::<Vec<String>>()
     }
 
-    /// Split text into paragraphs by splitting on newlines.
+    /// Split text into paragraphs, by default by splitting on newlines, see
+    /// `Tokenizer::with_paragraph_split`.
     pub fn split_into_paragraphs(&self) -> Vec<String> {
-        let special_char_regex = get_special_char_regex();
+        let special_char_regex = &self.special_char_regex;
 
         #[cfg(feature = "parallel")]
         {
-            self.text
-                .par_lines()
+            self.paragraph_split
+                .split(&self.text)
+                .into_par_iter()
                 .filter_map(|s| {
-                    process_paragraphs(s, &special_char_regex, &self.punctuation, &self.stopwords)
+                    process_paragraphs(
+                        s,
+                        special_char_regex,
+                        &self.punctuation,
+                        &self.stopwords,
+                        &self.normalizer,
+                    )
                 })
                 .collect::<Vec<String>>()
         }
 
         #[cfg(not(feature = "parallel"))]
         {
-            self.text
-                .lines()
+            self.paragraph_split
+                .split(&self.text)
+                .into_iter()
                 .filter_map(|s| {
-                    process_paragraphs(s, &special_char_regex, &self.punctuation, &self.stopwords)
+                    process_paragraphs(
+                        s,
+                        special_char_regex,
+                        &self.punctuation,
+                        &self.stopwords,
+                        &self.normalizer,
+                    )
                 })
                 .collect()
         }
@@ -312,12 +1255,303 @@ impl Tokenizer {
 
     /// Split text into paragraphs (always synchronous even with parallel flag).
     pub fn sync_split_into_paragraphs(&self) -> Vec<String> {
-        let special_char_regex = get_special_char_regex();
-        self.text
-            .lines()
+        let special_char_regex = &self.special_char_regex;
+        self.paragraph_split
+            .split(&self.text)
+            .into_iter()
             .filter_map(|s| {
-                process_paragraphs(s, &special_char_regex, &self.punctuation, &self.stopwords)
+                process_paragraphs(
+                    s,
+                    special_char_regex,
+                    &self.punctuation,
+                    &self.stopwords,
+                    &self.normalizer,
+                )
             })
             .collect()
     }
 }
+
+impl Tokenize for Tokenizer {
+    fn words(&self) -> Vec<String> {
+        self.sync_split_into_words()
+    }
+
+    fn sentences(&self) -> Vec<String> {
+        self.sync_split_into_sentences()
+    }
+
+    fn phrases(&self, length: PhraseLength) -> Vec<String> {
+        self.sync_split_into_phrases(length)
+    }
+
+    fn surface_forms(&self) -> SurfaceForms {
+        self.get_surface_forms()
+    }
+}
+
+/// Reads a text in chunks that end at sentence bounds, or at whitespace in very long sentences, so
+/// words are never split across chunks, yielding a tokenizer of every chunk.
+pub struct TokenizerReader<R: Read> {
+    reader: R,
+    tokenizer: Tokenizer,
+    buffer: Vec<u8>,
+    buffer_size: usize,
+    is_finished: bool,
+}
+
+impl<R: Read> TokenizerReader<R> {
+    /// Sets the maximum number of bytes of a chunk, 64 KiB by default.
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size.max(4);
+        self
+    }
+
+    fn fill_buffer(&mut self) -> io::Result<()> {
+        let mut length = self.buffer.len();
+        self.buffer.resize(self.buffer_size.max(length), 0);
+
+        while length < self.buffer.len() {
+            match self.reader.read(&mut self.buffer[length..]) {
+                Ok(0) => {
+                    self.is_finished = true;
+                    break;
+                }
+                Ok(read) => length += read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => {
+                    self.buffer.truncate(length);
+                    return Err(error);
+                }
+            }
+        }
+
+        self.buffer.truncate(length);
+        Ok(())
+    }
+
+    /// Gets the number of bytes of the next chunk, up to the last sentence bound or whitespace of
+    /// the valid UTF-8 of the buffer.
+    fn get_chunk_length(&self) -> io::Result<usize> {
+        let text = match std::str::from_utf8(&self.buffer) {
+            Ok(text) => text,
+            Err(error) if error.error_len().is_none() && !self.is_finished => {
+                // a character split by the end of the buffer
+                std::str::from_utf8(&self.buffer[..error.valid_up_to()]).unwrap()
+            }
+            Err(error) => return Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+        };
+
+        if self.is_finished {
+            return Ok(text.len());
+        }
+
+        let sentence_start = text
+            .split_sentence_bound_indices()
+            .map(|(start, _)| start)
+            .last()
+            .unwrap_or(0);
+
+        if sentence_start > 0 {
+            return Ok(sentence_start);
+        }
+
+        Ok(text
+            .rfind(char::is_whitespace)
+            .map_or(text.len(), |whitespace| whitespace + 1))
+    }
+}
+
+impl<R: Read> Iterator for TokenizerReader<R> {
+    type Item = io::Result<Tokenizer>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.is_finished {
+            if let Err(error) = self.fill_buffer() {
+                return Some(Err(error));
+            }
+        }
+
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        let length = match self.get_chunk_length() {
+            Ok(length) => length,
+            Err(error) => {
+                self.buffer.clear();
+                return Some(Err(error));
+            }
+        };
+        let chunk = String::from_utf8(self.buffer.drain(..length).collect()).unwrap();
+        let mut tokenizer = self.tokenizer.clone();
+        tokenizer.text = tokenizer.normalizer.normalize_text(&chunk).into_owned();
+
+        Some(Ok(tokenizer))
+    }
+}
+
+/// Builds tokenizers of many texts with the same options, set one by one instead of as positional
+/// arguments, e.g. `Tokenizer::builder().with_stopwords(&stop_words).with_min_word_length(3)`.
+/// The stopwords are normalized once, when the text normalization options are set, and shared by
+/// the built tokenizers.
+#[derive(Clone)]
+pub struct TokenizerBuilder {
+    tokenizer: Tokenizer,
+}
+
+impl Default for TokenizerBuilder {
+    fn default() -> Self {
+        Self {
+            tokenizer: Tokenizer::new("", &[], None),
+        }
+    }
+}
+
+impl TokenizerBuilder {
+    fn map(self, f: impl FnOnce(Tokenizer) -> Tokenizer) -> Self {
+        Self {
+            tokenizer: f(self.tokenizer),
+        }
+    }
+
+    /// Sets the stopwords, none by default.
+    pub fn with_stopwords(mut self, stopwords: Stopwords) -> Self {
+        self.tokenizer.stopwords = stopwords.iter().cloned().collect();
+        self.tokenizer.normalize_text();
+        self
+    }
+
+    /// Sets a set of stopwords built once, shared by the built tokenizers.
+    pub fn with_stopword_set(mut self, stopwords: &StopwordSet) -> Self {
+        self.tokenizer.stopwords = stopwords.clone();
+        self.tokenizer.normalize_text();
+        self
+    }
+
+    /// See [`Tokenizer::with_extra_stopwords`].
+    pub fn with_extra_stopwords(self, stopwords: Stopwords) -> Self {
+        self.map(|tokenizer| tokenizer.with_extra_stopwords(stopwords))
+    }
+
+    /// Sets the punctuation, `PUNCTUATION` by default.
+    pub fn with_punctuation(mut self, punctuation: &[String]) -> Self {
+        self.tokenizer.punctuation = punctuation.iter().cloned().collect();
+        self
+    }
+
+    /// See [`Tokenizer::with_stop_phrases`].
+    pub fn with_stop_phrases(self, stop_phrases: &[String]) -> Self {
+        self.map(|tokenizer| tokenizer.with_stop_phrases(stop_phrases))
+    }
+
+    /// See [`Tokenizer::with_special_char_regex`].
+    pub fn with_special_char_regex(self, special_char_regex: Regex) -> Self {
+        self.map(|tokenizer| tokenizer.with_special_char_regex(special_char_regex))
+    }
+
+    /// See [`Tokenizer::with_special_char_patterns`].
+    pub fn with_special_char_patterns(self, patterns: &[&str]) -> Result<Self, RegexError> {
+        Ok(Self {
+            tokenizer: self.tokenizer.with_special_char_patterns(patterns)?,
+        })
+    }
+
+    /// See [`Tokenizer::with_stopword_patterns`].
+    pub fn with_stopword_patterns(self, patterns: &[&str]) -> Result<Self, RegexError> {
+        Ok(Self {
+            tokenizer: self.tokenizer.with_stopword_patterns(patterns)?,
+        })
+    }
+
+    /// See [`Tokenizer::with_paragraph_split`].
+    pub fn with_paragraph_split(self, paragraph_split: ParagraphSplit) -> Self {
+        self.map(|tokenizer| tokenizer.with_paragraph_split(paragraph_split))
+    }
+
+    /// See [`Tokenizer::with_stemming`].
+    #[cfg(feature = "stemming")]
+    pub fn with_stemming(self, language: StemmingLanguage) -> Self {
+        self.map(|tokenizer| tokenizer.with_stemming(language))
+    }
+
+    /// See [`Tokenizer::with_word_normalizer`].
+    pub fn with_word_normalizer<F>(self, normalizer: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.map(|tokenizer| tokenizer.with_word_normalizer(normalizer))
+    }
+
+    /// See [`Tokenizer::with_word_boundary_rules`].
+    pub fn with_word_boundary_rules(self, word_boundary_rules: WordBoundaryRules) -> Self {
+        self.map(|tokenizer| tokenizer.with_word_boundary_rules(word_boundary_rules))
+    }
+
+    /// See [`Tokenizer::with_token_filter`].
+    pub fn with_token_filter<F>(self, filter: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.map(|tokenizer| tokenizer.with_token_filter(filter))
+    }
+
+    /// See [`Tokenizer::with_min_word_length`].
+    pub fn with_min_word_length(self, min_word_length: usize) -> Self {
+        self.map(|tokenizer| tokenizer.with_min_word_length(min_word_length))
+    }
+
+    /// See [`Tokenizer::with_max_word_length`].
+    pub fn with_max_word_length(self, max_word_length: usize, policy: LongWordPolicy) -> Self {
+        self.map(|tokenizer| tokenizer.with_max_word_length(max_word_length, policy))
+    }
+
+    /// See [`Tokenizer::with_number_policy`].
+    pub fn with_number_policy(self, number_policy: NumberPolicy) -> Self {
+        self.map(|tokenizer| tokenizer.with_number_policy(number_policy))
+    }
+
+    /// See [`Tokenizer::with_emoji_policy`].
+    pub fn with_emoji_policy(self, emoji_policy: EmojiPolicy) -> Self {
+        self.map(|tokenizer| tokenizer.with_emoji_policy(emoji_policy))
+    }
+
+    /// See [`Tokenizer::with_hyphen_policy`].
+    pub fn with_hyphen_policy(self, hyphen_policy: HyphenPolicy) -> Self {
+        self.map(|tokenizer| tokenizer.with_hyphen_policy(hyphen_policy))
+    }
+
+    /// See [`Tokenizer::with_contraction_expansion`].
+    pub fn with_contraction_expansion(self, overrides: &[(&str, &str)]) -> Self {
+        self.map(|tokenizer| tokenizer.with_contraction_expansion(overrides))
+    }
+
+    /// See [`Tokenizer::with_entity_policy`].
+    pub fn with_entity_policy(self, kind: EntityKind, policy: EntityPolicy) -> Self {
+        self.map(|tokenizer| tokenizer.with_entity_policy(kind, policy))
+    }
+
+    /// See [`Tokenizer::with_unicode_normalization`].
+    #[cfg(feature = "unicode-normalization")]
+    pub fn with_unicode_normalization(self, form: UnicodeNormalization) -> Self {
+        self.map(|tokenizer| tokenizer.with_unicode_normalization(form))
+    }
+
+    /// See [`Tokenizer::with_diacritic_folding`].
+    #[cfg(feature = "unicode-normalization")]
+    pub fn with_diacritic_folding(self) -> Self {
+        self.map(|tokenizer| tokenizer.with_diacritic_folding())
+    }
+
+    /// Builds a tokenizer of a text.
+    pub fn build(&self, text: Text) -> Tokenizer {
+        let mut tokenizer = self.tokenizer.clone();
+        tokenizer.text = tokenizer.normalizer.normalize_text(text).into_owned();
+        tokenizer
+    }
+
+    /// Builds a reader of the text of `reader` in chunks, see [`Tokenizer::from_reader`].
+    pub fn build_reader<R: Read>(&self, reader: R) -> TokenizerReader<R> {
+        self.tokenizer.clone().into_reader(reader)
+    }
+}
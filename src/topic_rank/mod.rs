@@ -0,0 +1,81 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+mod topic_rank_logic;
+pub mod topic_rank_params;
+#[cfg(feature = "multipartite_rank")]
+pub(crate) use topic_rank_logic::Candidates;
+pub(crate) use topic_rank_logic::TopicRankLogic;
+pub use topic_rank_params::TopicRankParams;
+
+use crate::{
+    common::{get_ranked_scores, get_ranked_strings},
+    tokenizer::Tokenizer,
+};
+
+pub struct TopicRank {
+    topic_rank: HashMap<String, f32>,
+    topics: HashMap<String, Vec<String>>,
+}
+
+impl TopicRank {
+    /// Create a new TopicRank instance.
+    pub fn new(params: TopicRankParams) -> Self {
+        let (text, stop_words, punctuation, threshold, damping, tol, phrase_length) =
+            params.get_params();
+        let tokenizer = Tokenizer::new(text, stop_words, punctuation);
+        let (topic_rank, topics) = TopicRankLogic::build_topic_rank(
+            tokenizer.sync_split_into_phrases(phrase_length),
+            threshold,
+            damping,
+            tol,
+        );
+
+        Self { topic_rank, topics }
+    }
+
+    /// Gets the score of the topic a phrase represents.
+    pub fn get_phrase_score(&self, phrase: &str) -> f32 {
+        *self.topic_rank.get(phrase).unwrap_or(&0.0)
+    }
+
+    /// Gets the top n topics with the highest score, each represented by its first appearing phrase.
+    pub fn get_ranked_phrases(&self, n: usize) -> Vec<String> {
+        get_ranked_strings(&self.topic_rank, n)
+    }
+
+    /// Gets the top n topics with the highest score, each represented by its first appearing phrase,
+    /// and their score.
+    pub fn get_ranked_phrase_scores(&self, n: usize) -> Vec<(String, f32)> {
+        get_ranked_scores(&self.topic_rank, n)
+    }
+
+    /// Gets all the candidate phrases of the topic represented by a phrase.
+    pub fn get_topic(&self, phrase: &str) -> Option<&Vec<String>> {
+        self.topics.get(phrase)
+    }
+
+    /// Gets the map of representative phrases to the candidate phrases of their topic.
+    pub fn get_topics_map(&self) -> &HashMap<String, Vec<String>> {
+        &self.topics
+    }
+
+    /// Gets the phrase scores map.
+    pub fn get_phrase_scores_map(&self) -> &HashMap<String, f32> {
+        &self.topic_rank
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    common::{average_linkage_clustering, jaccard_distance, s_stem},
+    text_rank::{Graph, TextRankLogic},
+};
+
+pub struct TopicRankLogic;
+
+/// The candidates of the text, in order of first appearance, with the word positions of every occurrence.
+pub type Candidates = Vec<(String, Vec<usize>)>;
+
+impl TopicRankLogic {
+    pub fn build_topic_rank(
+        phrases: Vec<String>,
+        threshold: f32,
+        damping: f32,
+        tol: f32,
+    ) -> (HashMap<String, f32>, HashMap<String, Vec<String>>) {
+        let candidates = Self::get_candidates(phrases);
+        let topics = Self::cluster_candidates(&candidates, threshold);
+        let (topic_rank, _) = TextRankLogic::rank_graph(
+            &Self::create_graph(&topics, &candidates),
+            damping,
+            tol,
+            None,
+        );
+        let topics = topics
+            .into_iter()
+            .map(|topic| {
+                (
+                    candidates[topic[0]].0.to_string(),
+                    topic
+                        .iter()
+                        .map(|i| candidates[*i].0.to_string())
+                        .collect::<Vec<String>>(),
+                )
+            })
+            .collect::<HashMap<String, Vec<String>>>();
+        (topic_rank, topics)
+    }
+
+    pub fn get_candidates(phrases: Vec<String>) -> Candidates {
+        let mut position = 0_usize;
+        let mut candidate_indexes = HashMap::<String, usize>::new();
+        let mut candidates = Candidates::new();
+
+        phrases.into_iter().for_each(|phrase| {
+            let length = phrase.split_whitespace().count();
+
+            match candidate_indexes.get(&phrase) {
+                Some(i) => candidates[*i].1.push(position),
+                None => {
+                    candidate_indexes.insert(phrase.to_string(), candidates.len());
+                    candidates.push((phrase, vec![position]));
+                }
+            }
+
+            position += length;
+        });
+
+        candidates
+    }
+
+    /// Groups candidates sharing stemmed words into topics, ordered by first appearance.
+    /// The first candidate of every topic is the one that appears first in the text.
+    pub fn cluster_candidates(candidates: &Candidates, threshold: f32) -> Vec<Vec<usize>> {
+        let stems = candidates
+            .iter()
+            .map(|(candidate, _)| {
+                candidate
+                    .split_whitespace()
+                    .map(s_stem)
+                    .collect::<HashSet<String>>()
+            })
+            .collect::<Vec<HashSet<String>>>();
+        let stems = stems
+            .iter()
+            .map(|words| words.iter().map(|w| w.as_str()).collect::<HashSet<&str>>())
+            .collect::<Vec<HashSet<&str>>>();
+
+        #[cfg(feature = "parallel")]
+        let distances = stems
+            .par_iter()
+            .map(|words1| {
+                stems
+                    .iter()
+                    .map(|words2| jaccard_distance(words1, words2))
+                    .collect::<Vec<f32>>()
+            })
+            .collect::<Vec<Vec<f32>>>();
+
+        #[cfg(not(feature = "parallel"))]
+        let distances = stems
+            .iter()
+            .map(|words1| {
+                stems
+                    .iter()
+                    .map(|words2| jaccard_distance(words1, words2))
+                    .collect::<Vec<f32>>()
+            })
+            .collect::<Vec<Vec<f32>>>();
+
+        average_linkage_clustering(&distances, threshold)
+    }
+
+    /// Sums the inverse distances between every occurrence of the candidates of two topics.
+    pub fn get_topic_weight(topic1: &[usize], topic2: &[usize], candidates: &Candidates) -> f32 {
+        topic1
+            .iter()
+            .flat_map(|i| {
+                topic2
+                    .iter()
+                    .map(move |j| (&candidates[*i].1, &candidates[*j].1))
+            })
+            .flat_map(|(positions1, positions2)| {
+                positions1
+                    .iter()
+                    .flat_map(move |p1| positions2.iter().map(move |p2| (*p1, *p2)))
+            })
+            .filter(|(p1, p2)| p1 != p2)
+            .map(|(p1, p2)| 1.0 / p1.abs_diff(p2) as f32)
+            .sum()
+    }
+
+    /// Creates the complete topic graph, where the weight between two topics is the sum of the
+    /// inverse distances between the occurrences of their candidates.
+    fn create_graph(topics: &[Vec<usize>], candidates: &Candidates) -> Graph {
+        let mut graph = topics
+            .iter()
+            .map(|topic| (candidates[topic[0]].0.to_string(), HashMap::new()))
+            .collect::<Graph>();
+
+        topics.iter().enumerate().for_each(|(i, topic1)| {
+            topics[i + 1..].iter().for_each(|topic2| {
+                let weight = Self::get_topic_weight(topic1, topic2, candidates);
+
+                if weight > 0.0 {
+                    let (key1, key2) = (&candidates[topic1[0]].0, &candidates[topic2[0]].0);
+                    graph
+                        .entry(key1.to_string())
+                        .or_default()
+                        .insert(key2.to_string(), weight);
+                    graph
+                        .entry(key2.to_string())
+                        .or_default()
+                        .insert(key1.to_string(), weight);
+                }
+            });
+        });
+
+        graph
+    }
+}
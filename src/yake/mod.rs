@@ -0,0 +1,162 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet};
+
+mod yake_logic;
+pub mod yake_params;
+pub use yake_logic::TermFeatures;
+use yake_logic::YakeLogic;
+pub use yake_params::{YakeFeatureWeights, YakeParams};
+
+use crate::{
+    common::{get_phrase_offsets, get_ranked_scores, get_ranked_strings, PUNCTUATION},
+    tokenizer::{SurfaceForms, Tokenize, Tokenizer},
+};
+
+pub struct Yake {
+    keyword_rank: HashMap<String, f32>,
+    term_features: HashMap<String, TermFeatures>,
+    offsets: HashMap<String, Vec<(usize, usize)>>,
+    surface_forms: SurfaceForms,
+}
+
+impl Yake {
+    /// Create a new Yake instance.
+    pub fn new(params: YakeParams) -> Self {
+        let (text, stop_words, punctuation, threshold, ngram, window_size, feature_weights) =
+            params.get_params();
+        let surface_forms = Tokenizer::new(text, stop_words, punctuation).get_surface_forms();
+        let stop_words = stop_words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect::<HashSet<String>>();
+        let punctuation = match punctuation {
+            Some(punctuation) => punctuation.iter().map(|s| s.to_string()).collect(),
+            None => PUNCTUATION.iter().map(|s| s.to_string()).collect(),
+        };
+
+        let (keyword_rank, term_features) = YakeLogic::build_yake(
+            YakeLogic::split_into_sentences(text, &stop_words, &punctuation),
+            threshold,
+            ngram,
+            window_size,
+            feature_weights,
+        );
+
+        let offsets = get_phrase_offsets(text, keyword_rank.keys());
+
+        Self {
+            keyword_rank,
+            term_features,
+            offsets,
+            surface_forms,
+        }
+    }
+
+    /// Create a new Yake instance splitting the text into sentences with a custom tokenizer, the
+    /// punctuation of the parameters is left to it. Every sentence is a single chunk, and words in the
+    /// stop words, which most tokenizers already drop, or shorter than 3 characters are stopwords.
+    pub fn with_tokenizer<T: Tokenize>(params: YakeParams, tokenizer: &T) -> Self {
+        let (text, stop_words, _, threshold, ngram, window_size, feature_weights) =
+            params.get_params();
+        let stop_words = stop_words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect::<HashSet<String>>();
+
+        let (keyword_rank, term_features) = YakeLogic::build_yake(
+            YakeLogic::split_tokenized_sentences(tokenizer.sentences(), &stop_words),
+            threshold,
+            ngram,
+            window_size,
+            feature_weights,
+        );
+
+        let offsets = get_phrase_offsets(text, keyword_rank.keys());
+
+        Self {
+            keyword_rank,
+            term_features,
+            offsets,
+            surface_forms: tokenizer.surface_forms(),
+        }
+    }
+
+    /// Gets the score of a keyword, YAKE scores are the lower the better so this is their inverse.
+    pub fn get_keyword_score(&self, keyword: &str) -> f32 {
+        *self.keyword_rank.get(keyword).unwrap_or(&0.0)
+    }
+
+    /// Gets the top n keywords with the highest score.
+    pub fn get_ranked_keywords(&self, n: usize) -> Vec<String> {
+        get_ranked_strings(&self.keyword_rank, n)
+    }
+
+    /// Gets the top n keywords with the highest score and their score.
+    pub fn get_ranked_keyword_scores(&self, n: usize) -> Vec<(String, f32)> {
+        get_ranked_scores(&self.keyword_rank, n)
+    }
+
+    /// Gets the top n keywords with the highest score and their score, written in the most frequent
+    /// form of their words in the text, e.g. "NASA" instead of "nasa".
+    pub fn get_ranked_surface_keyword_scores(&self, n: usize) -> Vec<(String, f32)> {
+        self.surface_forms
+            .apply(get_ranked_scores(&self.keyword_rank, n))
+    }
+
+    /// Gets the most frequent form of every word in the text.
+    pub fn get_surface_forms(&self) -> &SurfaceForms {
+        &self.surface_forms
+    }
+
+    /// Gets the features of every term of a ranked keyword, to understand why it ranked where it did.
+    pub fn explain(&self, keyword: &str) -> Option<Vec<(String, TermFeatures)>> {
+        if !self.keyword_rank.contains_key(keyword) {
+            return None;
+        }
+
+        keyword
+            .split_whitespace()
+            .map(|term| {
+                self.term_features
+                    .get(term)
+                    .map(|features| (term.to_string(), features.clone()))
+            })
+            .collect()
+    }
+
+    /// Gets the character offsets, as `(start, end)` with an exclusive end, of every occurrence of a
+    /// keyword in the original text.
+    pub fn get_keyword_offsets(&self, keyword: &str) -> &[(usize, usize)] {
+        self.offsets.get(keyword).map_or(&[], |offsets| offsets)
+    }
+
+    /// Gets the top n keywords with the highest score and the character offsets of their occurrences.
+    pub fn get_ranked_keyword_offsets(&self, n: usize) -> Vec<(String, Vec<(usize, usize)>)> {
+        get_ranked_strings(&self.keyword_rank, n)
+            .into_iter()
+            .map(|keyword| {
+                let offsets = self.get_keyword_offsets(&keyword).to_vec();
+                (keyword, offsets)
+            })
+            .collect()
+    }
+
+    /// Gets the keyword scores map.
+    pub fn get_keyword_scores_map(&self) -> &HashMap<String, f32> {
+        &self.keyword_rank
+    }
+}
@@ -0,0 +1,491 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::common::{get_special_char_regex, is_punctuation};
+
+use super::YakeFeatureWeights;
+
+/// How a token is written in the text.
+#[derive(Clone, Copy, PartialEq)]
+enum Tag {
+    /// A number.
+    Digit,
+    /// A token mixing letters and digits, or with no letters at all.
+    Unparsable,
+    /// A token written in upper case.
+    Acronym,
+    /// A token starting with an upper case letter that does not start the sentence.
+    Uppercase,
+    Parsable,
+}
+
+pub(crate) struct Token {
+    term: String,
+    tag: Tag,
+    is_stopword: bool,
+}
+
+impl Token {
+    fn new(word: &str, index: usize, stopwords: &HashSet<String>) -> Self {
+        let term = word.to_lowercase();
+        let is_stopword = stopwords.contains(&term) || term.chars().count() < 3;
+
+        Self {
+            tag: get_tag(word, index),
+            term,
+            is_stopword,
+        }
+    }
+
+    fn is_excluded(&self) -> bool {
+        self.tag == Tag::Digit || self.tag == Tag::Unparsable
+    }
+}
+
+/// The sentences of the text, split into chunks of tokens on punctuation.
+pub(crate) type Sentences = Vec<Vec<Vec<Token>>>;
+
+/// The YAKE features of a term, the lower the score the more important the term.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TermFeatures {
+    /// How often the term is written in upper case or as an acronym.
+    pub casing: f32,
+    /// How early the term appears, from the median of the indexes of the sentences it appears in.
+    pub position: f32,
+    /// The frequency of the term normalized by the mean and standard deviation of the frequencies.
+    pub frequency: f32,
+    /// How many different terms appear around the term, high values point to stopword-like terms.
+    pub relatedness: f32,
+    /// The fraction of the sentences the term appears in.
+    pub dispersion: f32,
+    /// `(position * relatedness) / (casing + frequency / relatedness + dispersion / relatedness)`, with the
+    /// feature weights applied.
+    pub score: f32,
+}
+
+#[derive(Default)]
+struct TermStats {
+    frequency: f32,
+    acronym_frequency: f32,
+    uppercase_frequency: f32,
+    sentences: BTreeSet<usize>,
+    is_stopword: bool,
+}
+
+/// The left to right co-occurrences of the terms, from the left term to the right term and back.
+#[derive(Default)]
+struct CoOccurrences<'a> {
+    right: HashMap<&'a str, HashMap<&'a str, f32>>,
+    left: HashMap<&'a str, HashMap<&'a str, f32>>,
+}
+
+impl<'a> CoOccurrences<'a> {
+    fn get(&self, left: &str, right: &str) -> f32 {
+        self.right
+            .get(left)
+            .and_then(|neighbours| neighbours.get(right))
+            .copied()
+            .unwrap_or(0.0)
+    }
+}
+
+fn get_tag(word: &str, index: usize) -> Tag {
+    if word.replace(',', "").parse::<f64>().is_ok() {
+        return Tag::Digit;
+    }
+
+    let digits = word.chars().filter(|c| c.is_numeric()).count();
+    let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+
+    if (digits > 0 && letters > 0) || letters == 0 {
+        return Tag::Unparsable;
+    }
+    if word.chars().all(|c| !c.is_alphabetic() || c.is_uppercase()) {
+        return Tag::Acronym;
+    }
+    if index > 0 && word.chars().next().is_some_and(|c| c.is_uppercase()) {
+        return Tag::Uppercase;
+    }
+
+    Tag::Parsable
+}
+
+/// The similarity of two strings, one minus their Levenshtein distance over the longest length.
+fn levenshtein_similarity(word1: &str, word2: &str) -> f32 {
+    let chars1 = word1.chars().collect::<Vec<char>>();
+    let chars2 = word2.chars().collect::<Vec<char>>();
+    let longest = chars1.len().max(chars2.len());
+
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous = (0..=chars2.len()).collect::<Vec<usize>>();
+    chars1.iter().enumerate().for_each(|(i, c1)| {
+        let mut current = vec![i + 1; chars2.len() + 1];
+        chars2.iter().enumerate().for_each(|(j, c2)| {
+            let substitution = previous[j] + usize::from(c1 != c2);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        });
+        previous = current;
+    });
+
+    1.0 - previous[chars2.len()] as f32 / longest as f32
+}
+
+pub struct YakeLogic;
+
+impl YakeLogic {
+    pub fn build_yake(
+        sentences: Sentences,
+        threshold: f32,
+        ngram: usize,
+        window_size: usize,
+        weights: YakeFeatureWeights,
+    ) -> (HashMap<String, f32>, HashMap<String, TermFeatures>) {
+        let co_occurrences = Self::get_co_occurrences(&sentences, window_size);
+        let term_scores = Self::get_term_scores(&sentences, &co_occurrences, weights);
+        let candidates = Self::score_candidates(
+            Self::get_candidates(&sentences, ngram),
+            &term_scores,
+            &co_occurrences,
+        );
+
+        let keyword_rank = Self::deduplicate(candidates, threshold)
+            .into_iter()
+            .map(|(candidate, score)| (candidate, 1.0 / score))
+            .collect::<HashMap<String, f32>>();
+        let term_features = term_scores
+            .into_iter()
+            .map(|(term, (features, _))| (term.to_string(), features))
+            .collect::<HashMap<String, TermFeatures>>();
+
+        (keyword_rank, term_features)
+    }
+
+    pub fn split_into_sentences(
+        text: &str,
+        stopwords: &HashSet<String>,
+        punctuation: &HashSet<String>,
+    ) -> Sentences {
+        let special_char_regex = get_special_char_regex();
+
+        text.unicode_sentences()
+            .map(|sentence| {
+                Self::split_into_chunks(sentence, stopwords, punctuation, special_char_regex)
+            })
+            .filter(|chunks| !chunks.is_empty())
+            .collect::<Sentences>()
+    }
+
+    fn split_into_chunks(
+        sentence: &str,
+        stopwords: &HashSet<String>,
+        punctuation: &HashSet<String>,
+        special_char_regex: &Regex,
+    ) -> Vec<Vec<Token>> {
+        let mut chunks = vec![Vec::<Token>::new()];
+        let mut index = 0_usize;
+
+        sentence.split_word_bounds().for_each(|w| {
+            if w.trim().is_empty() {
+                return;
+            }
+
+            let word = special_char_regex.replace_all(w.trim(), "");
+
+            if is_punctuation(&word, punctuation) || !word.chars().any(|c| c.is_alphanumeric()) {
+                if chunks.last().is_some_and(|chunk| !chunk.is_empty()) {
+                    chunks.push(Vec::new());
+                }
+                return;
+            }
+
+            chunks
+                .last_mut()
+                .unwrap()
+                .push(Token::new(&word, index, stopwords));
+            index += 1;
+        });
+
+        chunks.retain(|chunk| !chunk.is_empty());
+        chunks
+    }
+
+    /// Turns the sentences of a custom tokenizer into a single chunk each, their casing is kept for
+    /// the casing feature if the tokenizer keeps it.
+    pub fn split_tokenized_sentences(
+        sentences: Vec<String>,
+        stopwords: &HashSet<String>,
+    ) -> Sentences {
+        sentences
+            .iter()
+            .map(|sentence| {
+                vec![sentence
+                    .split_whitespace()
+                    .enumerate()
+                    .map(|(index, word)| Token::new(word, index, stopwords))
+                    .collect::<Vec<Token>>()]
+            })
+            .filter(|chunks| !chunks[0].is_empty())
+            .collect::<Sentences>()
+    }
+
+    fn get_co_occurrences(sentences: &Sentences, window_size: usize) -> CoOccurrences<'_> {
+        let mut co_occurrences = CoOccurrences::default();
+
+        sentences.iter().flatten().for_each(|chunk| {
+            chunk.iter().enumerate().for_each(|(i, token)| {
+                if token.is_excluded() {
+                    return;
+                }
+
+                chunk[i.saturating_sub(window_size)..i]
+                    .iter()
+                    .filter(|left| !left.is_excluded())
+                    .for_each(|left| {
+                        *co_occurrences
+                            .right
+                            .entry(left.term.as_str())
+                            .or_default()
+                            .entry(token.term.as_str())
+                            .or_insert(0.0) += 1.0;
+                        *co_occurrences
+                            .left
+                            .entry(token.term.as_str())
+                            .or_default()
+                            .entry(left.term.as_str())
+                            .or_insert(0.0) += 1.0;
+                    });
+            });
+        });
+
+        co_occurrences
+    }
+
+    fn get_term_stats(sentences: &Sentences) -> HashMap<&str, TermStats> {
+        let mut stats = HashMap::<&str, TermStats>::new();
+
+        sentences.iter().enumerate().for_each(|(i, chunks)| {
+            chunks
+                .iter()
+                .flatten()
+                .filter(|token| !token.is_excluded())
+                .for_each(|token| {
+                    let term_stats = stats.entry(token.term.as_str()).or_default();
+                    term_stats.frequency += 1.0;
+                    term_stats.sentences.insert(i);
+                    term_stats.is_stopword = token.is_stopword;
+
+                    match token.tag {
+                        Tag::Acronym => term_stats.acronym_frequency += 1.0,
+                        Tag::Uppercase => term_stats.uppercase_frequency += 1.0,
+                        _ => {}
+                    }
+                });
+        });
+
+        stats
+    }
+
+    /// The features of every term, see [`TermFeatures`].
+    fn get_term_scores<'a>(
+        sentences: &'a Sentences,
+        co_occurrences: &CoOccurrences,
+        weights: YakeFeatureWeights,
+    ) -> HashMap<&'a str, (TermFeatures, TermStats)> {
+        let stats = Self::get_term_stats(sentences);
+        let valid_frequencies = stats
+            .values()
+            .filter(|term| !term.is_stopword)
+            .map(|term| term.frequency)
+            .collect::<Vec<f32>>();
+        let max_frequency = stats
+            .values()
+            .fold(0.0_f32, |max, term| max.max(term.frequency));
+        let mean = valid_frequencies.iter().sum::<f32>() / valid_frequencies.len().max(1) as f32;
+        let std = (valid_frequencies
+            .iter()
+            .map(|frequency| (frequency - mean).powi(2))
+            .sum::<f32>()
+            / valid_frequencies.len().max(1) as f32)
+            .sqrt();
+        let total_sentences = sentences.len() as f32;
+        let get_side_weight = |neighbours: Option<&HashMap<&str, f32>>| {
+            neighbours.map_or(0.0, |neighbours| {
+                neighbours.len() as f32 / neighbours.values().sum::<f32>()
+            })
+        };
+
+        stats
+            .into_iter()
+            .map(|(term, term_stats)| {
+                let frequency = term_stats.frequency;
+                let left = get_side_weight(co_occurrences.left.get(term));
+                let right = get_side_weight(co_occurrences.right.get(term));
+                let relatedness = (0.5 + left * frequency / max_frequency)
+                    + (0.5 + right * frequency / max_frequency);
+                let casing = term_stats
+                    .acronym_frequency
+                    .max(term_stats.uppercase_frequency)
+                    / (1.0 + frequency.ln());
+                let position = (3.0 + Self::median(&term_stats.sentences)).ln().ln();
+                let frequency_weight = frequency / (mean + std);
+                let dispersion = term_stats.sentences.len() as f32 / total_sentences;
+                let weighted_relatedness = relatedness.powf(weights.relatedness);
+                let score = (position.powf(weights.position) * weighted_relatedness)
+                    / (weights.casing * casing
+                        + weights.frequency * frequency_weight / weighted_relatedness
+                        + weights.dispersion * dispersion / weighted_relatedness)
+                        .max(f32::EPSILON);
+                let features = TermFeatures {
+                    casing,
+                    position,
+                    frequency: frequency_weight,
+                    relatedness,
+                    dispersion,
+                    score,
+                };
+                (term, (features, term_stats))
+            })
+            .collect()
+    }
+
+    fn median(sentences: &BTreeSet<usize>) -> f32 {
+        let values = sentences.iter().collect::<Vec<&usize>>();
+        let middle = values.len() / 2;
+
+        if values.len() % 2 == 0 {
+            (*values[middle - 1] + *values[middle]) as f32 / 2.0
+        } else {
+            *values[middle] as f32
+        }
+    }
+
+    /// Gets the n-gram candidates that neither start nor end with a stopword, nor contain numbers,
+    /// in order of first appearance with their frequency.
+    fn get_candidates(sentences: &Sentences, ngram: usize) -> Vec<(Vec<&Token>, f32)> {
+        let mut candidate_indexes = HashMap::<String, usize>::new();
+        let mut candidates = Vec::<(Vec<&Token>, f32)>::new();
+
+        sentences.iter().flatten().for_each(|chunk| {
+            (0..chunk.len()).for_each(|start| {
+                (start + 1..=(start + ngram).min(chunk.len()))
+                    .map(|end| &chunk[start..end])
+                    .filter(|tokens| {
+                        !tokens[0].is_stopword
+                            && !tokens[tokens.len() - 1].is_stopword
+                            && tokens.iter().all(|token| !token.is_excluded())
+                    })
+                    .for_each(|tokens| {
+                        let key = tokens
+                            .iter()
+                            .map(|token| token.term.as_str())
+                            .collect::<Vec<&str>>()
+                            .join(" ");
+
+                        match candidate_indexes.get(&key) {
+                            Some(i) => candidates[*i].1 += 1.0,
+                            None => {
+                                candidate_indexes.insert(key, candidates.len());
+                                candidates.push((tokens.iter().collect(), 1.0));
+                            }
+                        }
+                    });
+            });
+        });
+
+        candidates
+    }
+
+    /// The score of every candidate: the product of its term scores over its frequency times one plus their sum,
+    /// interior stopwords are weighted by the probability of their co-occurrence with the adjacent terms.
+    fn score_candidates(
+        candidates: Vec<(Vec<&Token>, f32)>,
+        term_scores: &HashMap<&str, (TermFeatures, TermStats)>,
+        co_occurrences: &CoOccurrences,
+    ) -> Vec<(String, f32)> {
+        let score_candidate = |(tokens, frequency): &(Vec<&Token>, f32)| {
+            let (product, sum) =
+                tokens
+                    .iter()
+                    .enumerate()
+                    .fold((1.0_f32, 0.0_f32), |(product, sum), (i, token)| {
+                        if !token.is_stopword {
+                            let score = term_scores[token.term.as_str()].0.score;
+                            return (product * score, sum + score);
+                        }
+
+                        let previous = &tokens[i - 1].term;
+                        let next = &tokens[i + 1].term;
+                        let left_probability = co_occurrences.get(previous, &token.term)
+                            / term_scores[previous.as_str()].1.frequency;
+                        let right_probability = co_occurrences.get(&token.term, next)
+                            / term_scores[next.as_str()].1.frequency;
+                        let probability = left_probability * right_probability;
+                        (product * (2.0 - probability), sum - (1.0 - probability))
+                    });
+            let key = tokens
+                .iter()
+                .map(|token| token.term.as_str())
+                .collect::<Vec<&str>>()
+                .join(" ");
+            (key, product / ((sum + 1.0) * frequency))
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            candidates
+                .par_iter()
+                .map(score_candidate)
+                .collect::<Vec<(String, f32)>>()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            candidates
+                .iter()
+                .map(score_candidate)
+                .collect::<Vec<(String, f32)>>()
+        }
+    }
+
+    /// Drops the candidates whose similarity with a better ranked candidate is above the threshold.
+    fn deduplicate(mut candidates: Vec<(String, f32)>, threshold: f32) -> Vec<(String, f32)> {
+        candidates.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+        candidates.into_iter().fold(
+            Vec::<(String, f32)>::new(),
+            |mut selected, (candidate, score)| {
+                if selected
+                    .iter()
+                    .all(|(other, _)| levenshtein_similarity(&candidate, other) <= threshold)
+                {
+                    selected.push((candidate, score));
+                }
+
+                selected
+            },
+        )
+    }
+}
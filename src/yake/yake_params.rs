@@ -0,0 +1,170 @@
+// Copyright (C) 2023 Afonso Barracha
+//
+// Rust Keyword Extraction is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Rust Keyword Extraction is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with Rust Keyword Extraction. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::common::{Punctuation, Stopwords, Text, WindowSize};
+
+type DeduplicationThreshold = f32;
+type NgramSize = usize;
+
+/// The weights of the YAKE term features, a weight of 0.0 disables the feature and 1.0 keeps it as is.
+/// Casing, frequency and dispersion weights scale the feature, while position and relatedness weights,
+/// being multiplicative, are used as exponents.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct YakeFeatureWeights {
+    pub casing: f32,
+    pub position: f32,
+    pub frequency: f32,
+    pub relatedness: f32,
+    pub dispersion: f32,
+}
+
+impl Default for YakeFeatureWeights {
+    fn default() -> Self {
+        Self {
+            casing: 1.0,
+            position: 1.0,
+            frequency: 1.0,
+            relatedness: 1.0,
+            dispersion: 1.0,
+        }
+    }
+}
+
+/// The parameters to be used in the YAKE algorithm.
+pub enum YakeParams<'a> {
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `threshold` - The Levenshtein similarity above which a candidate is a duplicate of a better ranked one, defaults to 0.9.
+    /// * `ngram` - The maximum number of words of a candidate, defaults to 3.
+    /// * `window_size` - The number of neighbouring words counted in the relatedness feature, defaults to 1.
+    /// * `feature_weights` - The weights of the term features, defaults to 1.0 for every feature.
+    WithDefaults(Text<'a>, Stopwords<'a>),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `ngram` - The maximum number of words of a candidate, from 1 to 5.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `threshold` - The Levenshtein similarity above which a candidate is a duplicate of a better ranked one, defaults to 0.9.
+    WithDefaultsAndNgram(Text<'a>, Stopwords<'a>, NgramSize),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `window_size` - The number of neighbouring words counted in the relatedness feature, at least 1.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `threshold` - The Levenshtein similarity above which a candidate is a duplicate of a better ranked one, defaults to 0.9.
+    /// * `ngram` - The maximum number of words of a candidate, defaults to 3.
+    WithDefaultsAndWindowSize(Text<'a>, Stopwords<'a>, WindowSize),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `feature_weights` - The weights of the term features, e.g. a casing of 0.0 for lowercase text.
+    /// ### Default values:
+    /// * `punctuation` - A list of punctuation symbols, defaults to those in Latin and Germanic languages.
+    /// * `threshold` - The Levenshtein similarity above which a candidate is a duplicate of a better ranked one, defaults to 0.9.
+    /// * `ngram` - The maximum number of words of a candidate, defaults to 3.
+    WithDefaultsAndFeatureWeights(Text<'a>, Stopwords<'a>, YakeFeatureWeights),
+
+    /// ## Arguments
+    /// 1. `text` - The text to be analyzed.
+    /// 2. `stop_words` - A list of stop words.
+    /// 3. `punctuation` - Optional list of punctuation symbols.
+    /// 4. `threshold` - The Levenshtein similarity above which a candidate is a duplicate of a better ranked one,
+    ///    1.0 disables the deduplication.
+    /// 5. `ngram` - The maximum number of words of a candidate, from 1 to 5.
+    /// 6. `window_size` - The number of neighbouring words counted in the relatedness feature, at least 1.
+    /// 7. `feature_weights` - The weights of the term features.
+    All(
+        Text<'a>,
+        Stopwords<'a>,
+        Punctuation<'a>,
+        DeduplicationThreshold,
+        NgramSize,
+        WindowSize,
+        YakeFeatureWeights,
+    ),
+}
+
+impl<'a> YakeParams<'a> {
+    /// Returns the params to be used in the YAKE algorithm.
+    pub fn get_params(
+        &self,
+    ) -> (
+        Text<'_>,
+        Stopwords<'_>,
+        Punctuation<'_>,
+        DeduplicationThreshold,
+        NgramSize,
+        WindowSize,
+        YakeFeatureWeights,
+    ) {
+        match self {
+            YakeParams::WithDefaults(text, stop_words) => (
+                text,
+                stop_words,
+                None,
+                0.9,
+                3,
+                1,
+                YakeFeatureWeights::default(),
+            ),
+            YakeParams::WithDefaultsAndNgram(text, stop_words, ngram) => (
+                text,
+                stop_words,
+                None,
+                0.9,
+                (*ngram).clamp(1, 5),
+                1,
+                YakeFeatureWeights::default(),
+            ),
+            YakeParams::WithDefaultsAndWindowSize(text, stop_words, window_size) => (
+                text,
+                stop_words,
+                None,
+                0.9,
+                3,
+                (*window_size).max(1),
+                YakeFeatureWeights::default(),
+            ),
+            YakeParams::WithDefaultsAndFeatureWeights(text, stop_words, feature_weights) => {
+                (text, stop_words, None, 0.9, 3, 1, *feature_weights)
+            }
+            YakeParams::All(
+                text,
+                stop_words,
+                punctuation,
+                threshold,
+                ngram,
+                window_size,
+                feature_weights,
+            ) => (
+                text,
+                stop_words,
+                *punctuation,
+                *threshold,
+                (*ngram).clamp(1, 5),
+                (*window_size).max(1),
+                *feature_weights,
+            ),
+        }
+    }
+}